@@ -2,6 +2,8 @@ use num_integer::Integer;
 use num_bigint::BigInt;
 use num_traits::Zero;
 use num_traits::One;
+use num_traits::Signed;
+use num_traits::ToPrimitive;
 
 /// T^n
 /// NOTE: BigInt::Pow is not enough functionality, so implement by myself.
@@ -110,6 +112,134 @@ pub fn extended_gcd(a: BigInt, b: BigInt) -> (BigInt, BigInt, BigInt) {
     (g, y - q * x.clone(), x.clone())
 }
 
+/// big endian bytes of non negative n, left padded with zeros to len bytes
+pub fn to_bytes_be(n: &BigInt, len: usize) -> Vec<u8> {
+    let (_, bytes) = n.to_bytes_be();
+    let mut v = vec![0u8; len.saturating_sub(bytes.len())];
+    v.extend_from_slice(&bytes);
+    v
+}
+
+/// a non trivial factor of odd composite n by Pollard rho with Brent's cycle detection
+fn pollard_brent(n: &BigInt) -> BigInt {
+    let mut c = BigInt::one();
+    loop {
+        let f = |x: &BigInt| (x * x + &c).mod_floor(n);
+        let mut y = BigInt::from(2);
+        let mut r = 1usize;
+        let mut q = BigInt::one();
+        let mut g = BigInt::one();
+        let mut x = y.clone();
+        let mut ys = y.clone();
+        while g.is_one() {
+            x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && g.is_one() {
+                ys = y.clone();
+                for _ in 0..std::cmp::min(128, r - k) {
+                    y = f(&y);
+                    q = (q * (&x - &y).abs()).mod_floor(n);
+                }
+                g = q.gcd(n);
+                k += 128;
+            }
+            r *= 2;
+        }
+        if &g == n {
+            // backtrack one step at a time
+            loop {
+                ys = f(&ys);
+                g = (&x - &ys).abs().gcd(n);
+                if !g.is_one() {
+                    break;
+                }
+            }
+        }
+        if &g != n {
+            return g;
+        }
+        c += 1;
+    }
+}
+
+/// prime factorization of n > 0 as (prime, exponent) in ascending order
+pub fn factorize(n: &BigInt) -> Vec<(BigInt, u32)> {
+    assert!(n.is_positive(), "n must be positive");
+    let mut factors: Vec<BigInt> = Vec::new();
+    let mut n = n.clone();
+    for sp in 2u32..1000 {
+        let sp = BigInt::from(sp);
+        while n.is_multiple_of(&sp) {
+            n /= &sp;
+            factors.push(sp.clone());
+        }
+    }
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m.is_one() {
+            continue;
+        }
+        if is_probable_prime(&m) {
+            factors.push(m);
+            continue;
+        }
+        let r = m.sqrt();
+        if &r * &r == m {
+            stack.push(r.clone());
+            stack.push(r);
+            continue;
+        }
+        let d = pollard_brent(&m);
+        stack.push(&m / &d);
+        stack.push(d);
+    }
+    factors.sort();
+    let mut result: Vec<(BigInt, u32)> = Vec::new();
+    for f in factors {
+        match result.last_mut() {
+            Some((q, e)) if *q == f => *e += 1,
+            _ => result.push((f, 1)),
+        }
+    }
+    result
+}
+
+/// order of a group element from a multiple m of it
+/// is_identity(d) tells whether [d] P = O, the smallest such divisor of m is returned
+pub fn order_from_multiple<F: Fn(&BigInt) -> bool>(m: &BigInt, is_identity: F) -> BigInt {
+    let mut order = m.clone();
+    for (q, e) in factorize(m) {
+        for _ in 0..e {
+            let d = &order / &q;
+            if is_identity(&d) {
+                order = d;
+            } else {
+                break;
+            }
+        }
+    }
+    order
+}
+
+/// uniformly random integer in [0, n)
+pub fn random_below<R: rand::Rng + ?Sized>(rng: &mut R, n: &BigInt) -> BigInt {
+    assert!(n.is_positive(), "n must be positive");
+    let bits = n.bits();
+    let mut bytes = vec![0u8; bits.div_ceil(8)];
+    loop {
+        rng.fill_bytes(&mut bytes);
+        let excess = bytes.len() * 8 - bits;
+        bytes[0] &= 0xff >> excess;
+        let r = BigInt::from_bytes_be(num_bigint::Sign::Plus, &bytes);
+        if &r < n {
+            return r;
+        }
+    }
+}
+
 /// x (mod l) = r
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct ModResult {
@@ -134,6 +264,269 @@ pub fn chinese_remainder(mod_result: &Vec<ModResult>) -> ModResult {
     ModResult { l, r }
 }
 
+/// Jacobi symbol (a/n) for odd n > 0
+pub fn jacobi(a: &BigInt, n: &BigInt) -> i32 {
+    let mut a = a.mod_floor(n);
+    let mut n = n.clone();
+    let mut t = 1;
+    while !a.is_zero() {
+        while a.is_even() {
+            a /= 2;
+            let r = n.mod_floor(&BigInt::from(8));
+            if r == BigInt::from(3) || r == BigInt::from(5) {
+                t = -t;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a.mod_floor(&BigInt::from(4)) == BigInt::from(3) && n.mod_floor(&BigInt::from(4)) == BigInt::from(3) {
+            t = -t;
+        }
+        a = a.mod_floor(&n);
+    }
+    if n.is_one() { t } else { 0 }
+}
+
+/// Legendre symbol (a/p) for odd prime p
+/// 1 if a is a non zero square, -1 if a is not a square, 0 if p | a
+pub fn legendre(a: &BigInt, p: &BigInt) -> i32 {
+    jacobi(a, p)
+}
+
+/// square root of a modulo prime p, None if a is not a square
+///
+/// p = 3 (mod 4) and p = 5 (mod 8) are computed directly,
+/// otherwise Tonelli-Shanks, or Cipolla when p - 1 has many factors of 2.
+pub fn sqrt_modulo(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = a.mod_floor(p);
+    if a.is_zero() || p == &BigInt::from(2) {
+        return Some(a);
+    }
+    if legendre(&a, p) != 1 {
+        return None;
+    }
+    match p.mod_floor(&BigInt::from(8)).to_u32() {
+        Some(3) | Some(7) => {
+            return Some(a.power_modulo(&((p + 1) >> 2usize), p));
+        }
+        Some(5) => {
+            // Atkin: v = (2a)^((p-5)/8), i = 2 a v^2, r = a v (i - 1)
+            let a2: BigInt = &a * 2;
+            let v = a2.power_modulo(&((p - 5) >> 3usize), p);
+            let i = (&a2 * &v * &v).mod_floor(p);
+            return Some((&a * v * (i - BigInt::one())).mod_floor(p));
+        }
+        _ => {}
+    }
+    let s = two_adic_valuation(&(p - 1));
+    // Tonelli-Shanks needs O(s^2) multiplications, Cipolla O(log p)
+    if s * (s - 1) > 8 * p.bits() + 20 {
+        cipolla(&a, p)
+    } else {
+        tonelli_shanks(&a, p)
+    }
+}
+
+/// largest s such that 2^s | n, n > 0
+fn two_adic_valuation(n: &BigInt) -> usize {
+    let mut n = n.clone();
+    let mut s = 0;
+    while n.is_even() {
+        n >>= 1usize;
+        s += 1;
+    }
+    s
+}
+
+/// square root of a modulo odd prime p by Tonelli-Shanks
+pub fn tonelli_shanks(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = a.mod_floor(p);
+    if a.is_zero() {
+        return Some(a);
+    }
+    if legendre(&a, p) != 1 {
+        return None;
+    }
+    // p - 1 = q 2^s
+    let s = two_adic_valuation(&(p - 1));
+    let q: BigInt = (p - 1) >> s;
+    let mut z = BigInt::from(2);
+    while legendre(&z, p) != -1 {
+        z += 1;
+    }
+    let one = BigInt::one();
+    let mut m = s;
+    let mut c = z.power_modulo(&q, p);
+    let mut t = a.power_modulo(&q, p);
+    let mut r = a.power_modulo(&((&q + 1) >> 1usize), p);
+    while t != one {
+        let mut i = 0;
+        let mut t2 = t.clone();
+        while t2 != one {
+            t2 = (&t2 * &t2).mod_floor(p);
+            i += 1;
+        }
+        let mut b = c;
+        for _ in 0..(m - i - 1) {
+            b = (&b * &b).mod_floor(p);
+        }
+        m = i;
+        c = (&b * &b).mod_floor(p);
+        t = (t * &c).mod_floor(p);
+        r = (r * b).mod_floor(p);
+    }
+    Some(r)
+}
+
+/// square root of a modulo odd prime p by Cipolla
+///
+/// (t + w)^((p+1)/2) in F_p[w] / (w^2 - (t^2 - a)) where t^2 - a is not a square
+pub fn cipolla(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    let a = a.mod_floor(p);
+    if a.is_zero() {
+        return Some(a);
+    }
+    if legendre(&a, p) != 1 {
+        return None;
+    }
+    let mut t = BigInt::one();
+    let w2 = loop {
+        let w2 = (&t * &t - &a).mod_floor(p);
+        if legendre(&w2, p) == -1 {
+            break w2;
+        }
+        t += 1;
+    };
+    // (x0 + x1 w) (y0 + y1 w)
+    let mul = |x: &(BigInt, BigInt), y: &(BigInt, BigInt)| {
+        ((&x.0 * &y.0 + &x.1 * &y.1 * &w2).mod_floor(p),
+         (&x.0 * &y.1 + &x.1 * &y.0).mod_floor(p))
+    };
+    let mut r = (BigInt::one(), BigInt::zero());
+    let mut b = (t, BigInt::one());
+    let mut e: BigInt = (p + 1) >> 1usize;
+    while !e.is_zero() {
+        if e.is_odd() {
+            r = mul(&r, &b);
+        }
+        b = mul(&b, &b);
+        e >>= 1usize;
+    }
+    Some(r.0)
+}
+
+/// strong probable prime test to base 2
+fn is_strong_probable_prime_base2(n: &BigInt) -> bool {
+    let n1: BigInt = n - 1;
+    let mut d = n1.clone();
+    let mut s = 0;
+    while d.is_even() {
+        d /= 2;
+        s += 1;
+    }
+    let mut x = BigInt::from(2).power_modulo(&d, n);
+    if x.is_one() || x == n1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = (&x * &x).mod_floor(n);
+        if x == n1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// x / 2 (mod n) for odd n
+fn half_modulo(x: BigInt, n: &BigInt) -> BigInt {
+    let x: BigInt = if x.is_odd() { x + n } else { x };
+    (x / BigInt::from(2)).mod_floor(n)
+}
+
+/// strong Lucas probable prime test with Selfridge's parameters
+/// n must be odd and not a perfect square
+fn is_strong_lucas_probable_prime(n: &BigInt) -> bool {
+    // D = 5, -7, 9, -11, ... such that (D/n) = -1
+    let mut d = BigInt::from(5);
+    loop {
+        match jacobi(&d, n) {
+            -1 => break,
+            0 if &d.abs() != n => return false,
+            _ => {}
+        }
+        d = if d.is_positive() { -(d + BigInt::from(2)) } else { -(d - BigInt::from(2)) };
+    }
+    let q: BigInt = (BigInt::from(1) - &d) / 4;
+
+    // n + 1 = k 2^s, k odd
+    let mut k: BigInt = n + 1;
+    let mut s = 0;
+    while k.is_even() {
+        k /= 2;
+        s += 1;
+    }
+
+    // U_1 = 1, V_1 = P = 1
+    let mut u: BigInt = One::one();
+    let mut v: BigInt = One::one();
+    let mut qk = q.mod_floor(n);
+    for bit in k.to_str_radix(2).chars().skip(1) {
+        u = (&u * &v).mod_floor(n);
+        v = (&v * &v - &qk * BigInt::from(2)).mod_floor(n);
+        qk = (&qk * &qk).mod_floor(n);
+        if bit == '1' {
+            let u1 = half_modulo(&u + &v, n);
+            let v1 = half_modulo(&d * &u + &v, n);
+            u = u1;
+            v = v1;
+            qk = (&qk * &q).mod_floor(n);
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = (&v * &v - &qk * BigInt::from(2)).mod_floor(n);
+        if v.is_zero() {
+            return true;
+        }
+        qk = (&qk * &qk).mod_floor(n);
+    }
+    false
+}
+
+/// Baillie-PSW probable prime test
+///
+/// trial division, strong probable prime test to base 2 and strong Lucas test.
+/// No composite number passing this test is known.
+pub fn is_probable_prime(n: &BigInt) -> bool {
+    const SMALL_PRIMES: [u32; 25] = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41,
+        43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97];
+    if n < &BigInt::from(2) {
+        return false;
+    }
+    for &sp in SMALL_PRIMES.iter() {
+        let sp = BigInt::from(sp);
+        if n == &sp {
+            return true;
+        }
+        if n.is_multiple_of(&sp) {
+            return false;
+        }
+    }
+    if n < &BigInt::from(97 * 97) {
+        return true;
+    }
+    if !is_strong_probable_prime_base2(n) {
+        return false;
+    }
+    let r = n.sqrt();
+    if &(&r * &r) == n {
+        return false;
+    }
+    is_strong_lucas_probable_prime(n)
+}
+
 #[test]
 fn bigint_power_test() {
     let q = BigInt::from(2);
@@ -237,3 +630,95 @@ fn chinese_remainder_test() {
                });
 }
 
+
+#[test]
+fn is_probable_prime_test() {
+    let primes: Vec<u64> = (0..2000).filter(|&n| primes::is_prime(n)).collect();
+    for n in 0..2000 {
+        assert_eq!(is_probable_prime(&BigInt::from(n)), primes.contains(&n), "n:{}", n);
+    }
+    // Carmichael numbers and strong pseudoprimes to base 2
+    for &n in [561u64, 1105, 1729, 2047, 3277, 4033, 4681, 8321, 3_215_031_751, 3_825_123_056_546_413_051].iter() {
+        assert!(!is_probable_prime(&BigInt::from(n)), "n:{}", n);
+    }
+    assert!(is_probable_prime(&BigInt::from(4_294_967_291u64)));
+    assert!(is_probable_prime(&(BigInt::from(2).power(127) - 1)));
+    assert!(!is_probable_prime(&(BigInt::from(2).power(128) + 1)));
+    let p = BigInt::from(2).power(256) - BigInt::from(2).power(32) - BigInt::from(977);
+    assert!(is_probable_prime(&p));
+    assert!(!is_probable_prime(&(&p * &p)));
+    assert!(!is_probable_prime(&(&p * (BigInt::from(2).power(127) - 1))));
+}
+
+#[test]
+fn jacobi_test() {
+    assert_eq!(legendre(&BigInt::from(0), &BigInt::from(7)), 0);
+    assert_eq!(legendre(&BigInt::from(-1), &BigInt::from(7)), -1);
+    assert_eq!(legendre(&BigInt::from(-1), &BigInt::from(13)), 1);
+    assert_eq!(jacobi(&BigInt::from(2), &BigInt::from(7)), 1);
+    assert_eq!(jacobi(&BigInt::from(3), &BigInt::from(7)), -1);
+    assert_eq!(jacobi(&BigInt::from(-7), &BigInt::from(15)), 1);
+    assert_eq!(jacobi(&BigInt::from(5), &BigInt::from(15)), 0);
+}
+
+#[test]
+fn sqrt_modulo_test() {
+    // p = 3 mod 4, 5 mod 8, 9 mod 16, 17 mod 32 and 1 mod 64
+    for &p in [19u64, 29, 41, 113, 257, 193, 7681, 12289].iter() {
+        let p = BigInt::from(p);
+        let mut squares = 0;
+        for a in num_iter::range(BigInt::from(0), p.clone()) {
+            let l = legendre(&a, &p);
+            let r = sqrt_modulo(&a, &p);
+            assert_eq!(r.is_some(), l != -1, "a:{} p:{}", a, p);
+            if let Some(r) = r {
+                assert_eq!((&r * &r).mod_floor(&p), a, "p:{}", p);
+                let c = cipolla(&a, &p).unwrap();
+                assert_eq!((&c * &c).mod_floor(&p), a, "p:{}", p);
+                let t = tonelli_shanks(&a, &p).unwrap();
+                assert_eq!((&t * &t).mod_floor(&p), a, "p:{}", p);
+                if l == 1 {
+                    squares += 1;
+                }
+            }
+        }
+        assert_eq!(BigInt::from(squares), (&p - 1) / 2, "p:{}", p);
+    }
+    // 2^255 - 19 = 5 mod 8, 2^224 - 2^96 + 1 has 2^96 | p - 1
+    let p = BigInt::from(2).power(255) - 19;
+    let r = sqrt_modulo(&BigInt::from(4), &p).unwrap();
+    assert!(r == BigInt::from(2) || r == &p - 2);
+    let p = BigInt::from(2).power(224) - BigInt::from(2).power(96) + 1;
+    let a = BigInt::from(123456789).power(2).mod_floor(&p);
+    let r = sqrt_modulo(&a, &p).unwrap();
+    assert_eq!((&r * &r).mod_floor(&p), a);
+    assert_eq!(sqrt_modulo(&(&p - 1), &p).map(|r| (&r * &r).mod_floor(&p)), Some(&p - 1));
+}
+
+#[test]
+fn random_below_test() {
+    let mut rng = rand::thread_rng();
+    let n = BigInt::from(5);
+    let mut counts = [0; 5];
+    for _ in 0..1000 {
+        counts[random_below(&mut rng, &n).to_usize().unwrap()] += 1;
+    }
+    assert!(counts.iter().all(|&c| c > 100));
+    let n = BigInt::from(2).power(255) - 19;
+    assert!(random_below(&mut rng, &n) < n);
+}
+
+#[test]
+fn factorize_test() {
+    let show = |n: &BigInt| factorize(n).iter()
+        .map(|(q, e)| if *e == 1 { q.to_string() } else { format!("{}^{}", q, e) })
+        .collect::<Vec<String>>()
+        .join(" ");
+    assert_eq!(show(&BigInt::from(1)), "");
+    assert_eq!(show(&BigInt::from(360)), "2^3 3^2 5");
+    assert_eq!(show(&BigInt::from(1_000_003u64 * 1_000_033)), "1000003 1000033");
+    assert_eq!(show(&(BigInt::from(1_000_003u64).power(2) * 7)), "7 1000003^2");
+    // 2^64 + 1 = 274177 * 67280421310721
+    assert_eq!(show(&(BigInt::from(2).power(64) + 1)), "274177 67280421310721");
+    assert_eq!(show(&BigInt::from(4_294_967_291u64)), "4294967291");
+}
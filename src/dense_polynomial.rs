@@ -0,0 +1,798 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+use std::{fmt, ops};
+use super::bigint::{self, Power};
+use super::error::{Error, Result};
+use super::fp;
+use super::multiplication;
+use super::polynomial;
+use super::term_builder;
+use super::term_builder::TermBuildable;
+
+type Polynomial = polynomial::Polynomial;
+
+/// Dense univariate polynomial in x over F_p
+/// coefs[i] is the coefficient of x^i in [0, p),
+/// the highest coefficient is not zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DensePolynomial {
+    coefs: Vec<BigInt>,
+    field: fp::PrimeField,
+}
+
+fn try_common_field(a: &DensePolynomial, b: &DensePolynomial) -> Result<fp::PrimeField> {
+    if a.field != b.field {
+        return Err(Error::MismatchedField);
+    }
+    Ok(a.field.clone())
+}
+
+fn common_field(a: &DensePolynomial, b: &DensePolynomial) -> fp::PrimeField {
+    assert!(a.field == b.field, "mismatched field {} {}", a.field, b.field);
+    a.field.clone()
+}
+
+// DensePolynomial + DensePolynomial
+impl_op_ex!(+ |a: &DensePolynomial, b: &DensePolynomial| -> DensePolynomial {
+    let field = common_field(a, b);
+    let n = a.coefs.len().max(b.coefs.len());
+    let mut coefs: Vec<BigInt> = Vec::with_capacity(n);
+    for i in 0..n {
+        let mut c = BigInt::zero();
+        if i < a.coefs.len() {
+            c += &a.coefs[i];
+        }
+        if i < b.coefs.len() {
+            c += &b.coefs[i];
+        }
+        coefs.push(c);
+    }
+    DensePolynomial::new(&field, coefs)
+});
+
+// DensePolynomial - DensePolynomial
+impl_op_ex!(- |a: &DensePolynomial, b: &DensePolynomial| -> DensePolynomial {
+    a + (-b)
+});
+
+// Negate
+impl_op_ex!(- |a: &DensePolynomial| -> DensePolynomial {
+    let coefs = a.coefs.iter().map(|c| -c).collect();
+    DensePolynomial::new(&a.field, coefs)
+});
+
+// DensePolynomial * DensePolynomial
+impl_op_ex!(* |a: &DensePolynomial, b: &DensePolynomial| -> DensePolynomial {
+    let field = common_field(a, b);
+    if a.is_zero() || b.is_zero() {
+        return DensePolynomial::zero(&field);
+    }
+    DensePolynomial::new(&field, multiplication::multiply(&a.coefs, &b.coefs))
+});
+
+// DensePolynomial * Fp
+impl_op_ex!(* |a: &DensePolynomial, b: &fp::Fp| -> DensePolynomial {
+    let coefs = a.coefs.iter().map(|c| c * b.value()).collect();
+    DensePolynomial::new(&a.field, coefs)
+});
+
+// DensePolynomial += DensePolynomial
+impl_op_ex!(+= |a: &mut DensePolynomial, b: &DensePolynomial| {
+    *a = &*a + b;
+});
+
+// DensePolynomial -= DensePolynomial
+impl_op_ex!(-= |a: &mut DensePolynomial, b: &DensePolynomial| {
+    *a = &*a - b;
+});
+
+// DensePolynomial *= DensePolynomial
+impl_op_ex!(*= |a: &mut DensePolynomial, b: &DensePolynomial| {
+    *a = &*a * b;
+});
+
+// DensePolynomial % DensePolynomial
+impl_op_ex!(% |a: &DensePolynomial, b: &DensePolynomial| -> DensePolynomial {
+    a.divrem(b).1
+});
+
+impl DensePolynomial {
+    /// coefs[i] is the coefficient of x^i
+    pub fn new(field: &fp::PrimeField, coefs: Vec<BigInt>) -> Self {
+        let mut pol = DensePolynomial {
+            coefs: coefs.into_iter().map(|c| c.mod_floor(field.p())).collect(),
+            field: field.clone(),
+        };
+        pol.normalize();
+        pol
+    }
+
+    pub fn zero(field: &fp::PrimeField) -> Self {
+        DensePolynomial {
+            coefs: Vec::new(),
+            field: field.clone(),
+        }
+    }
+
+    pub fn one(field: &fp::PrimeField) -> Self {
+        DensePolynomial::constant(&field.one())
+    }
+
+    pub fn constant(c: &fp::Fp) -> Self {
+        DensePolynomial::try_constant(c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_constant(c: &fp::Fp) -> Result<Self> {
+        let field = c.field().ok_or(Error::UnknownField)?;
+        Ok(DensePolynomial::new(field, vec![c.to_bigint()]))
+    }
+
+    /// x
+    pub fn x(field: &fp::PrimeField) -> Self {
+        DensePolynomial::monomial(&field.one(), 1)
+    }
+
+    /// c x^n
+    pub fn monomial(c: &fp::Fp, n: usize) -> Self {
+        DensePolynomial::try_monomial(c, n).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_monomial(c: &fp::Fp, n: usize) -> Result<Self> {
+        let field = c.field().ok_or(Error::UnknownField)?;
+        let mut coefs = vec![BigInt::zero(); n + 1];
+        coefs[n] = c.to_bigint();
+        Ok(DensePolynomial::new(field, coefs))
+    }
+
+    fn normalize(&mut self) {
+        while let Some(c) = self.coefs.last() {
+            if !c.is_zero() {
+                break;
+            }
+            self.coefs.pop();
+        }
+    }
+
+    pub fn field(&self) -> &fp::PrimeField {
+        &self.field
+    }
+
+    pub fn coefs(&self) -> &[BigInt] {
+        &self.coefs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coefs.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.coefs.len() == 1 && self.coefs[0].is_one()
+    }
+
+    /// degree, 0 for zero polynomial
+    pub fn degree(&self) -> usize {
+        if self.coefs.is_empty() {
+            0
+        } else {
+            self.coefs.len() - 1
+        }
+    }
+
+    /// coefficient of x^i
+    pub fn coef(&self, i: usize) -> fp::Fp {
+        match self.coefs.get(i) {
+            Some(c) => self.field.elem(c.clone()),
+            None => self.field.zero(),
+        }
+    }
+
+    pub fn leading_coef(&self) -> fp::Fp {
+        self.coef(self.degree())
+    }
+
+    pub fn to_monic(&self) -> Self {
+        if self.is_zero() {
+            return self.clone();
+        }
+        self * self.leading_coef().inv()
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Result<Self> {
+        try_common_field(self, other)?;
+        Ok(self + other)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Result<Self> {
+        try_common_field(self, other)?;
+        Ok(self - other)
+    }
+
+    pub fn checked_mul(&self, other: &Self) -> Result<Self> {
+        try_common_field(self, other)?;
+        Ok(self * other)
+    }
+
+    /// (self / other, self % other)
+    pub fn divrem(&self, other: &Self) -> (Self, Self) {
+        self.checked_divrem(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_divrem(&self, other: &Self) -> Result<(Self, Self)> {
+        let field = try_common_field(self, other)?;
+        if other.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if self.coefs.len() < other.coefs.len() {
+            return Ok((DensePolynomial::zero(&field), self.clone()));
+        }
+        let p = field.p();
+        let inv = other.leading_coef().inv().to_bigint();
+        let od = other.degree();
+        let mut r = self.coefs.clone();
+        let mut q = vec![BigInt::zero(); self.coefs.len() - od];
+        for i in (0..q.len()).rev() {
+            let c = (&r[i + od] * &inv).mod_floor(p);
+            if c.is_zero() {
+                continue;
+            }
+            for (j, oc) in other.coefs.iter().enumerate() {
+                r[i + j] = (&r[i + j] - &c * oc).mod_floor(p);
+            }
+            q[i] = c;
+        }
+        r.truncate(od);
+        Ok((DensePolynomial::new(&field, q), DensePolynomial::new(&field, r)))
+    }
+
+    /// monic gcd
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a.to_monic()
+    }
+
+    /// extended euclid algorithm
+    ///
+    /// s self + t other = gcd(self, other)
+    ///
+    /// return: (gcd(self, other), s, t), gcd is monic
+    pub fn xgcd(&self, other: &Self) -> (Self, Self, Self) {
+        let field = common_field(self, other);
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (DensePolynomial::one(&field), DensePolynomial::zero(&field));
+        let (mut t0, mut t1) = (DensePolynomial::zero(&field), DensePolynomial::one(&field));
+        while !r1.is_zero() {
+            let (q, r) = r0.divrem(&r1);
+            r0 = std::mem::replace(&mut r1, r);
+            let s = &s0 - &q * &s1;
+            s0 = std::mem::replace(&mut s1, s);
+            let t = &t0 - &q * &t1;
+            t0 = std::mem::replace(&mut t1, t);
+        }
+        if r0.is_zero() {
+            return (r0, s0, t0);
+        }
+        let inv = r0.leading_coef().inv();
+        (&r0 * &inv, &s0 * &inv, &t0 * &inv)
+    }
+
+    /// 1/self (mod modulus)
+    pub fn inverse_mod(&self, modulus: &Self) -> Option<Self> {
+        let (g, s, _) = self.xgcd(modulus);
+        if g.is_one() {
+            Some(s % modulus)
+        } else {
+            None
+        }
+    }
+
+    /// self^n (mod modulus)
+    pub fn powmod(&self, n: &BigInt, modulus: &Self) -> Self {
+        self.checked_powmod(n, modulus).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_powmod(&self, n: &BigInt, modulus: &Self) -> Result<Self> {
+        if n < &Zero::zero() {
+            return Err(Error::NegativeExponent);
+        }
+        let mut b = self.checked_divrem(modulus)?.1;
+        let mut r = DensePolynomial::one(&self.field) % modulus;
+        let mut e = n.clone();
+        while !e.is_zero() {
+            if e.is_odd() {
+                r = (&r * &b) % modulus;
+            }
+            e >>= 1;
+            if !e.is_zero() {
+                b = (&b * &b) % modulus;
+            }
+        }
+        Ok(r)
+    }
+
+    /// evaluation using concrete x
+    pub fn eval(&self, x: &fp::Fp) -> fp::Fp {
+        let p = self.field.p();
+        let mut sum = BigInt::zero();
+        for c in self.coefs.iter().rev() {
+            sum = (sum * x.value() + c).mod_floor(p);
+        }
+        self.field.elem(sum)
+    }
+
+    pub fn derivative(&self) -> Self {
+        let coefs = self.coefs.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| c * BigInt::from(i))
+            .collect();
+        DensePolynomial::new(&self.field, coefs)
+    }
+
+    /// nonzero polynomial over a prime field
+    fn check_factorizable(&self) -> Result<()> {
+        if self.is_zero() {
+            return Err(Error::InvalidArgument("zero polynomial".to_string()));
+        }
+        if !bigint::is_probable_prime(self.field.p()) {
+            return Err(Error::NotPrime(self.field.p().clone()));
+        }
+        Ok(())
+    }
+
+    /// roots in F_p with multiplicity in ascending order
+    pub fn roots(&self) -> Vec<BigInt> {
+        self.checked_roots().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// gcd(self, x^p - x) is the product of (x - r) over the distinct roots,
+    /// which is split by Cantor-Zassenhaus.
+    pub fn checked_roots(&self) -> Result<Vec<BigInt>> {
+        self.check_factorizable()?;
+        if self.degree() == 0 {
+            return Ok(Vec::new());
+        }
+        let x = DensePolynomial::x(&self.field);
+        let g = (x.powmod(self.field.p(), self) - &x).gcd(self);
+        let mut linear: Vec<DensePolynomial> = Vec::new();
+        split_equal_degree(&g, 1, &mut rand::thread_rng(), &mut linear);
+        let mut distinct: Vec<BigInt> = linear.iter().map(|f| (-f.coef(0)).to_bigint()).collect();
+        distinct.sort();
+        let mut roots: Vec<BigInt> = Vec::new();
+        for r in distinct {
+            let linear = &x - DensePolynomial::constant(&self.field.elem(r.clone()));
+            let (mut q, mut rem) = self.divrem(&linear);
+            while rem.is_zero() {
+                roots.push(r.clone());
+                let next = q.divrem(&linear);
+                q = next.0;
+                rem = next.1;
+            }
+        }
+        Ok(roots)
+    }
+
+    /// Ben-Or: f of degree k is irreducible iff gcd(x^(p^i) - x, f) = 1 for i <= k / 2
+    pub fn is_irreducible(&self) -> bool {
+        if self.is_zero() || self.degree() == 0 {
+            return false;
+        }
+        let x = DensePolynomial::x(&self.field);
+        let mut h = x.clone();
+        for _ in 0..self.degree() / 2 {
+            h = h.powmod(self.field.p(), self);
+            if !(&h - &x).gcd(self).is_one() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// (f_i, i) with self = c prod f_i^i, f_i monic, square-free and coprime
+    pub fn square_free_factorization(&self) -> Vec<(DensePolynomial, u32)> {
+        self.checked_square_free_factorization().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_square_free_factorization(&self) -> Result<Vec<(DensePolynomial, u32)>> {
+        self.check_factorizable()?;
+        let mut factors = square_free(&self.to_monic());
+        factors.sort_by_key(|(_, i)| *i);
+        Ok(factors)
+    }
+
+    /// (g_d, d) where g_d is the product of the irreducible factors of degree d
+    /// self must be square-free
+    pub fn distinct_degree_factorization(&self) -> Vec<(DensePolynomial, usize)> {
+        self.checked_distinct_degree_factorization().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_distinct_degree_factorization(&self) -> Result<Vec<(DensePolynomial, usize)>> {
+        self.check_factorizable()?;
+        if !self.gcd(&self.derivative()).is_one() {
+            return Err(Error::InvalidArgument(format!("not square-free: {}", self)));
+        }
+        Ok(distinct_degree(&self.to_monic()))
+    }
+
+    /// irreducible factors of a square-free product of irreducible polynomials of degree d
+    pub fn equal_degree_factorization(&self, d: usize) -> Vec<DensePolynomial> {
+        self.checked_equal_degree_factorization(d).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_equal_degree_factorization(&self, d: usize) -> Result<Vec<DensePolynomial>> {
+        let ddf = self.checked_distinct_degree_factorization()?;
+        if ddf.iter().any(|(_, e)| *e != d) {
+            return Err(Error::InvalidArgument(format!("factors of {} are not of degree {}", self, d)));
+        }
+        let mut factors: Vec<DensePolynomial> = Vec::new();
+        for (g, _) in ddf {
+            split_equal_degree(&g, d, &mut rand::thread_rng(), &mut factors);
+        }
+        factors.sort_by(|a, b| a.coefs.cmp(&b.coefs));
+        Ok(factors)
+    }
+
+    /// monic irreducible factors with multiplicity in ascending order of degree,
+    /// the leading coefficient of self is dropped
+    pub fn factorize(&self) -> Vec<(DensePolynomial, u32)> {
+        self.checked_factorize().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// square-free, distinct-degree and equal-degree factorization
+    pub fn checked_factorize(&self) -> Result<Vec<(DensePolynomial, u32)>> {
+        let mut rng = rand::thread_rng();
+        let mut factors: Vec<(DensePolynomial, u32)> = Vec::new();
+        for (f, i) in self.checked_square_free_factorization()? {
+            for (g, d) in distinct_degree(&f) {
+                let mut split: Vec<DensePolynomial> = Vec::new();
+                split_equal_degree(&g, d, &mut rng, &mut split);
+                factors.extend(split.into_iter().map(|h| (h, i)));
+            }
+        }
+        factors.sort_by(|(a, i), (b, j)| (a.degree(), &a.coefs, i).cmp(&(b.degree(), &b.coefs, j)));
+        Ok(factors)
+    }
+
+    /// convert Polynomial which has only x terms
+    pub fn from_polynomial(pol: &Polynomial, field: &fp::PrimeField) -> Self {
+        DensePolynomial::try_from_polynomial(pol, field).unwrap_or_else(|e| panic!("{}: {}", e, pol))
+    }
+
+    pub fn try_from_polynomial(pol: &Polynomial, field: &fp::PrimeField) -> Result<Self> {
+        if !pol.is_univariate_x() {
+            return Err(Error::NotUnivariate);
+        }
+        let mut coefs = vec![BigInt::zero(); pol.highest_term_x().xpow() as usize + 1];
+        for (m, coef) in &pol.terms {
+            coefs[m.xpow as usize] = coef.clone();
+        }
+        Ok(DensePolynomial::new(field, coefs))
+    }
+
+    pub fn to_polynomial(&self) -> Polynomial {
+        let mut pol = Polynomial::new();
+        for (i, c) in self.coefs.iter().enumerate() {
+            if !c.is_zero() {
+                pol += term_builder::TermBuilder::new().coef(c).xpow(i as i32).build();
+            }
+        }
+        pol
+    }
+}
+
+/// square-free factorization of a monic f
+/// f' = 0 means f = g(x)^p with g(x) = sum c_ip x^i
+fn square_free(f: &DensePolynomial) -> Vec<(DensePolynomial, u32)> {
+    let field = f.field();
+    let mut factors: Vec<(DensePolynomial, u32)> = Vec::new();
+    if f.degree() == 0 {
+        return factors;
+    }
+    let mut c = f.gcd(&f.derivative());
+    let mut w = f.divrem(&c).0;
+    let mut i = 1;
+    // w is the product of the factors with multiplicity >= i not divisible by p
+    while w.degree() > 0 {
+        let y = w.gcd(&c);
+        let z = w.divrem(&y).0;
+        if z.degree() > 0 {
+            factors.push((z, i));
+        }
+        w = y;
+        c = c.divrem(&w).0;
+        i += 1;
+    }
+    if c.degree() > 0 {
+        let p = field.p().to_usize().expect("p <= degree");
+        let root = DensePolynomial::new(field, c.coefs.iter().step_by(p).cloned().collect());
+        factors.extend(square_free(&root).into_iter().map(|(g, j)| (g, j * p as u32)));
+    }
+    factors
+}
+
+/// distinct-degree factorization of a monic square-free f
+fn distinct_degree(f: &DensePolynomial) -> Vec<(DensePolynomial, usize)> {
+    let field = f.field();
+    let x = DensePolynomial::x(field);
+    let mut factors: Vec<(DensePolynomial, usize)> = Vec::new();
+    let mut f = f.clone();
+    let mut h = x.clone();
+    let mut d = 1;
+    while f.degree() >= 2 * d {
+        // h = x^(p^d) mod f
+        h = h.powmod(field.p(), &f);
+        let g = (&h - &x).gcd(&f);
+        if !g.is_one() {
+            f = f.divrem(&g).0;
+            h = &h % &f;
+            factors.push((g, d));
+        }
+        d += 1;
+    }
+    if f.degree() > 0 {
+        let d = f.degree();
+        factors.push((f, d));
+    }
+    factors
+}
+
+/// Cantor-Zassenhaus, g is a monic product of distinct irreducible polynomials of degree d
+///
+/// gcd(a^((p^d - 1) / 2) - 1, g) splits g for about half of a, for p = 2 the trace
+/// a + a^2 + ... + a^(2^(d-1)) is used instead.
+fn split_equal_degree<R: rand::Rng + ?Sized>(g: &DensePolynomial, d: usize, rng: &mut R,
+                                             factors: &mut Vec<DensePolynomial>) {
+    let field = g.field();
+    let n = g.degree();
+    if n == 0 {
+        return;
+    }
+    if n <= d {
+        factors.push(g.clone());
+        return;
+    }
+    let p = field.p();
+    let e: BigInt = (p.power(d as i32) - 1u32) >> 1usize;
+    loop {
+        let a = DensePolynomial::new(field, (0..n).map(|_| bigint::random_below(rng, p)).collect());
+        if a.degree() == 0 {
+            continue;
+        }
+        let b = if p == &BigInt::from(2) {
+            let mut t = a.clone();
+            let mut sum = a.clone();
+            for _ in 1..d {
+                t = &t * &t % g;
+                sum += &t;
+            }
+            sum
+        } else {
+            a.powmod(&e, g) - DensePolynomial::one(field)
+        };
+        let h = b.gcd(g);
+        if h.degree() > 0 && h.degree() < n {
+            split_equal_degree(&h, d, rng, factors);
+            split_equal_degree(&g.divrem(&h).0, d, rng, factors);
+            return;
+        }
+    }
+}
+
+impl From<&DensePolynomial> for Polynomial {
+    fn from(pol: &DensePolynomial) -> Self {
+        pol.to_polynomial()
+    }
+}
+
+impl From<DensePolynomial> for Polynomial {
+    fn from(pol: DensePolynomial) -> Self {
+        pol.to_polynomial()
+    }
+}
+
+impl fmt::Display for DensePolynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_polynomial())
+    }
+}
+
+#[cfg(test)]
+fn dense(field: &fp::PrimeField, coefs: &[i32]) -> DensePolynomial {
+    DensePolynomial::new(field, coefs.iter().map(|c| BigInt::from(*c)).collect())
+}
+
+#[test]
+fn dense_polynomial_arithmetic_test() {
+    let f = fp::PrimeField::new(&BigInt::from(19));
+    let a = dense(&f, &[2, 0, 3, 0, 3]);
+    let b = dense(&f, &[3, 0, 2]);
+    assert_eq_str!(a, "3 x^4 + 3 x^2 + 2");
+    assert_eq_str!(&a + &b, "3 x^4 + 5 x^2 + 5");
+    assert_eq_str!(&a - &a, "0");
+    assert_eq_str!(&a * &b, "6 x^6 + 15 x^4 + 13 x^2 + 6");
+    let (q, r) = a.divrem(&b);
+    assert_eq_str!(q, "11 x^2 + 4");
+    assert_eq_str!(r, "9");
+    assert_eq!(&q * &b + &r, a);
+    assert_eq_str!(a.derivative(), "12 x^3 + 6 x");
+    assert_eq_str!(a.eval(&f.elem(2)), "5");
+}
+
+#[test]
+fn dense_polynomial_gcd_test() {
+    let f = fp::PrimeField::new(&BigInt::from(23));
+    // (x - 1)(x - 2), (x - 1)(x + 5)
+    let a = dense(&f, &[2, -3, 1]);
+    let b = dense(&f, &[-5, 4, 1]);
+    assert_eq_str!(a.gcd(&b), "x + 22");
+    let (g, s, t) = a.xgcd(&b);
+    assert_eq_str!(g, "x + 22");
+    assert_eq!(&s * &a + &t * &b, g);
+
+    let m = dense(&f, &[1, 0, 1]);
+    let u = dense(&f, &[3, 1]);
+    let inv = u.inverse_mod(&m).unwrap();
+    assert!((&inv * &u % &m).is_one());
+    assert!(a.inverse_mod(&(&a * &u)).is_none());
+}
+
+#[test]
+fn dense_polynomial_powmod_test() {
+    let f = fp::PrimeField::new(&BigInt::from(7));
+    let m = dense(&f, &[1, 1, 0, 1]);
+    let x = DensePolynomial::x(&f);
+    let mut e = DensePolynomial::one(&f);
+    for _ in 0..100 {
+        e = &e * &x % &m;
+    }
+    assert_eq!(x.powmod(&BigInt::from(100), &m), e);
+    assert!(x.powmod(&BigInt::from(0), &m).is_one());
+}
+
+#[test]
+fn dense_polynomial_conversion_test() {
+    type TermBuilder = term_builder::TermBuilder;
+    let f = fp::PrimeField::new(&BigInt::from(5));
+    let pol = TermBuilder::new().coef(7).xpow(3).build()
+            + TermBuilder::new().coef(-1).xpow(1).build();
+    let d = DensePolynomial::from_polynomial(&pol, &f);
+    assert_eq_str!(d, "2 x^3 + 4 x");
+    assert_eq!(d.degree(), 3);
+    assert_eq_str!(Polynomial::from(&d), "2 x^3 + 4 x");
+}
+
+#[test]
+fn dense_polynomial_checked_test() {
+    use super::term_builder;
+    let f = fp::PrimeField::new(&BigInt::from(19));
+    let g = fp::PrimeField::new(&BigInt::from(23));
+    let a = dense(&f, &[1, 2, 3]);
+    assert_eq!(a.checked_divrem(&DensePolynomial::zero(&f)), Err(Error::DivisionByZero));
+    assert_eq!(a.checked_add(&dense(&g, &[1])), Err(Error::MismatchedField));
+    assert_eq!(a.checked_powmod(&BigInt::from(-2), &a), Err(Error::NegativeExponent));
+    assert_eq!(DensePolynomial::try_constant(&fp::Fp::one()), Err(Error::UnknownField));
+    let y = term_builder::TermBuilder::new().ypow(1).build().to_pol();
+    assert_eq!(DensePolynomial::try_from_polynomial(&y, &f), Err(Error::NotUnivariate));
+    assert_eq!(a.checked_mul(&a), Ok(&a * &a));
+}
+
+#[test]
+fn dense_polynomial_roots_test() {
+    let ints = |v: &[i64]| v.iter().map(|r| BigInt::from(*r)).collect::<Vec<BigInt>>();
+    let linear = |f: &fp::PrimeField, r: &BigInt| DensePolynomial::new(f, vec![-r, BigInt::one()]);
+    let f = fp::PrimeField::new(&BigInt::from(1009));
+    // (x - 3)^2 (x - 5) (x^2 - 11), 11 is not a square mod 1009
+    let a = dense(&f, &[-3, 1]) * dense(&f, &[-3, 1]) * dense(&f, &[-5, 1]) * dense(&f, &[-11, 0, 1]);
+    assert_eq!(a.roots(), ints(&[3, 3, 5]));
+    assert_eq!(dense(&f, &[-11, 0, 1]).roots(), ints(&[]));
+    assert_eq!(dense(&f, &[4]).roots(), ints(&[]));
+    // every element of F_p is a root of x^p - x
+    let mut xp = vec![0; 1010];
+    xp[1] = -1;
+    xp[1009] = 1;
+    assert_eq!(dense(&f, &xp).roots().len(), 1009);
+    assert_eq!(dense(&f, &[0, 0, 0, 1]).roots(), ints(&[0, 0, 0]));
+    let f2 = fp::PrimeField::new(&BigInt::from(2));
+    assert_eq!(dense(&f2, &[0, 1, 1]).roots(), ints(&[0, 1]));
+
+    // p of secp256k1
+    let p = BigInt::parse_bytes(b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f", 16).unwrap();
+    let f = fp::PrimeField::new(&p);
+    let mut rng = rand::thread_rng();
+    let mut expected: Vec<BigInt> = (0..6).map(|_| bigint::random_below(&mut rng, &p)).collect();
+    expected.push(expected[0].clone());
+    let mut a = DensePolynomial::one(&f);
+    for r in expected.iter() {
+        a *= linear(&f, r);
+    }
+    // x^2 - 3 has no root since p = 3 mod 4 and 3 is not a square mod p
+    a *= dense(&f, &[-3, 0, 1]);
+    expected.sort();
+    assert_eq!(a.roots(), expected);
+
+    assert!(DensePolynomial::zero(&f).checked_roots().is_err());
+    let f15 = fp::PrimeField::new(&BigInt::from(15));
+    assert_eq!(dense(&f15, &[1, 1]).checked_roots(), Err(Error::NotPrime(BigInt::from(15))));
+}
+
+#[cfg(test)]
+fn product_of_factors(field: &fp::PrimeField, factors: &[(DensePolynomial, u32)]) -> DensePolynomial {
+    let mut f = DensePolynomial::one(field);
+    for (g, i) in factors {
+        for _ in 0..*i {
+            f *= g;
+        }
+    }
+    f
+}
+
+#[test]
+fn dense_polynomial_factorize_test() {
+    let f = fp::PrimeField::new(&BigInt::from(1009));
+    let x3 = dense(&f, &[-3, 1]);
+    let q = dense(&f, &[-11, 0, 1]);
+    let c = dense(&f, &[-2, 0, 0, 1]);
+    assert!(q.is_irreducible() && c.is_irreducible() && x3.is_irreducible());
+    assert!(!dense(&f, &[-4, 0, 1]).is_irreducible());
+    assert!(!dense(&f, &[5]).is_irreducible());
+    // 5 (x - 3)^2 (x^2 - 11)^3 (x^3 - 2)
+    let a = &x3 * &x3 * &q * &q * &q * &c * f.elem(5);
+    let factors = a.factorize();
+    assert_eq!(factors, vec![(x3.clone(), 2), (q.clone(), 3), (c.clone(), 1)]);
+    assert_eq!(a.square_free_factorization(), vec![(c.clone(), 1), (x3.clone(), 2), (q.clone(), 3)]);
+    let b = &x3 * &q * &c * dense(&f, &[-7, 1]);
+    assert_eq!(b.distinct_degree_factorization(),
+               vec![(&x3 * dense(&f, &[-7, 1]), 1), (q.clone(), 2), (c.clone(), 3)]);
+    assert!(a.checked_distinct_degree_factorization().is_err());
+    let d = dense(&f, &[-5, 0, 0, 1]);
+    assert_eq!((&c * &d).equal_degree_factorization(3), vec![d.clone(), c.clone()]);
+    assert!((&c * &q).checked_equal_degree_factorization(3).is_err());
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..5 {
+        let mut a = DensePolynomial::one(&f);
+        for k in 1..5 {
+            // a nonzero leading coefficient keeps g and g' nonzero
+            let mut coefs: Vec<BigInt> = (0..k).map(|_| bigint::random_below(&mut rng, f.p())).collect();
+            coefs.push(bigint::random_below(&mut rng, &(f.p() - 1u32)) + 1u32);
+            let g = DensePolynomial::new(&f, coefs);
+            a *= &g * &g.derivative();
+        }
+        let a = a.to_monic();
+        let factors = a.factorize();
+        assert!(factors.iter().all(|(g, _)| g.is_irreducible() && g.leading_coef().is_one()));
+        assert_eq!(product_of_factors(&f, &factors), a);
+    }
+}
+
+#[test]
+fn dense_polynomial_factorize_small_field_test() {
+    // f' = 0 for (x^2 + 2)^5 over F_5
+    let f = fp::PrimeField::new(&BigInt::from(5));
+    let q = dense(&f, &[2, 0, 1]);
+    let x1 = dense(&f, &[1, 1]);
+    let mut a = x1.clone();
+    for _ in 0..5 {
+        a = &a * &q * &x1;
+    }
+    assert_eq!(a.factorize(), vec![(x1.clone(), 6), (q.clone(), 5)]);
+    assert_eq!(a.roots(), vec![BigInt::from(4); 6]);
+
+    // Cantor-Zassenhaus by the trace over F_2
+    let f = fp::PrimeField::new(&BigInt::from(2));
+    let c1 = dense(&f, &[1, 1, 0, 1]);
+    let c2 = dense(&f, &[1, 0, 1, 1]);
+    let x = DensePolynomial::x(&f);
+    let a = &c1 * &c2 * &c2 * &x * &x * dense(&f, &[1, 1, 1]);
+    assert_eq!(a.factorize(), vec![(x, 2), (dense(&f, &[1, 1, 1]), 1), (c2.clone(), 2), (c1.clone(), 1)]);
+    assert_eq!((&c1 * &c2).equal_degree_factorization(3), vec![c2, c1]);
+    assert!(dense(&f, &[1, 1, 0, 0, 1]).is_irreducible());
+    let f15 = fp::PrimeField::new(&BigInt::from(15));
+    assert_eq!(dense(&f15, &[1, 1]).checked_factorize(), Err(Error::NotPrime(BigInt::from(15))));
+}
@@ -0,0 +1,366 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::collections::HashMap;
+use super::bigint::{self, Inverse, ModResult};
+use super::elliptic_curve::{ECPoint, EllipticCurve};
+use super::error::{Error, Result};
+
+/// P + Q in affine coordinates
+fn add(ec: &EllipticCurve, p: &ECPoint, q: &ECPoint) -> ECPoint {
+    ec.to_affine(&ec.jacobian_add(p, q))
+}
+
+/// hash key of an affine point
+fn key(point: &ECPoint) -> Option<(BigInt, BigInt)> {
+    if point.is_infinity() {
+        None
+    } else {
+        Some((point.x().value().clone(), point.y().value().clone()))
+    }
+}
+
+/// exact order of P from a multiple m of it ([m] P = O)
+pub fn order_from_multiple(ec: &EllipticCurve, point: &ECPoint, m: &BigInt) -> BigInt {
+    bigint::order_from_multiple(m, |d| ec.multiply_scalar(point, d).is_infinity())
+}
+
+/// order of P by baby-step giant-step over the Hasse interval [p + 1 - 2 sqrt(p), p + 1 + 2 sqrt(p)]
+/// P must be on the curve
+pub fn point_order_bsgs(ec: &EllipticCurve, point: &ECPoint) -> Result<BigInt> {
+    if !ec.is_on_curve(point) {
+        return Err(Error::PointNotOnCurve);
+    }
+    if point.is_infinity() {
+        return Ok(One::one());
+    }
+    let point = ec.to_affine(point);
+    let s = ec.p().sqrt() + 1;
+    let lower = std::cmp::max(ec.p() + 1 - &s * 2, One::one());
+    let width: BigInt = &s * 4 + 1;
+    let m: BigInt = width.sqrt() + 1;
+
+    // baby steps j P, 0 < j <= m
+    let mut baby: HashMap<(BigInt, BigInt), BigInt> = HashMap::new();
+    let mut r = ECPoint::infinity();
+    let mut j = BigInt::zero();
+    while j < m {
+        r = add(ec, &r, &point);
+        j += 1;
+        if let Some(k) = key(&r) {
+            baby.entry(k).or_insert_with(|| j.clone());
+        }
+    }
+    // giant steps [lower + i m] P, looking for -[j] P
+    let step = ec.multiply_scalar(&point, &m);
+    let upper = &lower + &width;
+    let mut g = ec.multiply_scalar(&point, &lower);
+    let mut base = lower;
+    while base <= upper {
+        if g.is_infinity() {
+            return Ok(order_from_multiple(ec, &point, &base));
+        }
+        if let Some(j) = key(&ec.negate(&g)).and_then(|k| baby.get(&k)) {
+            return Ok(order_from_multiple(ec, &point, &(&base + j)));
+        }
+        g = add(ec, &g, &step);
+        base += &m;
+    }
+    // #E is in the Hasse interval for a non-singular curve
+    Err(Error::InvalidArgument(format!("no multiple of the order of {} in the Hasse interval", point)))
+}
+
+/// k with Q = [k] P, 0 <= k < n, by baby-step giant-step where n is a multiple of the order of P
+pub fn bsgs(ec: &EllipticCurve, p: &ECPoint, q: &ECPoint, n: &BigInt) -> Option<BigInt> {
+    let p = ec.to_affine(p);
+    let q = ec.to_affine(q);
+    let m: BigInt = n.sqrt() + 1;
+    let mut baby: HashMap<(BigInt, BigInt), BigInt> = HashMap::new();
+    let mut r = ECPoint::infinity();
+    let mut j = BigInt::zero();
+    while j < m {
+        if let Some(k) = key(&r) {
+            baby.entry(k).or_insert_with(|| j.clone());
+        } else if j.is_zero() && q.is_infinity() {
+            return Some(Zero::zero());
+        }
+        r = add(ec, &r, &p);
+        j += 1;
+    }
+    // Q - [i m] P = [j] P
+    let step = ec.negate(&ec.multiply_scalar(&p, &m));
+    let mut g = q;
+    let mut i = BigInt::zero();
+    while i <= m {
+        match key(&g) {
+            Some(k) => if let Some(j) = baby.get(&k) {
+                return Some((&i * &m + j).mod_floor(n));
+            },
+            None => return Some((&i * &m).mod_floor(n)),
+        }
+        g = add(ec, &g, &step);
+        i += 1;
+    }
+    None
+}
+
+/// number of low bits of x that must be zero for a distinguished point,
+/// about 2^(bits(n) / 4) steps between distinguished points
+fn distinguished_bits(n: &BigInt) -> usize {
+    n.bits() / 4
+}
+
+fn is_distinguished(point: &ECPoint, bits: usize) -> bool {
+    !point.is_infinity() && (point.x().value() % (BigInt::one() << bits)).is_zero()
+}
+
+/// partition of a point for the random walks
+fn partition(point: &ECPoint, count: usize) -> usize {
+    if point.is_infinity() {
+        0
+    } else {
+        (point.x().value() % BigInt::from(count)).to_usize().unwrap()
+    }
+}
+
+/// k with Q = [k] P by Pollard rho where n is the prime order of P
+///
+/// Walks R = [a] P + [b] Q with r-adding steps start from random (a, b) and run to a
+/// distinguished point. Two walks reaching the same one with different b give k.
+pub fn pollard_rho<R: rand::Rng + ?Sized>(ec: &EllipticCurve, p: &ECPoint, q: &ECPoint, n: &BigInt, rng: &mut R) -> Option<BigInt> {
+    const PARTITIONS: usize = 16;
+    if !bigint::is_probable_prime(n) {
+        return None;
+    }
+    if n < &BigInt::from(1000) {
+        return bsgs(ec, p, q, n);
+    }
+    let p = ec.to_affine(p);
+    let q = ec.to_affine(q);
+    let combination = |a: &BigInt, b: &BigInt| {
+        add(ec, &ec.multiply_scalar(&p, a), &ec.multiply_scalar(&q, b))
+    };
+    let steps: Vec<(BigInt, BigInt, ECPoint)> = (0..PARTITIONS).map(|_| {
+        let c = bigint::random_below(rng, n);
+        let d = bigint::random_below(rng, n);
+        let m = combination(&c, &d);
+        (c, d, m)
+    }).collect();
+    let bits = distinguished_bits(n);
+    let max_walk = 20usize << bits;
+    let mut found: HashMap<(BigInt, BigInt), (BigInt, BigInt)> = HashMap::new();
+    // expected sqrt(pi n / 2) steps in total, give up far beyond it
+    let mut budget: BigInt = n.sqrt() * 64 + 1000;
+    while budget.is_positive() {
+        let mut a = bigint::random_below(rng, n);
+        let mut b = bigint::random_below(rng, n);
+        let mut r = combination(&a, &b);
+        for _ in 0..max_walk {
+            if is_distinguished(&r, bits) {
+                break;
+            }
+            let (c, d, m) = &steps[partition(&r, PARTITIONS)];
+            r = add(ec, &r, m);
+            a = (a + c).mod_floor(n);
+            b = (b + d).mod_floor(n);
+        }
+        budget -= max_walk;
+        let k = match key(&r) {
+            Some(k) if is_distinguished(&r, bits) => k,
+            _ => continue,
+        };
+        if let Some((a2, b2)) = found.get(&k) {
+            // a + b k = a2 + b2 k
+            if b2 != &b {
+                let x = ((&a - a2) * (b2 - &b).mod_floor(n).inverse(n)).mod_floor(n);
+                if ec.multiply_scalar(&p, &x) == q {
+                    return Some(x);
+                }
+            }
+            continue;
+        }
+        found.insert(k, (a, b));
+    }
+    None
+}
+
+/// k with Q = [k] P and lower <= k <= upper by Pollard kangaroo
+///
+/// A tame kangaroo starts at [t] P, t random in the upper or lower half of the interval in turn,
+/// and a wild one at Q + [r] P. Both jump by powers of 2 chosen by the point
+/// and record distinguished points with their distances.
+/// Once the kangaroos met they walk the same path, so a collision giving x out of the interval
+/// (x = k + a multiple of the order of P) restarts them with other t and r.
+/// Two such x give a multiple of the order, which reduces x into the interval.
+pub fn pollard_kangaroo<R: rand::Rng + ?Sized>(ec: &EllipticCurve, p: &ECPoint, q: &ECPoint, lower: &BigInt, upper: &BigInt, rng: &mut R) -> Option<BigInt> {
+    let width: BigInt = upper - lower;
+    if width.is_negative() {
+        return None;
+    }
+    if width < BigInt::from(1000) {
+        let q2 = add(ec, q, &ec.negate(&ec.multiply_scalar(p, lower)));
+        let k = bsgs(ec, p, &q2, &(&width + 1))?;
+        return Some(lower + k);
+    }
+    let p = ec.to_affine(p);
+    let q = ec.to_affine(q);
+    // mean jump (2^jumps_count - 1) / jumps_count, about 2 sqrt(width) / jumps_count
+    let jumps_count = width.sqrt().bits().max(2);
+    let jumps: Vec<(BigInt, ECPoint)> = (0..jumps_count).map(|i| {
+        let d = BigInt::one() << i;
+        let m = ec.multiply_scalar(&p, &d);
+        (d, m)
+    }).collect();
+    let bits = distinguished_bits(&width);
+    let max_steps = width.sqrt() * 16 + BigInt::from(100 << bits);
+    let mut rejected: Option<BigInt> = None;
+    let half: BigInt = &width / 2;
+    'rounds: for round in 0..8 {
+        // the upper and the lower half in turn
+        let start = lower + if round % 2 == 0 { half.clone() } else { BigInt::zero() }
+            + bigint::random_below(rng, &(&half + 1));
+        let offset = bigint::random_below(rng, &(&width.sqrt() + 1));
+        // (point, distance travelled) for tame and wild
+        let mut tame = (ec.multiply_scalar(&p, &start), BigInt::zero());
+        let mut wild = (add(ec, &q, &ec.multiply_scalar(&p, &offset)), BigInt::zero());
+        let mut traps: HashMap<(BigInt, BigInt), (bool, BigInt)> = HashMap::new();
+        let mut steps = BigInt::zero();
+        while steps < max_steps {
+            for (is_tame, kangaroo) in [(true, &mut tame), (false, &mut wild)].iter_mut() {
+                if is_distinguished(&kangaroo.0, bits) {
+                    let k = key(&kangaroo.0).unwrap();
+                    match traps.get(&k) {
+                        Some((other_tame, d)) if other_tame != is_tame => {
+                            // start + tame distance = k + offset + wild distance
+                            let (dt, dw) = if *is_tame { (&kangaroo.1, d) } else { (d, &kangaroo.1) };
+                            let x: BigInt = &start + dt - &offset - dw;
+                            if ec.multiply_scalar(&p, &x) != q {
+                                continue 'rounds;
+                            }
+                            if &x >= lower && &x <= upper {
+                                return Some(x);
+                            }
+                            match rejected {
+                                Some(y) if y != x => {
+                                    let order = order_from_multiple(ec, &p, &(&x - y).abs());
+                                    let x = lower + (x - lower).mod_floor(&order);
+                                    return if &x <= upper { Some(x) } else { None };
+                                }
+                                _ => rejected = Some(x),
+                            }
+                            continue 'rounds;
+                        }
+                        // the kangaroo is in a cycle
+                        Some(_) => continue 'rounds,
+                        None => {
+                            traps.insert(k, (*is_tame, kangaroo.1.clone()));
+                        }
+                    }
+                }
+                let (d, m) = &jumps[partition(&kangaroo.0, jumps.len())];
+                kangaroo.0 = add(ec, &kangaroo.0, m);
+                kangaroo.1 += d;
+            }
+            steps += 1;
+        }
+    }
+    None
+}
+
+/// k with Q = [k] P, 0 <= k < n, by Pohlig-Hellman where n is the order of P
+///
+/// Each prime power q^e of n is solved digit by digit in the subgroup of order q,
+/// by BSGS for small q and Pollard rho otherwise, and the results are joined by CRT.
+pub fn pohlig_hellman<R: rand::Rng + ?Sized>(ec: &EllipticCurve, p: &ECPoint, q: &ECPoint, n: &BigInt, rng: &mut R) -> Option<BigInt> {
+    let mut results: Vec<ModResult> = Vec::new();
+    for (l, e) in bigint::factorize(n) {
+        // generator of the subgroup of order l
+        let p0 = ec.multiply_scalar(p, &(n / &l));
+        let mut x = BigInt::zero();
+        let mut le = BigInt::one();
+        for _ in 0..e {
+            // [n / l^(j+1)] (Q - [x] P) = [d_j] P0
+            let qj = add(ec, q, &ec.negate(&ec.multiply_scalar(p, &x)));
+            let qj = ec.multiply_scalar(&qj, &(n / (&le * &l)));
+            let d = if l < BigInt::from(1 << 20) {
+                bsgs(ec, &p0, &qj, &l)?
+            } else {
+                pollard_rho(ec, &p0, &qj, &l, rng)?
+            };
+            x += d * &le;
+            le *= &l;
+        }
+        results.push(ModResult { l: le, r: x });
+    }
+    let crt = bigint::chinese_remainder(&results);
+    let k = crt.r.mod_floor(n);
+    if ec.multiply_scalar(p, &k) == ec.to_affine(q) {
+        Some(k)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn point_order_bsgs_test() {
+    let ec = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    for point in ec.rational_points().iter().step_by(37) {
+        let order = point_order_bsgs(&ec, point).unwrap();
+        assert!(ec.multiply_scalar(point, &order).is_infinity());
+        let mut r = point.clone();
+        let mut naive = BigInt::one();
+        while !r.is_infinity() {
+            r = ec.plus(&r, point);
+            naive += 1;
+        }
+        assert_eq!(order, naive, "{}", point);
+        assert_eq!(order_from_multiple(&ec, point, &BigInt::from(ec.cardinality())), naive);
+    }
+    assert_eq!(point_order_bsgs(&ec, &ec.point(&BigInt::from(1120), &BigInt::from(1392))), Err(Error::PointNotOnCurve));
+}
+
+#[test]
+fn discrete_log_test() {
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(16);
+    let p = BigInt::from(1_000_003);
+    // #E = 999424 = 2^14 * 61
+    let ec = EllipticCurve::try_new(&BigInt::from(2), &BigInt::from(7), &p).unwrap();
+    let n = BigInt::from(999_424);
+    assert_eq!(bigint::factorize(&n), vec![(BigInt::from(2), 14), (BigInt::from(61), 1)]);
+    for _ in 0..4 {
+        let point = ec.random_point(&mut rng);
+        let order = point_order_bsgs(&ec, &point).unwrap();
+        assert!(n.is_multiple_of(&order));
+        assert_eq!(order_from_multiple(&ec, &point, &n), order);
+        let k = bigint::random_below(&mut rng, &order);
+        let q = ec.multiply_scalar(&point, &k);
+        assert_eq!(pohlig_hellman(&ec, &point, &q, &order, &mut rng), Some(k.clone()));
+        assert_eq!(bsgs(&ec, &point, &q, &order), Some(k.clone()));
+        assert_eq!(pollard_kangaroo(&ec, &point, &q, &BigInt::zero(), &order, &mut rng), Some(k.clone()));
+        assert_eq!(bsgs(&ec, &point, &ECPoint::infinity(), &order), Some(BigInt::zero()));
+    }
+
+    // #E = 1001228 = 2^2 * 250307
+    let ec = EllipticCurve::try_new(&BigInt::from(2), &BigInt::from(4), &p).unwrap();
+    let n = BigInt::from(1_001_228);
+    let l = BigInt::from(250_307);
+    let g = loop {
+        let g = ec.multiply_scalar(&ec.random_point(&mut rng), &(&n / &l));
+        if !g.is_infinity() {
+            break g;
+        }
+    };
+    assert_eq!(ec.point_order(&g), l);
+    let k = bigint::random_below(&mut rng, &l);
+    let q = ec.multiply_scalar(&g, &k);
+    assert_eq!(pollard_rho(&ec, &g, &q, &l, &mut rng), Some(k.clone()));
+    assert_eq!(pollard_rho(&ec, &g, &q, &n, &mut rng), None);
+    assert_eq!(pohlig_hellman(&ec, &g, &q, &l, &mut rng), Some(k));
+
+    // kangaroo in a short interval
+    let lower = BigInt::from(20_000);
+    let k = &lower + bigint::random_below(&mut rng, &BigInt::from(40_000));
+    let q = ec.multiply_scalar(&g, &k);
+    assert_eq!(pollard_kangaroo(&ec, &g, &q, &lower, &BigInt::from(60_000), &mut rng), Some(k));
+}
@@ -2,6 +2,9 @@ use num_integer::Integer;
 use num_bigint::BigInt;
 use num_traits::One;
 use num_traits::Zero;
+use super::error::{Error, Result};
+use super::fp;
+use super::dense_polynomial;
 use super::polynomial;
 use super::term_builder;
 use super::term_builder::TermBuildable;
@@ -9,6 +12,14 @@ use super::bigint::{Power};
 
 type TermBuilder = term_builder::TermBuilder;
 type Polynomial = polynomial::Polynomial;
+type DensePolynomial = dense_polynomial::DensePolynomial;
+
+pub fn try_psi(a: &BigInt, b: &BigInt, n: i32) -> Result<Polynomial> {
+    if n < 0 {
+        return Err(Error::InvalidArgument(format!("n:{}", n)));
+    }
+    Ok(psi(a, b, n))
+}
 
 pub fn psi(a: &BigInt, b: &BigInt, n: i32) -> Polynomial {
     assert!(n >= Zero::zero());
@@ -54,6 +65,13 @@ pub fn psi(a: &BigInt, b: &BigInt, n: i32) -> Polynomial {
     }
 }
 
+pub fn try_phi(a: &BigInt, b: &BigInt, n: i32) -> Result<Polynomial> {
+    if n < 1 {
+        return Err(Error::InvalidArgument(format!("n:{}", n)));
+    }
+    Ok(phi(a, b, n))
+}
+
 pub fn phi(a: &BigInt, b: &BigInt, n: i32) -> Polynomial {
     assert!(n >= One::one());
     let i = TermBuilder::new().xpow(1).build() * psi(a, b, n).power(2)
@@ -61,6 +79,13 @@ pub fn phi(a: &BigInt, b: &BigInt, n: i32) -> Polynomial {
     i.reduction(a, b)
 }
 
+pub fn try_omega(a: &BigInt, b: &BigInt, n: i32) -> Result<Polynomial> {
+    if n < 1 {
+        return Err(Error::InvalidArgument(format!("n:{}", n)));
+    }
+    Ok(omega(a, b, n))
+}
+
 pub fn omega(a: &BigInt, b: &BigInt, n: i32) -> Polynomial {
     assert!(n >= One::one());
     if n == One::one() {
@@ -73,6 +98,57 @@ pub fn omega(a: &BigInt, b: &BigInt, n: i32) -> Polynomial {
     }
 }
 
+/// division polynomials over F_p, f_0, f_1, ..., f_n
+/// f_m = psi_m for odd m, f_m = psi_m / y for even m
+/// y^2 is replaced with x^3 + a x + b
+pub fn psi_fp_list(a: &fp::Fp, b: &fp::Fp, n: usize) -> Vec<DensePolynomial> {
+    try_psi_fp_list(a, b, n).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// a and b must belong to the same odd prime field
+pub fn try_psi_fp_list(a: &fp::Fp, b: &fp::Fp, n: usize) -> Result<Vec<DensePolynomial>> {
+    let field = a.field().or_else(|| b.field()).ok_or(Error::UnknownField)?.clone();
+    if b.field().is_some_and(|f| f != &field) {
+        return Err(Error::MismatchedField);
+    }
+    if field.p() == &BigInt::from(2) {
+        return Err(Error::UnsupportedPrime(field.p().clone()));
+    }
+    let c = |v: &[fp::Fp]| DensePolynomial::new(&field, v.iter().map(|e| e.to_bigint()).collect());
+    let e = |v: i32| field.elem(v);
+    let mut f: Vec<DensePolynomial> = vec![
+        DensePolynomial::zero(&field),
+        DensePolynomial::one(&field),
+        c(&[e(2)]),
+        c(&[-a.square(), b * e(12), a * e(6), e(0), e(3)]),
+        c(&[(-b.square() * e(8) - a.square() * a) * e(4), -(a * b) * e(16), -a.square() * e(20),
+            b * e(80), a * e(20), e(0), e(4)]),
+    ];
+    f.truncate(n + 1);
+    // (x^3 + a x + b)^2
+    let ee = c(&[b.clone(), a.clone(), e(0), e(1)]);
+    let ee = &ee * &ee;
+    let inv2 = e(2).inv();
+    for k in f.len()..=n {
+        let m = k / 2;
+        let g = if k.is_odd() {
+            let s = &f[m + 2] * &f[m] * &f[m] * &f[m];
+            let t = &f[m - 1] * &f[m + 1] * &f[m + 1] * &f[m + 1];
+            if m.is_even() { &ee * s - t } else { s - &ee * t }
+        } else {
+            let s = &f[m + 2] * &f[m - 1] * &f[m - 1] - &f[m - 2] * &f[m + 1] * &f[m + 1];
+            &f[m] * s * &inv2
+        };
+        f.push(g);
+    }
+    Ok(f)
+}
+
+/// division polynomial f_n over F_p
+pub fn psi_fp(a: &fp::Fp, b: &fp::Fp, n: usize) -> DensePolynomial {
+    psi_fp_list(a, b, n).pop().unwrap()
+}
+
 #[test]
 fn division_polynomial_test_psi() {
     // psi
@@ -127,3 +203,17 @@ fn division_polynomial_test_omega() {
     assert_eq_str!(omega5, "x^36 y + 162 x^34 y + 4692 x^33 y - 10659 x^32 y - 107712 x^31 y - 902224 x^30 y + 556512 x^29 y - 3417068 x^28 y + 2557376 x^27 y - 24924744 x^26 y - 69151824 x^25 y - 257703372 x^24 y - 686331072 x^23 y - 1968515376 x^22 y - 2825185248 x^21 y - 5467087026 x^20 y - 10374222912 x^19 y - 12843672372 x^18 y - 23464263816 x^17 y - 28086809658 x^16 y - 28443733056 x^15 y - 33582134832 x^14 y - 29309513952 x^13 y - 19226935196 x^12 y - 19770442944 x^11 y - 13785051976 x^10 y - 12217620304 x^9 y - 14642004444 x^8 y - 14782274112 x^7 y - 13037393232 x^6 y - 8387833632 x^5 y - 2784562631 x^4 y + 221827904 x^3 y + 446882082 x^2 y + 112442324 x y + 30699397 y");
 }
 
+#[test]
+fn division_polynomial_test_psi_fp() {
+    let field = fp::PrimeField::new(&BigInt::from(1009));
+    let (a, b) = (BigInt::from(2), BigInt::from(3));
+    let y = TermBuilder::new().ypow(1).build();
+    for n in 0..=9 {
+        let mut expected = psi(&a, &b, n);
+        if n % 2 == 0 && n > 0 {
+            expected /= &y;
+        }
+        let expected = DensePolynomial::from_polynomial(&expected, &field);
+        assert_eq!(psi_fp(&field.elem(2), &field.elem(3), n as usize), expected, "n:{}", n);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::bigint::{self, Inverse};
+use hmac::{Hmac, Mac};
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use sha2::Sha256;
+use super::elliptic_curve::ECPoint;
+use super::error::{Error, Result};
+use super::secp256k1::Secp256k1;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// ECDSA signature over secp256k1
+///
+/// v is the recovery id: bit 0 is the parity of R.y, bit 1 is set when R.x >= n
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub r: BigInt,
+    pub s: BigInt,
+    pub v: u8,
+}
+
+/// leftmost 256 bits of the hash as an integer
+fn bits2int(hash: &[u8]) -> BigInt {
+    let z = BigInt::from_bytes_be(Sign::Plus, hash);
+    if hash.len() > 32 {
+        z >> (8 * (hash.len() - 32))
+    } else {
+        z
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).unwrap();
+    for d in data {
+        mac.update(d);
+    }
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// HMAC-DRBG of RFC 6979 section 3.2 with SHA-256
+struct Rfc6979 {
+    k: Vec<u8>,
+    v: Vec<u8>,
+    n: BigInt,
+}
+
+impl Rfc6979 {
+    fn new(n: &BigInt, sk: &BigInt, hash: &[u8]) -> Rfc6979 {
+        let x = bigint::to_bytes_be(sk, 32);
+        let h = bigint::to_bytes_be(&bits2int(hash).mod_floor(n), 32);
+        let v = vec![1u8; 32];
+        let k = hmac_sha256(&[0u8; 32], &[&v, &[0], &x, &h]);
+        let v = hmac_sha256(&k, &[&v]);
+        let k = hmac_sha256(&k, &[&v, &[1], &x, &h]);
+        let v = hmac_sha256(&k, &[&v]);
+        Rfc6979 { k, v, n: n.clone() }
+    }
+
+    /// next candidate in [1, n), the state is already advanced for a retry
+    fn next_nonce(&mut self) -> BigInt {
+        loop {
+            self.v = hmac_sha256(&self.k, &[&self.v]);
+            let t = BigInt::from_bytes_be(Sign::Plus, &self.v);
+            self.k = hmac_sha256(&self.k, &[&self.v, &[0]]);
+            self.v = hmac_sha256(&self.k, &[&self.v]);
+            if in_range(&t, &self.n) {
+                return t;
+            }
+        }
+    }
+}
+
+/// 1 <= x < n
+fn in_range(x: &BigInt, n: &BigInt) -> bool {
+    x >= &One::one() && x < n
+}
+
+/// deterministic nonce k of RFC 6979 for the secret key sk and the message hash
+pub fn nonce_rfc6979(curve: &Secp256k1, sk: &BigInt, hash: &[u8]) -> BigInt {
+    Rfc6979::new(&curve.n, sk, hash).next_nonce()
+}
+
+/// s <= n / 2
+pub fn is_low_s(curve: &Secp256k1, s: &BigInt) -> bool {
+    s <= &(&curve.n >> 1usize)
+}
+
+/// replace s by n - s if s > n / 2, flipping the parity of the recovery id
+pub fn normalize_s(curve: &Secp256k1, sig: &Signature) -> Signature {
+    if is_low_s(curve, &sig.s) {
+        sig.clone()
+    } else {
+        Signature { r: sig.r.clone(), s: &curve.n - &sig.s, v: sig.v ^ 1 }
+    }
+}
+
+/// sign the message hash with the secret key sk
+/// the signature is deterministic (RFC 6979) and low-S
+pub fn sign(curve: &Secp256k1, sk: &BigInt, hash: &[u8]) -> Result<Signature> {
+    let n = &curve.n;
+    if !in_range(sk, n) {
+        return Err(Error::InvalidArgument("secret key must be in [1, n)".to_string()));
+    }
+    let z = bits2int(hash);
+    let mut drbg = Rfc6979::new(n, sk, hash);
+    loop {
+        let k = drbg.next_nonce();
+        let point = curve.multiply_generator(&k);
+        let r = point.x().value().mod_floor(n);
+        if r.is_zero() {
+            continue;
+        }
+        let s = (k.inverse(n) * (&z + &r * sk)).mod_floor(n);
+        if s.is_zero() {
+            continue;
+        }
+        let mut v = if point.y().value().is_odd() { 1 } else { 0 };
+        if point.x().value() >= n {
+            v |= 2;
+        }
+        return Ok(normalize_s(curve, &Signature { r, s, v }));
+    }
+}
+
+/// verify the signature of the message hash with the public key pk
+/// high-S signatures are rejected
+pub fn verify(curve: &Secp256k1, pk: &ECPoint, hash: &[u8], sig: &Signature) -> bool {
+    let n = &curve.n;
+    if !in_range(&sig.r, n) || !in_range(&sig.s, n) || !is_low_s(curve, &sig.s) {
+        return false;
+    }
+    if pk.is_infinity() || !curve.ec.is_on_curve(pk) {
+        return false;
+    }
+    let w = sig.s.inverse(n);
+    let u1 = (bits2int(hash) * &w).mod_floor(n);
+    let u2 = (&sig.r * w).mod_floor(n);
+    let point = curve.ec.plus(&curve.multiply_generator(&u1), &curve.multiply_scalar(pk, &u2));
+    !point.is_infinity() && point.x().value().mod_floor(n) == sig.r
+}
+
+/// public key from the signature and its recovery id
+pub fn recover(curve: &Secp256k1, hash: &[u8], sig: &Signature) -> Result<ECPoint> {
+    let n = &curve.n;
+    if !in_range(&sig.r, n) || !in_range(&sig.s, n) {
+        return Err(Error::InvalidArgument("r and s must be in [1, n)".to_string()));
+    }
+    if sig.v > 3 {
+        return Err(Error::InvalidArgument(format!("recovery id {}", sig.v)));
+    }
+    let x = if sig.v & 2 != 0 { &sig.r + n } else { sig.r.clone() };
+    let point_r = curve.lift_x(&x, sig.v & 1 == 1)?;
+    let rinv = sig.r.inverse(n);
+    let u1 = (-bits2int(hash) * &rinv).mod_floor(n);
+    let u2 = (&sig.s * rinv).mod_floor(n);
+    let q = curve.ec.plus(&curve.multiply_generator(&u1), &curve.multiply_scalar(&point_r, &u2));
+    if q.is_infinity() {
+        return Err(Error::InvalidArgument("recovered public key is the point at infinity".to_string()));
+    }
+    Ok(q)
+}
+
+#[cfg(test)]
+fn hex(s: &str) -> BigInt {
+    BigInt::parse_bytes(s.as_bytes(), 16).unwrap()
+}
+
+#[test]
+fn ecdsa_rfc6979_test() {
+    use sha2::{Digest, Sha256};
+
+    let curve = Secp256k1::new();
+    // secret key, message, k, r, s, v
+    let vectors = [
+        ("1", "Satoshi Nakamoto",
+         "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+         "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+         "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5", 1),
+        ("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140", "Satoshi Nakamoto",
+         "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+         "fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d0",
+         "6b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed5", 0),
+        ("1", "All those moments will be lost in time, like tears in rain. Time to die...",
+         "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3",
+         "8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b",
+         "547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21", 0),
+        ("f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181", "Alan Turing",
+         "525a82b70e67874398067543fd84c83d30c175fdc45fdeee082fe13b1d7cfdf1",
+         "7063ae83e7f62bbb171798131b4a0564b956930092b33b07b395615d9ec7e15c",
+         "58dfcc1e00a35e1572f366ffe34ba0fc47db1e7189759b9fb233c5b05ab388ea", 0),
+    ];
+    for (sk, msg, k, r, s, v) in vectors.iter() {
+        let sk = hex(sk);
+        let hash = Sha256::digest(msg.as_bytes());
+        assert_eq!(nonce_rfc6979(&curve, &sk, &hash), hex(k), "{}", msg);
+        let sig = sign(&curve, &sk, &hash).unwrap();
+        assert_eq!(sig, Signature { r: hex(r), s: hex(s), v: *v }, "{}", msg);
+
+        let pk = curve.public_key(&sk);
+        assert!(verify(&curve, &pk, &hash, &sig));
+        assert_eq!(recover(&curve, &hash, &sig), Ok(pk.clone()));
+
+        // high-S form is rejected, other messages and keys fail
+        let high = Signature { r: sig.r.clone(), s: &curve.n - &sig.s, v: sig.v ^ 1 };
+        assert!(!verify(&curve, &pk, &hash, &high));
+        assert_eq!(normalize_s(&curve, &high), sig);
+        assert_eq!(recover(&curve, &hash, &high), Ok(pk.clone()));
+        assert!(!verify(&curve, &pk, &Sha256::digest(b"other"), &sig));
+        assert!(!verify(&curve, &curve.public_key(&(sk + 1)), &hash, &sig));
+    }
+}
+
+#[test]
+fn ecdsa_error_test() {
+    let curve = Secp256k1::new();
+    let hash = [0u8; 32];
+    assert!(sign(&curve, &BigInt::from(0), &hash).is_err());
+    assert!(sign(&curve, &curve.n, &hash).is_err());
+    let sig = sign(&curve, &BigInt::from(2), &hash).unwrap();
+    let bad = Signature { v: 4, ..sig.clone() };
+    assert!(recover(&curve, &hash, &bad).is_err());
+    let bad = Signature { s: BigInt::from(0), ..sig };
+    assert!(!verify(&curve, &curve.public_key(&BigInt::from(2)), &hash, &bad));
+    // R = G and s = z give Q = r^-1 (s G - z G) = O
+    let mut hash = [0u8; 32];
+    hash[31] = 1;
+    let bad = Signature { r: curve.g.x().to_bigint(), s: BigInt::from(1), v: curve.g.y().value().is_odd() as u8 };
+    assert_eq!(recover(&curve, &hash, &bad).err(),
+               Some(Error::InvalidArgument("recovered public key is the point at infinity".to_string())));
+}
@@ -4,134 +4,351 @@ use num_integer::Integer;
 use std::fmt;
 use std::vec;
 use std::ops::Deref;
+use std::sync::OnceLock;
+use super::bigint;
+use super::discrete_log;
+use super::error::{Error, Result};
+use super::extension_field;
+use super::fp;
+use super::generic_curve;
+use super::group_structure;
+use super::hash_to_curve;
+use super::isogeny;
 use super::polynomial;
 use super::term_builder::TermBuildable;
 use super::term_builder;
-use crate::bigint::Inverse;
 use num_traits::Zero;
 use num_traits::One;
-use num_traits::ToPrimitive;
+use num_traits::Signed;
 
 /// y^2 = x^3 + a x + b
 /// GF(p)
+/// a and b are elements of field(), use a_fp(), b_fp() and p() to read them.
 #[derive(Debug, Clone)]
 pub struct EllipticCurve {
-    pub a: BigInt,
-    pub b: BigInt,
-    pub p: BigInt,
+    a: fp::Fp,
+    b: fp::Fp,
+    field: fp::PrimeField,
     pol: polynomial::Polynomial,
-    /// rational points
-    pub points: Vec<ECPoint> // usize
+    /// rational points, enumerated on first use
+    /// NOTE: this was the public field points, use points() or rational_points() instead.
+    points: OnceLock<Vec<ECPoint>>,
 }
 
 /// Jacobian coordinates point
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// (X, Y, Z) represents the affine point (X / Z^2, Y / Z^3), Z = 0 is the point at infinity
+/// the coordinates are elements of F_p, the point at infinity has no field
+#[derive(Debug, Clone)]
 pub struct ECPoint {
-    pub x: BigInt,
-    pub y: BigInt,
-    pub z: BigInt,
+    x: fp::Fp,
+    y: fp::Fp,
+    z: fp::Fp,
 }
 
 impl EllipticCurve {
     pub fn new(a: &BigInt, b: &BigInt, p: &BigInt) -> EllipticCurve {
-        assert!(p >= &BigInt::from(2));
-        assert!(primes::is_prime(p.to_u64().unwrap()));
-        let pol = term_builder::TermBuilder::new().xpow(3).build()
-        + term_builder::TermBuilder::new().coef(a).xpow(1).build()
-        + term_builder::TermBuilder::new().coef(b).build();
-        let mut ec = EllipticCurve {
-            a: a.clone(),
-            b: b.clone(),
-            p: p.clone(),
-            pol: pol.clone(), 
-            points: Vec::new(),
-        };
-        ec.create_points();
-        ec
+        EllipticCurve::try_new(a, b, p).unwrap_or_else(|e| panic!("{}", e))
     }
 
+    /// p must be prime and 4 a^3 + 27 b^2 != 0 (mod p).
+    /// rational points are not enumerated, call create_points or points if needed.
+    pub fn try_new(a: &BigInt, b: &BigInt, p: &BigInt) -> Result<EllipticCurve> {
+        if p < &BigInt::from(2) {
+            return Err(Error::InvalidModulus(p.clone()));
+        }
+        if !bigint::is_probable_prime(p) {
+            return Err(Error::NotPrime(p.clone()));
+        }
+        let ec = EllipticCurve::new_raw(a, b, p);
+        if ec.discriminant().is_zero() {
+            return Err(Error::SingularCurve);
+        }
+        Ok(ec)
+    }
+
+    /// no checks, the curve may be singular and p is assumed to be prime
     pub fn new_raw(a: &BigInt, b: &BigInt, p: &BigInt) -> EllipticCurve {
+        let field = fp::PrimeField::new(p);
+        let (a, b) = (field.elem(a.clone()), field.elem(b.clone()));
         let pol = term_builder::TermBuilder::new().xpow(3).build()
-        + term_builder::TermBuilder::new().coef(a).xpow(1).build()
-        + term_builder::TermBuilder::new().coef(b).build();
-        let ec = EllipticCurve {
-            a: a.clone(),
-            b: b.clone(),
-            p: p.clone(),
-            pol: pol.clone(), 
-            points: Vec::new(),
+        + term_builder::TermBuilder::new().coef(a.value()).xpow(1).build()
+        + term_builder::TermBuilder::new().coef(b.value()).build();
+        EllipticCurve {
+            a,
+            b,
+            field,
+            pol,
+            points: OnceLock::new(),
+        }
+    }
+
+    /// base field F_p
+    pub fn field(&self) -> &fp::PrimeField {
+        &self.field
+    }
+
+    /// characteristic of the base field
+    pub fn p(&self) -> &BigInt {
+        self.field.p()
+    }
+
+    /// a as an element of F_p
+    pub fn a_fp(&self) -> fp::Fp {
+        self.a.clone()
+    }
+
+    /// b as an element of F_p
+    pub fn b_fp(&self) -> fp::Fp {
+        self.b.clone()
+    }
+
+    /// affine point (x, y), x and y are reduced into F_p
+    /// the point is not checked to be on the curve
+    pub fn point(&self, x: &BigInt, y: &BigInt) -> ECPoint {
+        ECPoint::new(&self.field.elem(x.clone()), &self.field.elem(y.clone()), &self.field.one())
+    }
+
+    /// x^3 + a x + b
+    pub fn rhs(&self, x: &fp::Fp) -> fp::Fp {
+        x.power(3) + self.a_fp() * x + self.b_fp()
+    }
+
+    /// square root of a modulo p, None if a is not a square
+    pub fn sqrt(&self, a: &BigInt) -> Option<BigInt> {
+        bigint::sqrt_modulo(a, self.p())
+    }
+
+    /// point (x, y) on the curve with the given parity of y
+    pub fn lift_x(&self, x: &BigInt, odd: bool) -> Result<ECPoint> {
+        if x.is_negative() || x >= self.p() {
+            return Err(Error::PointNotOnCurve);
+        }
+        let y = match self.to_generic().lift_x(&self.field.elem(x.clone())) {
+            Some(generic_curve::GenericPoint::Affine(_, y)) => y,
+            _ => return Err(Error::PointNotOnCurve),
+        };
+        let y = if y.value().is_odd() != odd { -y } else { y };
+        if y.value().is_odd() != odd {
+            return Err(Error::PointNotOnCurve);
+        }
+        Ok(ECPoint::new(&self.field.elem(x.clone()), &y, &self.field.one()))
+    }
+
+    /// uniformly random affine point
+    /// the point at infinity is never returned
+    pub fn random_point<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> ECPoint {
+        match self.to_generic().random_point(rng) {
+            generic_curve::GenericPoint::Affine(x, y) => ECPoint::new(&x, &y, &self.field.one()),
+            generic_curve::GenericPoint::Infinity => unreachable!(),
+        }
+    }
+
+    /// RFC 9380 hash_to_curve with SHA-256 and simplified SWU
+    /// secp256k1 uses the 3-isogeny of RFC 9380, other curves need a b != 0
+    pub fn hash_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<ECPoint> {
+        let secp256k1 = BigInt::from(2).power(256) - BigInt::from(2).power(32) - BigInt::from(977);
+        let map = if self.a.is_zero() && self.b.value() == &BigInt::from(7) && self.p() == &secp256k1 {
+            hash_to_curve::SswuMap::secp256k1(self)
+        } else {
+            hash_to_curve::SswuMap::new(self)?
         };
-        ec
+        map.hash_to_curve(msg, dst)
+    }
+
+    /// byte length of an element of F_p
+    fn field_bytes(&self) -> usize {
+        self.p().bits().div_ceil(8)
+    }
+
+    /// SEC1 encoding
+    /// 0x00 for infinity, 0x02/0x03 || x if compressed, 0x04 || x || y otherwise
+    pub fn encode_point(&self, point: &ECPoint, compressed: bool) -> Vec<u8> {
+        if point.is_infinity() {
+            return vec![0];
+        }
+        let point = self.to_affine(point);
+        let len = self.field_bytes();
+        let mut v = Vec::with_capacity(1 + 2 * len);
+        if compressed {
+            v.push(if point.y.value().is_odd() { 3 } else { 2 });
+            v.extend(bigint::to_bytes_be(point.x.value(), len));
+        } else {
+            v.push(4);
+            v.extend(bigint::to_bytes_be(point.x.value(), len));
+            v.extend(bigint::to_bytes_be(point.y.value(), len));
+        }
+        v
+    }
+
+    /// SEC1 decoding, the point must be on the curve
+    pub fn decode_point(&self, bytes: &[u8]) -> Result<ECPoint> {
+        let len = self.field_bytes();
+        let int = |b: &[u8]| BigInt::from_bytes_be(num_bigint::Sign::Plus, b);
+        match bytes.first() {
+            Some(0) if bytes.len() == 1 => Ok(ECPoint::infinity()),
+            Some(&prefix) if (prefix == 2 || prefix == 3) && bytes.len() == 1 + len => {
+                self.lift_x(&int(&bytes[1..]), prefix == 3)
+            }
+            Some(4) if bytes.len() == 1 + 2 * len => {
+                let x = int(&bytes[1..=len]);
+                let y = int(&bytes[1 + len..]);
+                if &x >= self.p() || &y >= self.p() {
+                    return Err(Error::PointNotOnCurve);
+                }
+                let point = self.point(&x, &y);
+                if !self.is_on_curve(&point) {
+                    return Err(Error::PointNotOnCurve);
+                }
+                Ok(point)
+            }
+            _ => Err(Error::InvalidArgument(format!("SEC1 encoding of {} bytes", bytes.len()))),
+        }
+    }
+
+    /// this curve as a GenericCurve over F_p
+    pub fn to_generic(&self) -> generic_curve::GenericCurve<fp::Fp> {
+        generic_curve::GenericCurve { a: self.a_fp(), b: self.b_fp() }
+    }
+
+    /// base change to the extension field F_{p^k} of F_p
+    pub fn over_extension(&self, field: &extension_field::ExtensionField) -> generic_curve::GenericCurve<extension_field::Fpk> {
+        assert!(field.base() == &self.field, "mismatched field {} {}", field.base(), self.field);
+        generic_curve::GenericCurve { a: field.from_base(&self.a_fp()), b: field.from_base(&self.b_fp()) }
+    }
+
+    /// 4 a^3 + 27 b^2
+    pub fn discriminant(&self) -> fp::Fp {
+        self.field.elem(4) * self.a_fp().power(3) + self.field.elem(27) * self.b_fp().square()
     }
 
     pub fn j_invariant(&self) -> BigInt {
-        let n = BigInt::from(4) * self.a.power(3);
-        let d = n.clone() + BigInt::from(27) * self.b.power(2); 
-        let j = BigInt::from(1728) * n.clone() * d.clone().inverse(&self.p);
-        return j.mod_floor(&self.p);
+        let n = self.field.elem(4) * self.a_fp().power(3);
+        let d = &n + self.field.elem(27) * self.b_fp().square();
+        if d.is_zero() {
+            // singular curve
+            return Zero::zero();
+        }
+        let j = self.field.elem(1728) * n / d;
+        j.to_bigint()
     }
 
+    /// Y^2 = X^3 + a X Z^4 + b Z^6
     pub fn is_on_curve(&self, ecpoint: &ECPoint) -> bool {
         if ecpoint.is_infinity() {
             return true;
         }
-        let c = self.pol.eval_xy(&ecpoint.x, &ecpoint.y);
-        let cc = c.mod_floor(&self.p);
-        let d = ecpoint.y.power(2);
-        let dd = d.mod_floor(&self.p);
-        let diff = cc - dd;
-        let r = diff.mod_floor(&self.p);
-        r == Zero::zero()
+        if ecpoint.z.field().is_some_and(|f| f != &self.field) {
+            return false;
+        }
+        let (x, y) = (&ecpoint.x, &ecpoint.y);
+        let z2 = ecpoint.z.square();
+        let z4 = z2.square();
+        let z6 = &z4 * &z2;
+        y.square() == x.power(3) + &self.a * x * z4 + &self.b * z6
     }
 
+    /// the point with its coordinates in the field of the curve
     pub fn canonicalize(&self, point: &ECPoint) -> ECPoint {
-        ECPoint {
-            x: point.x.mod_floor(&self.p),
-            y: point.y.mod_floor(&self.p), 
-            z: point.z.mod_floor(&self.p),
+        if point.is_infinity() {
+            return ECPoint::infinity();
         }
+        let e = |v: &fp::Fp| self.field.elem(v.to_bigint());
+        ECPoint::new(&e(&point.x), &e(&point.y), &e(&point.z))
     }
 
     /// Elliptic curve point addition
     pub fn plus(&self, point1: &ECPoint, point2: &ECPoint) -> ECPoint {
-        if !self.is_on_curve(point1) {
-            println!("{}", point1);
-            assert!(false, "point1 is not on curve");
+        self.checked_plus(point1, point2).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_plus(&self, point1: &ECPoint, point2: &ECPoint) -> Result<ECPoint> {
+        if !self.is_on_curve(point1) || !self.is_on_curve(point2) {
+            return Err(Error::PointNotOnCurve);
         }
-        assert!(self.is_on_curve(point1), "point1 is not on curve");
-        assert!(self.is_on_curve(point2), "point2 is not on curve");
+        Ok(self.to_affine(&self.jacobian_add(point1, point2)))
+    }
+
+    /// (X, Y, Z) -> (X / Z^2, Y / Z^3, 1)
+    pub fn to_affine(&self, point: &ECPoint) -> ECPoint {
+        if point.is_infinity() {
+            return ECPoint::infinity();
+        }
+        let zinv = point.z.inv();
+        let zinv2 = zinv.square();
+        let x = &point.x * &zinv2;
+        let y = &point.y * zinv2 * zinv;
+        ECPoint::new(&x, &y, &self.field.one())
+    }
+
+    /// projective equality modulo p, same as ==
+    pub fn point_eq(&self, point1: &ECPoint, point2: &ECPoint) -> bool {
+        point1 == point2
+    }
+
+    /// 2 P in Jacobian coordinates
+    pub fn jacobian_double(&self, point: &ECPoint) -> ECPoint {
+        if point.is_infinity() || point.y.is_zero() {
+            return ECPoint::infinity();
+        }
+        let (x, y, z) = (&point.x, &point.y, &point.z);
+        let yy = y.square();
+        let s = self.field.elem(4) * x * &yy;
+        let m = self.field.elem(3) * x.square() + &self.a * z.square().square();
+        let x3 = m.square() - self.field.elem(2) * &s;
+        let y3 = m * (s - &x3) - self.field.elem(8) * yy.square();
+        let z3 = self.field.elem(2) * y * z;
+        ECPoint::new(&x3, &y3, &z3)
+    }
+
+    /// P + Q in Jacobian coordinates
+    pub fn jacobian_add(&self, point1: &ECPoint, point2: &ECPoint) -> ECPoint {
         if point1.is_infinity() {
-            return ECPoint::new(&point2.x, &point2.y, &point2.z);
+            return point2.clone();
         } else if point2.is_infinity() {
-            return ECPoint::new(&point1.x, &point2.y, &point2.z);
-        }
-        let p1 = self.canonicalize(point1);
-        let p2 = self.canonicalize(point2);
-        let x1 = p1.x.clone();
-        let x2 = p2.x.clone();
-        let y1 = p1.y.clone();
-        let y2 = p2.y.clone();
-        if p1.x != p2.x {
-            let m = (&y2 - &y1) * (&x2 - &x1).inverse(&self.p);
-            let x3 = m.power(2) - &x1 - &x2;
-            let y3 = m * (&x1 - &x3) - &y1; 
-            return ECPoint::new(
-                &x3.mod_floor(&self.p),
-                &y3.mod_floor(&self.p),
-                &BigInt::from(1));
-        } else {
-            if y1 != y2 || y1 == Zero::zero() {
-                ECPoint::infinity()
-            } else {
-                let m = (BigInt::from(3) * x1.power(2) + self.a.clone()) * (BigInt::from(2) * y1.clone()).inverse(&self.p);
-                let x3 = m.power(2) - BigInt::from(2) * &x1;
-                let y3 = m * (&x1 - &x3) - &y1;
-                return ECPoint::new(
-                    &x3.mod_floor(&self.p),
-                    &y3.mod_floor(&self.p),
-                    &BigInt::from(1));
+            return point1.clone();
+        }
+        let (z1, z2) = (&point1.z, &point2.z);
+        let z1z1 = z1.square();
+        let z2z2 = z2.square();
+        let u1 = &point1.x * &z2z2;
+        let u2 = &point2.x * &z1z1;
+        let s1 = &point1.y * z2 * z2z2;
+        let s2 = &point2.y * z1 * z1z1;
+        self.jacobian_add_common(u1, u2, s1, s2, z1 * z2, point1)
+    }
+
+    /// P + Q in Jacobian coordinates where Q is affine (Z = 1)
+    pub fn jacobian_mixed_add(&self, point1: &ECPoint, point2: &ECPoint) -> ECPoint {
+        if point1.is_infinity() {
+            return point2.clone();
+        } else if point2.is_infinity() {
+            return point1.clone();
+        }
+        let z1 = &point1.z;
+        let z1z1 = z1.square();
+        let u1 = point1.x.clone();
+        let u2 = &point2.x * &z1z1;
+        let s1 = point1.y.clone();
+        let s2 = &point2.y * z1 * z1z1;
+        self.jacobian_add_common(u1, u2, s1, s2, z1.clone(), point1)
+    }
+
+    fn jacobian_add_common(&self, u1: fp::Fp, u2: fp::Fp, s1: fp::Fp, s2: fp::Fp, z1z2: fp::Fp, point1: &ECPoint) -> ECPoint {
+        let h = u2 - &u1;
+        let r = s2 - &s1;
+        if h.is_zero() {
+            if r.is_zero() {
+                return self.jacobian_double(point1);
             }
+            return ECPoint::infinity();
         }
+        let hh = h.square();
+        let hhh = &h * &hh;
+        let v = u1 * hh;
+        let x3 = r.square() - &hhh - self.field.elem(2) * &v;
+        let y3 = r * (v - &x3) - s1 * hhh;
+        let z3 = z1z2 * h;
+        ECPoint::new(&x3, &y3, &z3)
     }
 
     /// Point negation: -P
@@ -141,87 +358,122 @@ impl EllipticCurve {
         }
         ECPoint::new(
             &point.x,
-            &((-point.clone().y).mod_floor(&self.p)),
+            &-&point.y,
             &point.z)
     }
 
     /// create rational points 
     pub fn create_points(&mut self) {
-        for x in num_iter::range(BigInt::from(0), self.p.clone()) {
-            let xpol = x.power(3)
-                + self.a.clone() * x.clone()
-                + self.b.clone();
-            let xpol = xpol.mod_floor(&self.p);
-            for y in num_iter::range(BigInt::from(0), self.p.clone()) {
-                let ypol = y.power(2);
-                let ypol = ypol.mod_floor(&self.p);
-                if ypol == xpol {
-                    let point = ECPoint::new(&x, &y, &BigInt::from(1));
-                    self.points.push(point.clone());
-                    let minus_point = self.negate(&point);
-                    if minus_point != point {
-                        self.points.push(minus_point);
-                    }
-                    break;
+        self.points.get_or_init(|| self.enumerate_points());
+    }
+
+    fn enumerate_points(&self) -> Vec<ECPoint> {
+        let mut points = Vec::new();
+        for x in num_iter::range(BigInt::from(0), self.p().clone()) {
+            let rhs = self.rhs(&self.field.elem(x.clone())).to_bigint();
+            if let Some(y) = self.sqrt(&rhs) {
+                let minus_y = (self.p() - &y).mod_floor(self.p());
+                let (y0, y1) = if y <= minus_y { (y, minus_y) } else { (minus_y, y) };
+                points.push(self.point(&x, &y0));
+                if y0 != y1 {
+                    points.push(self.point(&x, &y1));
                 }
             }
         }
-        self.points.push(ECPoint::infinity());
+        points.push(ECPoint::infinity());
+        points
     }
 
     /// get all rational points
+    /// enumerates them on first call, only usable for small p
     pub fn points(&self) -> Vec<ECPoint> {
-        self.points.clone()
+        self.rational_points().to_vec()
+    }
+
+    /// all rational points without copying them
+    pub fn rational_points(&self) -> &[ECPoint] {
+        self.points.get_or_init(|| self.enumerate_points())
     }
 
     /// EC cardinality by points count
     pub fn cardinality(&self) -> usize {
-        return self.points.len();
+        self.rational_points().len()
+    }
+
+    /// n * P
+    pub fn checked_multiply_scalar(&self, point: &ECPoint, n: &BigInt) -> Result<ECPoint> {
+        if !self.is_on_curve(point) {
+            return Err(Error::PointNotOnCurve);
+        }
+        Ok(self.multiply_scalar(point, n))
     }
 
     /// n * P 
     pub fn multiply_scalar(&self, point: &ECPoint, n: &BigInt) -> ECPoint {
-        if n == &Zero::zero() {
+        if n.is_zero() || point.is_infinity() {
             return ECPoint::infinity();
         } else if n < &Zero::zero() {
             let minus_np = self.multiply_scalar(point, &(-n));
-            return self.plus(&ECPoint::infinity(), &minus_np);
-        } else if n == &One::one() {
-            return point.clone();
+            return self.negate(&minus_np);
         }
-        let mut e = n.clone();
-        let mut b = point.clone();
+        let base = self.to_affine(point);
         let mut r = ECPoint::infinity();
-        while e > One::one() {
-            if e.is_odd() {
-                r = self.plus(&r, &b.clone());
+        for bit in n.to_str_radix(2).chars() {
+            r = self.jacobian_double(&r);
+            if bit == '1' {
+                r = self.jacobian_mixed_add(&r, &base);
             }
-            let f = self.plus(&b.clone(), &b.clone());
-            b = f;
-            e /= 2;
-        } 
-        self.plus(&r, &b)
+        }
+        self.to_affine(&r)
     }
 
-    /// order of P
-    pub fn point_order(&self, point: &ECPoint) -> BigInt {
-        if point.is_infinity() {
-            return One::one();
-        }
-        let mut x = BigInt::from(2);
-        let mut r_point = point.clone();
-        loop {
-            r_point = self.plus(point, &r_point);
-            if r_point.is_infinity() {
-                return x;
-            }
-            x += 1;
+    /// k * P by Montgomery ladder over the lowest `bits` bits of k
+    ///
+    /// Every bit costs one addition and one doubling, whatever its value.
+    /// The ladder runs on 2^bits + k, so the accumulator starts at P instead of infinity,
+    /// and 2^bits P is subtracted at the end. The formulas still branch if an intermediate
+    /// point is infinity or R0 = -R1, which needs a prefix of 2^bits + k divisible by the order of P.
+    /// BigInt arithmetic itself is not constant time.
+    pub fn multiply_scalar_ladder(&self, point: &ECPoint, k: &BigInt, bits: usize) -> ECPoint {
+        assert!(!k.is_negative() && k.bits() <= bits, "scalar out of range");
+        let base = self.to_affine(point);
+        if base.is_infinity() {
+            return base;
         }
+        let mut r0 = base.clone();
+        let mut r1 = self.jacobian_double(&base);
+        let digits = format!("{:0>width$}", k.to_str_radix(2), width = bits);
+        for bit in digits.bytes() {
+            let bit = bit - b'0';
+            conditional_swap(&self.field, &mut r0, &mut r1, bit);
+            r1 = self.jacobian_add(&r0, &r1);
+            r0 = self.jacobian_double(&r0);
+            conditional_swap(&self.field, &mut r0, &mut r1, bit);
+        }
+        let mut high = base;
+        for _ in 0..bits {
+            high = self.jacobian_double(&high);
+        }
+        self.to_affine(&self.jacobian_add(&r0, &self.negate(&high)))
+    }
+
+    /// order of P by baby-step giant-step over the Hasse interval
+    pub fn point_order(&self, point: &ECPoint) -> BigInt {
+        self.checked_point_order(point).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_point_order(&self, point: &ECPoint) -> Result<BigInt> {
+        discrete_log::point_order_bsgs(self, point)
+    }
+
+    /// E(F_p) = Z/n1 x Z/n2 with n1 | n2 and its generators
+    pub fn group_structure(&self) -> Result<group_structure::GroupStructure> {
+        group_structure::group_structure(self)
     }
 
     pub fn division_points(&self, order: &BigInt) -> ECPointVec {
         let mut vec: Vec<ECPoint> = Vec::new();
-        for point in &self.points {
+        for point in self.rational_points() {
             if self.multiply_scalar(point, order) == ECPoint::infinity() { 
                 vec.push(point.clone());
             }
@@ -229,36 +481,103 @@ impl EllipticCurve {
         ECPointVec(vec)
     }
 
+    /// codomain of the isogeny with the kernel E[order](F_p) by Velu's formulas
     pub fn isogeny(&self, order: &BigInt) -> EllipticCurve {
-        let pointvec = self.division_points(order);
-        let points: vec::Vec<ECPoint> = pointvec.0.clone();
-        let is_odd_order = order.is_odd();
-        let mut v = BigInt::from(0);
-        let mut w = BigInt::from(0);
-        for point in points {
-            if !point.is_infinity() {
-                let gpx = (BigInt::from(3) * point.x.power(2) + &self.a).div_floor(&self.p);
-                let gpy = (- BigInt::from(2) * point.y).div_floor(&self.p);
-                let up = gpy.power(2).div_floor(&self.p);
-                let vp = if is_odd_order { gpx } else { gpx * BigInt::from(2) };
-                v += vp.clone();
-                w += up + point.x * vp;
+        isogeny::Isogeny::from_kernel(self, &self.division_points(order))
+            .unwrap_or_else(|e| panic!("{}", e))
+            .codomain
+    }
+}
+
+/// Lim-Lee comb table of a fixed base point G
+///
+/// A scalar k of `bits` bits is cut into `TEETH` rows of `spacing` bits,
+/// entry j holds sum_i bit_i(j) 2^(i spacing) G + G in affine coordinates.
+/// The extra G keeps every entry away from infinity, so each column costs
+/// one doubling and one mixed addition, and (2^spacing - 1) G is subtracted at the end.
+#[derive(Debug, Clone)]
+pub struct CombTable {
+    spacing: usize,
+    table: Vec<ECPoint>,
+    correction: ECPoint,
+}
+
+impl CombTable {
+    pub const TEETH: usize = 8;
+
+    pub fn new(ec: &EllipticCurve, g: &ECPoint, bits: usize) -> CombTable {
+        let spacing = bits.div_ceil(CombTable::TEETH);
+        let mut rows = vec![ec.to_affine(g)];
+        for i in 1..CombTable::TEETH {
+            let mut r = rows[i - 1].clone();
+            for _ in 0..spacing {
+                r = ec.jacobian_double(&r);
             }
+            rows.push(ec.to_affine(&r));
+        }
+        let mut table = vec![ECPoint::infinity(); 1 << CombTable::TEETH];
+        for j in 1..table.len() {
+            let low = j.trailing_zeros() as usize;
+            table[j] = ec.jacobian_mixed_add(&table[j & (j - 1)], &rows[low]);
         }
-        v = v.div_floor(&self.p);
-        w = w.div_floor(&self.p);
-        let a = (&self.a - BigInt::from(5) * v).div_floor(&self.p);
-        let b = (&self.b - BigInt::from(7) * w).div_floor(&self.p);
-        EllipticCurve::new(&a, &b, &self.p)
+        let g = ec.to_affine(g);
+        let table = table.iter().map(|t| ec.to_affine(&ec.jacobian_mixed_add(t, &g))).collect();
+        let correction = ec.negate(&ec.multiply_scalar(&g, &((BigInt::one() << spacing) - 1)));
+        CombTable { spacing, table, correction }
+    }
+
+    /// k * G, k must be in [0, 2^(TEETH spacing))
+    /// the accumulator starts at the entry of the first column, the entries are never infinity
+    pub fn multiply(&self, ec: &EllipticCurve, k: &BigInt) -> ECPoint {
+        assert!(!k.is_negative() && k.bits() <= self.spacing * CombTable::TEETH, "scalar out of range");
+        let digits: Vec<u8> = format!("{:0>width$}", k.to_str_radix(2), width = self.spacing * CombTable::TEETH)
+            .bytes().rev().map(|b| b - b'0').collect();
+        let mut r = self.select(ec, &digits, self.spacing - 1);
+        for col in (0..self.spacing - 1).rev() {
+            r = ec.jacobian_double(&r);
+            r = ec.jacobian_mixed_add(&r, &self.select(ec, &digits, col));
+        }
+        ec.to_affine(&ec.jacobian_mixed_add(&r, &self.correction))
+    }
+
+    /// entry of a column, every entry is read and masked so the access pattern doesn't depend on k
+    fn select(&self, ec: &EllipticCurve, digits: &[u8], col: usize) -> ECPoint {
+        let mut j = 0;
+        for tooth in 0..CombTable::TEETH {
+            j |= (digits[tooth * self.spacing + col] as usize) << tooth;
+        }
+        let zero = ec.field().zero();
+        let mut r = ECPoint::new(&zero, &zero, &zero);
+        for (i, entry) in self.table.iter().enumerate() {
+            let mask = ec.field().elem((i == j) as u8);
+            r.x += &entry.x * &mask;
+            r.y += &entry.y * &mask;
+            r.z += &entry.z * &mask;
+        }
+        r
+    }
+}
+
+/// swap P and Q if bit is 1, Q - P is added to P and subtracted from Q with the weight bit
+fn conditional_swap(field: &fp::PrimeField, point1: &mut ECPoint, point2: &mut ECPoint, bit: u8) {
+    let bit = field.elem(bit);
+    let coordinates = [(&mut point1.x, &mut point2.x), (&mut point1.y, &mut point2.y), (&mut point1.z, &mut point2.z)];
+    for (a, b) in coordinates {
+        let d = (&*b - &*a) * &bit;
+        *a += &d;
+        *b -= d;
     }
 }
 
 impl ECPoint {
-    pub fn new(x: &BigInt, y:&BigInt, z:&BigInt) -> ECPoint {
+    /// (X, Y, Z) in Jacobian coordinates
+    /// coordinates without field take the field of the others, the fields must agree
+    pub fn new(x: &fp::Fp, y: &fp::Fp, z: &fp::Fp) -> ECPoint {
+        let zero = x.field().or(y.field()).or(z.field()).map_or_else(fp::Fp::zero, |f| f.zero());
         ECPoint {
-            x: x.clone(),
-            y: y.clone(),
-            z: z.clone(),
+            x: x + &zero,
+            y: y + &zero,
+            z: z + zero,
         }
     }
     pub fn infinity() -> ECPoint {
@@ -268,15 +587,51 @@ impl ECPoint {
             z: Zero::zero(),
         }
     }
+    pub fn x(&self) -> &fp::Fp {
+        &self.x
+    }
+    pub fn y(&self) -> &fp::Fp {
+        &self.y
+    }
+    pub fn z(&self) -> &fp::Fp {
+        &self.z
+    }
     pub fn is_infinity(&self) -> bool {
-        self.z == Zero::zero()
+        self.z.is_zero()
+    }
+}
+
+/// projective equality modulo p: X1 Z2^2 = X2 Z1^2 and Y1 Z2^3 = Y2 Z1^3,
+/// all points at infinity are equal, points over different fields are not
+impl PartialEq for ECPoint {
+    fn eq(&self, other: &ECPoint) -> bool {
+        match (self.is_infinity(), other.is_infinity()) {
+            (true, true) => return true,
+            (false, false) => {},
+            _ => return false,
+        }
+        if let (Some(f1), Some(f2)) = (self.z.field(), other.z.field()) {
+            if f1 != f2 {
+                return false;
+            }
+        }
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        &self.x * &z2z2 == &other.x * &z1z1
+            && &self.y * z2z2 * &other.z == &other.y * z1z1 * &self.z
     }
 }
 
+impl Eq for ECPoint {}
+
 impl fmt::Display for EllipticCurve {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "F_{}: y^2 = {}, cardinality:{}, j:{}",
-            self.p, self.pol, self.cardinality(), self.j_invariant()) 
+        write!(f, "F_{}: y^2 = {}, ", self.p(), self.pol)?;
+        // enumerating the points is only feasible for small p
+        if let Some(points) = self.points.get() {
+            write!(f, "cardinality:{}, ", points.len())?;
+        }
+        write!(f, "j:{}", self.j_invariant())
     }
 }
 
@@ -357,8 +712,9 @@ fn elliptic_curve_test1() {
 #[test]
 fn elliptic_curve_test2() {
     let ec = EllipticCurve::new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(5));
-    assert_eq_str!(ec, "F_5: y^2 = x^3 + x + 1, cardinality:9, j:2");
+    assert_eq_str!(ec, "F_5: y^2 = x^3 + x + 1, j:2");
     let points = ec.points();
+    assert_eq_str!(ec, "F_5: y^2 = x^3 + x + 1, cardinality:9, j:2");
     assert_eq!(points.len(), 9);
     assert_eq_str!(points[0], "(0, 1)");
     assert_eq_str!(points[1], "(0, 4)");
@@ -374,16 +730,17 @@ fn elliptic_curve_test2() {
 #[test]
 fn elliptic_curve_test3() {
     let ec = EllipticCurve::new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(5));
+    assert_eq!(ec.cardinality(), 9);
     assert_eq_str!(ec, "F_5: y^2 = x^3 + x + 1, cardinality:9, j:2");
-    assert_eq_str!(ec.point_order(&ec.points[0]), "9");
-    assert_eq_str!(ec.point_order(&ec.points[1]), "9");
-    assert_eq_str!(ec.point_order(&ec.points[2]), "3");
-    assert_eq_str!(ec.point_order(&ec.points[3]), "3");
-    assert_eq_str!(ec.point_order(&ec.points[4]), "9");
-    assert_eq_str!(ec.point_order(&ec.points[5]), "9");
-    assert_eq_str!(ec.point_order(&ec.points[6]), "9");
-    assert_eq_str!(ec.point_order(&ec.points[7]), "9");
-    assert_eq_str!(ec.point_order(&ec.points[8]), "1");
+    assert_eq_str!(ec.point_order(&ec.points()[0]), "9");
+    assert_eq_str!(ec.point_order(&ec.points()[1]), "9");
+    assert_eq_str!(ec.point_order(&ec.points()[2]), "3");
+    assert_eq_str!(ec.point_order(&ec.points()[3]), "3");
+    assert_eq_str!(ec.point_order(&ec.points()[4]), "9");
+    assert_eq_str!(ec.point_order(&ec.points()[5]), "9");
+    assert_eq_str!(ec.point_order(&ec.points()[6]), "9");
+    assert_eq_str!(ec.point_order(&ec.points()[7]), "9");
+    assert_eq_str!(ec.point_order(&ec.points()[8]), "1");
 }
 
 #[test]
@@ -391,7 +748,7 @@ fn elliptic_curve_test4() {
     let ec = EllipticCurve::new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(29));
     println!("{}", ec);
     for x in num_iter::range(0, ec.cardinality()) {
-        println!("P{} {} cardinality {}", x + 1, ec.points[x], ec.point_order(&ec.points[x]));       
+        println!("P{} {} cardinality {}", x + 1, ec.points()[x], ec.point_order(&ec.points()[x]));       
     }
 }
 
@@ -402,12 +759,16 @@ fn elliptic_curve_test5() {
 
     let mut pset = PrimeSet::new();
     for p in pset.iter().skip(2).take(10) { 
-        let ec = EllipticCurve::new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(p));
+        let ec = match EllipticCurve::try_new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(p)) {
+            Ok(ec) => ec,
+            Err(Error::SingularCurve) => continue,
+            Err(e) => panic!("{}", e),
+        };
         print!("{}", ec);
         if is_prime(ec.cardinality() as u64) {
             print!(" cardinality is prime");
         }
-        println!("");
+        println!();
     }
 }
 
@@ -416,8 +777,8 @@ fn isogeny_test1() {
     let ec = EllipticCurve::new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(19));
     println!("{}", ec);
     for x in num_iter::range(0, ec.cardinality()) {
-        let point_order = ec.point_order(&ec.points[x]);
-        println!("P{} {} order {}", x + 1, ec.points[x], point_order);
+        let point_order = ec.point_order(&ec.points()[x]);
+        println!("P{} {} order {}", x + 1, ec.points()[x], point_order);
     }
     let points2 = ec.division_points(&BigInt::from(2));
     assert_eq_str!(points2, "O");
@@ -445,7 +806,6 @@ fn isogeny_test2() {
     assert_eq_str!(points4, "(1164, 0), (1222, 0), (1620, 0), O");
 }
 
-// TODO:
 #[test]
 fn isogeny_test3() {
     use primes::PrimeSet;
@@ -453,16 +813,13 @@ fn isogeny_test3() {
 
     for (_, n) in pset.iter().enumerate().skip(3).take(10) {
         let n: i64 = n as i64;
-        let ec = EllipticCurve::new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(n));
+        let ec = match EllipticCurve::try_new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(n)) {
+            Ok(ec) => ec,
+            Err(Error::SingularCurve) => continue,
+            Err(e) => panic!("{}", e),
+        };
         println!("{}", ec);
-        /*
-        for x in num_iter::range(0, ec.cardinality()) {
-            let point_order = ec.point_order(&ec.points[x]);
-            println!("P{} {} order {}", x + 1, ec.points[x], point_order);
-        }
-        */
         let order = BigInt::from(3);
-        println!("order: {}", order);
         let mut ec_d = ec.clone();
         for _ in num_iter::range(0, 100) {
             let points = ec_d.division_points(&order);
@@ -472,19 +829,163 @@ fn isogeny_test3() {
             }
             ec_d = ec_d.isogeny(&order);
             println!("{}", ec_d);
+            // isogenous curves have the same number of points
+            assert_eq!(ec_d.cardinality(), ec.cardinality());
         }
     }
+}
 
-    /*
-    let points3 = ec.division_points(&BigInt::from(3));
-    //assert_eq_str!(points3, "(2, 7), (2, 12), O");
-    println!("3: {}", points3);
-    let ec3 = ec.isogeny(&BigInt::from(3));
-    println!("{}", ec3);
+#[test]
+fn elliptic_curve_try_new_test() {
+    let (a, b) = (BigInt::from(1), BigInt::from(1));
+    assert_eq!(EllipticCurve::try_new(&a, &b, &BigInt::from(1)).err(), Some(Error::InvalidModulus(BigInt::from(1))));
+    assert_eq!(EllipticCurve::try_new(&a, &b, &BigInt::from(15)).err(), Some(Error::NotPrime(BigInt::from(15))));
+    let ec = EllipticCurve::try_new(&a, &b, &BigInt::from(5)).unwrap();
+    let p = ec.point(&BigInt::from(0), &BigInt::from(1));
+    let q = ec.point(&BigInt::from(1), &BigInt::from(1));
+    assert_eq!(ec.checked_plus(&p, &q), Err(Error::PointNotOnCurve));
+    assert_eq!(ec.checked_multiply_scalar(&q, &BigInt::from(2)), Err(Error::PointNotOnCurve));
+    assert_eq!(ec.checked_plus(&p, &ECPoint::infinity()), Ok(p));
+    // a, b and the coordinates are elements of F_p
+    let ec = EllipticCurve::new(&BigInt::from(-1), &BigInt::from(0), &BigInt::from(5));
+    assert_eq!(ec.a_fp().to_bigint(), BigInt::from(4));
+    let q = ec.point(&BigInt::from(-1), &BigInt::from(7));
+    assert_eq!((q.x().to_bigint(), q.y().to_bigint()), (BigInt::from(4), BigInt::from(2)));
+    assert_eq!(q.z().field(), Some(ec.field()));
+}
 
-    let points4 = ec.division_points(&BigInt::from(4));
-    println!("4: {}", points4);
-    //assert_eq_str!(points7, "(10, 2), (10, 17), (14, 2), (14, 17), (15, 3), (15, 16), O");
-    */
+#[test]
+fn elliptic_curve_singular_test() {
+    let (a, b) = (BigInt::from(1), BigInt::from(1));
+    assert_eq!(EllipticCurve::try_new(&a, &b, &BigInt::from(31)).err(), Some(Error::SingularCurve));
+    assert_eq!(EllipticCurve::try_new(&BigInt::from(-3), &BigInt::from(2), &BigInt::from(7)).err(), Some(Error::SingularCurve));
+    assert_eq!(EllipticCurve::try_new(&a, &b, &BigInt::from(561)).err(), Some(Error::NotPrime(BigInt::from(561))));
+    // larger than u64, points are not enumerated
+    let p = BigInt::from(2).power(127) - 1;
+    let ec = EllipticCurve::try_new(&a, &b, &p).unwrap();
+    assert!(ec.points.get().is_none());
+    assert!(ec.is_on_curve(&ec.point(&BigInt::from(0), &BigInt::from(1))));
+    assert!(!ec.to_string().contains("cardinality"));
+
+    let mut ec = EllipticCurve::try_new(&a, &b, &BigInt::from(5)).unwrap();
+    assert!(ec.points.get().is_none());
+    ec.create_points();
+    assert_eq!(ec.points.get().map(|v| v.len()), Some(9));
+    assert_eq!(ec.cardinality(), 9);
+    let points: Vec<ECPoint> = ec.points();
+    assert_eq!(points.len(), ec.rational_points().len());
 }
 
+#[test]
+fn jacobian_test() {
+    let ec = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    let p = ec.point(&BigInt::from(1120), &BigInt::from(1391));
+    let q = ec.point(&BigInt::from(894), &BigInt::from(1425));
+    // P with Z = 5
+    let e = |v: i64| ec.field().elem(v);
+    let pj = ECPoint::new(&e(1120 * 25), &e(1391 * 125), &e(5));
+    assert!(ec.is_on_curve(&pj));
+    assert!(ec.point_eq(&p, &pj));
+    assert!(!ec.point_eq(&q, &pj));
+    assert_eq!(p, pj);
+    assert_ne!(q, pj);
+    assert_eq!(ec.to_affine(&pj), p);
+    assert_eq!(ec.point(&BigInt::from(2), &BigInt::from(3)), ECPoint::new(&e(8), &e(24), &e(2)));
+    assert_ne!(ec.point(&BigInt::from(2), &BigInt::from(3)), ECPoint::new(&e(8), &e(2003 - 24), &e(2)));
+    // same coordinates over another field
+    let other = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2011));
+    assert_ne!(p, other.point(&BigInt::from(1120), &BigInt::from(1391)));
+    assert_eq!(ECPoint::infinity(), ECPoint::new(&e(5), &e(7), &e(0)));
+
+    assert_eq_str!(ec.to_affine(&ec.jacobian_add(&pj, &q)), "(1683, 1388)");
+    assert_eq_str!(ec.to_affine(&ec.jacobian_mixed_add(&pj, &q)), "(1683, 1388)");
+    assert_eq!(ec.to_affine(&ec.jacobian_double(&pj)), ec.plus(&p, &p));
+    assert_eq!(ec.jacobian_add(&pj, &ec.negate(&p)), ECPoint::infinity());
+    assert!(ec.point_eq(&ec.jacobian_add(&pj, &p), &ec.jacobian_double(&pj)));
+    assert_eq!(ec.jacobian_add(&pj, &p), ec.jacobian_double(&pj));
+
+    let mut r = ECPoint::infinity();
+    for n in 0..40 {
+        assert_eq!(ec.multiply_scalar(&pj, &BigInt::from(n)), r, "n:{}", n);
+        r = ec.plus(&r, &p);
+    }
+    assert_eq!(ec.multiply_scalar(&p, &BigInt::from(-3)), ec.negate(&ec.multiply_scalar(&p, &BigInt::from(3))));
+}
+
+#[test]
+fn multiply_scalar_ladder_test() {
+    let ec = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    let p = ec.point(&BigInt::from(1120), &BigInt::from(1391));
+    let table = CombTable::new(&ec, &p, 12);
+    for n in 0..2100 {
+        let k = BigInt::from(n);
+        let r = ec.multiply_scalar(&p, &k);
+        assert_eq!(ec.multiply_scalar_ladder(&p, &k, 12), r, "n:{}", n);
+        assert_eq!(table.multiply(&ec, &k), r, "n:{}", n);
+    }
+}
+
+#[test]
+fn sqrt_test() {
+    // p = 3 mod 4, p = 5 mod 8 and p = 1 mod 16
+    for p in [19, 29, 97, 113, 257].iter() {
+        let ec = EllipticCurve::new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(*p));
+        let mut squares = 0;
+        for a in 0..*p {
+            let a = BigInt::from(a);
+            if let Some(r) = ec.sqrt(&a) {
+                assert_eq!((&r * &r).mod_floor(ec.p()), a, "p:{}", p);
+                squares += 1;
+            }
+        }
+        assert_eq!(squares, (p + 1) / 2, "p:{}", p);
+    }
+}
+
+#[test]
+fn sec1_test() {
+    let ec = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    for point in ec.rational_points() {
+        for compressed in [true, false].iter() {
+            let bytes = ec.encode_point(point, *compressed);
+            assert_eq!(bytes.len(), if point.is_infinity() { 1 } else if *compressed { 3 } else { 5 });
+            assert_eq!(ec.decode_point(&bytes), Ok(point.clone()));
+        }
+    }
+    assert_eq!(ec.encode_point(&ec.point(&BigInt::from(1120), &BigInt::from(1391)), true), vec![3, 4, 0x60]);
+    assert_eq!(ec.decode_point(&[4, 4, 0x60, 5, 0x70]), Err(Error::PointNotOnCurve));
+    assert_eq!(ec.decode_point(&[4, 0xff, 0xff, 0, 1]), Err(Error::PointNotOnCurve));
+    assert!(ec.decode_point(&[5, 4, 0x60]).is_err());
+    assert!(ec.decode_point(&[2, 4]).is_err());
+    assert!(ec.decode_point(&[]).is_err());
+    // x with no point on the curve
+    let x = (0..2003).find(|x| ec.sqrt(&ec.rhs(&ec.field().elem(*x)).to_bigint()).is_none()).unwrap();
+    assert_eq!(ec.decode_point(&[2, (x >> 8) as u8, x as u8]), Err(Error::PointNotOnCurve));
+}
+
+#[test]
+fn create_points_test() {
+    // enumeration by square roots agrees with Schoof
+    let (a, b, p) = (BigInt::from(2), BigInt::from(3), BigInt::from(10007));
+    let ec = EllipticCurve::new(&a, &b, &p);
+    assert_eq!(BigInt::from(ec.cardinality()), super::schoof::count_points(&a, &b, &p));
+    assert!(ec.points().iter().all(|point| ec.is_on_curve(point)));
+    let p1 = ec.lift_x(ec.points()[0].x().value(), true).unwrap();
+    assert!(p1.y().value().is_odd() && ec.is_on_curve(&p1));
+}
+
+#[test]
+fn random_point_test() {
+    let mut rng = rand::thread_rng();
+    let ec = EllipticCurve::new(&BigInt::from(1), &BigInt::from(1), &BigInt::from(5));
+    let mut seen = std::collections::HashSet::new();
+    for _ in 0..200 {
+        let point = ec.random_point(&mut rng);
+        assert!(ec.is_on_curve(&point) && !point.is_infinity());
+        seen.insert(point.to_string());
+    }
+    assert_eq!(seen.len(), ec.cardinality() - 1);
+    let p = BigInt::from(2).power(127) - 1;
+    let ec = EllipticCurve::try_new(&BigInt::from(1), &BigInt::from(1), &p).unwrap();
+    assert!(ec.is_on_curve(&ec.random_point(&mut rng)));
+}
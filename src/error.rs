@@ -0,0 +1,92 @@
+use num_bigint::BigInt;
+use std::fmt;
+
+/// error of this crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// modulus must be >= 2
+    InvalidModulus(BigInt),
+    /// number must be prime
+    NotPrime(BigInt),
+    /// polynomial must be irreducible
+    NotIrreducible,
+    /// prime which the algorithm can't handle
+    UnsupportedPrime(BigInt),
+    /// j-invariant 0 or 1728 which the algorithm can't handle
+    UnsupportedJInvariant(BigInt),
+    /// root which is not simple
+    MultipleRoot(BigInt),
+    /// 4 a^3 + 27 b^2 = 0
+    SingularCurve,
+    PointNotOnCurve,
+    DivisionByZero,
+    /// element has no inverse
+    NotInvertible,
+    /// operands belong to different prime fields
+    MismatchedField,
+    /// element is not bound to a prime field
+    UnknownField,
+    /// divisor has two or more terms
+    NonMonomialDivisor,
+    /// product of two subscripted variables
+    VariableProduct,
+    /// power of a subscripted variable
+    VariablePower,
+    /// divisor with a subscripted variable
+    VariableDivisor,
+    NegativeExponent,
+    /// exponent does not fit in i32
+    ExponentOverflow,
+    /// polynomial is not a constant
+    NotScalar,
+    /// polynomial has terms other than x^n (n >= 0)
+    NotUnivariate,
+    /// subscripted variable out of range
+    InvalidVariable,
+    /// index of subscripted variable out of range
+    InvalidIndex(u64),
+    /// argument out of range
+    InvalidArgument(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidModulus(p) => write!(f, "modulus must be >= 2: {}", p),
+            Error::NotPrime(p) => write!(f, "not prime: {}", p),
+            Error::NotIrreducible => write!(f, "polynomial is not irreducible"),
+            Error::UnsupportedPrime(p) => write!(f, "unsupported prime: {}", p),
+            Error::UnsupportedJInvariant(j) => write!(f, "unsupported j-invariant: {}", j),
+            Error::MultipleRoot(r) => write!(f, "multiple root: {}", r),
+            Error::SingularCurve => write!(f, "singular curve"),
+            Error::PointNotOnCurve => write!(f, "point is not on curve"),
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::NotInvertible => write!(f, "not invertible"),
+            Error::MismatchedField => write!(f, "mismatched field"),
+            Error::UnknownField => write!(f, "field of the element is unknown"),
+            Error::NonMonomialDivisor => write!(f, "divisor has two or more terms"),
+            Error::VariableProduct => write!(f, "can't multiply another variable"),
+            Error::VariablePower => write!(f, "variable can't power"),
+            Error::VariableDivisor => write!(f, "can't divide by a variable"),
+            Error::NegativeExponent => write!(f, "negative exponent"),
+            Error::ExponentOverflow => write!(f, "exponent overflow"),
+            Error::NotScalar => write!(f, "not scalar"),
+            Error::NotUnivariate => write!(f, "not univariate polynomial"),
+            Error::InvalidVariable => write!(f, "invalid variable"),
+            Error::InvalidIndex(i) => write!(f, "invalid index: {}", i),
+            Error::InvalidArgument(s) => write!(f, "invalid argument: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[test]
+fn error_display_test() {
+    assert_eq_str!(Error::NotPrime(BigInt::from(15)), "not prime: 15");
+    assert_eq_str!(Error::DivisionByZero, "division by zero");
+    let r: Result<()> = Err(Error::SingularCurve);
+    assert_eq!(r, Err(Error::SingularCurve));
+}
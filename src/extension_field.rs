@@ -0,0 +1,378 @@
+use num_bigint::BigInt;
+use num_traits::Zero;
+use std::{fmt, ops};
+use std::sync::Arc;
+use super::bigint::{self, Power};
+use super::dense_polynomial::DensePolynomial;
+use super::error::{Error, Result};
+use super::field::FieldElement;
+use super::fp;
+use super::polynomial::Polynomial;
+
+/// extension field F_{p^k} = F_p[x] / (f(x))
+/// f is a monic irreducible polynomial of degree k
+#[derive(Debug, Clone)]
+pub struct ExtensionField {
+    modulus: Arc<DensePolynomial>,
+}
+
+/// element of F_{p^k}, a polynomial of degree < k
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fpk {
+    value: DensePolynomial,
+    field: ExtensionField,
+}
+
+impl ExtensionField {
+    pub fn new(base: &fp::PrimeField, modulus: &Polynomial) -> ExtensionField {
+        ExtensionField::try_new(base, modulus).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// modulus must be irreducible over F_p and of degree >= 1, it is made monic
+    pub fn try_new(base: &fp::PrimeField, modulus: &Polynomial) -> Result<ExtensionField> {
+        ExtensionField::try_from_dense(&DensePolynomial::try_from_polynomial(modulus, base)?)
+    }
+
+    pub fn try_from_dense(modulus: &DensePolynomial) -> Result<ExtensionField> {
+        if modulus.is_zero() || modulus.degree() == 0 {
+            return Err(Error::InvalidArgument(format!("modulus must have degree >= 1: {}", modulus)));
+        }
+        let modulus = modulus.to_monic();
+        if !modulus.is_irreducible() {
+            return Err(Error::NotIrreducible);
+        }
+        Ok(ExtensionField {
+            modulus: Arc::new(modulus),
+        })
+    }
+
+    /// F_p
+    pub fn base(&self) -> &fp::PrimeField {
+        self.modulus.field()
+    }
+
+    pub fn modulus(&self) -> &DensePolynomial {
+        &self.modulus
+    }
+
+    /// k = [F_{p^k} : F_p]
+    pub fn degree(&self) -> usize {
+        self.modulus.degree()
+    }
+
+    /// p^k
+    pub fn order(&self) -> BigInt {
+        self.base().p().power(self.degree() as i32)
+    }
+
+    /// element represented by the polynomial in x, reduced mod f
+    pub fn elem(&self, value: &Polynomial) -> Fpk {
+        self.elem_dense(&DensePolynomial::from_polynomial(value, self.base()))
+    }
+
+    pub fn elem_dense(&self, value: &DensePolynomial) -> Fpk {
+        assert!(value.field() == self.base(), "mismatched field {} {}", value.field(), self.base());
+        Fpk {
+            value: value % self.modulus.as_ref(),
+            field: self.clone(),
+        }
+    }
+
+    /// element of F_p embedded in F_{p^k}
+    pub fn from_base(&self, a: &fp::Fp) -> Fpk {
+        self.elem_dense(&DensePolynomial::new(self.base(), vec![a.to_bigint()]))
+    }
+
+    pub fn from_int<T: Into<BigInt>>(&self, n: T) -> Fpk {
+        self.from_base(&self.base().elem(n))
+    }
+
+    pub fn zero(&self) -> Fpk {
+        self.from_int(0)
+    }
+
+    pub fn one(&self) -> Fpk {
+        self.from_int(1)
+    }
+
+    /// the class of x, a root of f
+    pub fn generator(&self) -> Fpk {
+        self.elem_dense(&DensePolynomial::x(self.base()))
+    }
+}
+
+impl PartialEq for ExtensionField {
+    fn eq(&self, other: &Self) -> bool {
+        self.modulus == other.modulus
+    }
+}
+impl Eq for ExtensionField {}
+
+impl fmt::Display for ExtensionField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GF({})[x]/({})", self.base().p(), self.modulus)
+    }
+}
+
+impl Fpk {
+    /// polynomial of degree < k
+    pub fn value(&self) -> &DensePolynomial {
+        &self.value
+    }
+
+    pub fn field(&self) -> &ExtensionField {
+        &self.field
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.value.is_one()
+    }
+
+    /// Some(a) if self is in F_p
+    pub fn to_base(&self) -> Option<fp::Fp> {
+        if self.value.degree() == 0 {
+            Some(self.value.coef(0))
+        } else {
+            None
+        }
+    }
+
+    pub fn square(&self) -> Fpk {
+        self * self
+    }
+
+    /// 1/a
+    pub fn inv(&self) -> Fpk {
+        self.checked_inv().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// 1/a by the extended euclid algorithm on a and f
+    pub fn checked_inv(&self) -> Result<Fpk> {
+        match self.value.inverse_mod(&self.field.modulus) {
+            Some(value) => Ok(Fpk { value, field: self.field.clone() }),
+            None => Err(Error::NotInvertible),
+        }
+    }
+
+    /// a^(p^i)
+    pub fn frobenius_power(&self, i: usize) -> Fpk {
+        let mut r = self.clone();
+        for _ in 0..i % self.field.degree() {
+            r = r.frobenius();
+        }
+        r
+    }
+
+    fn check_field(&self, other: &Fpk) -> Result<()> {
+        if self.field != other.field {
+            return Err(Error::MismatchedField);
+        }
+        Ok(())
+    }
+
+    pub fn checked_add(&self, other: &Fpk) -> Result<Fpk> {
+        self.check_field(other)?;
+        Ok(Fpk { value: &self.value + &other.value, field: self.field.clone() })
+    }
+
+    pub fn checked_sub(&self, other: &Fpk) -> Result<Fpk> {
+        self.check_field(other)?;
+        Ok(Fpk { value: &self.value - &other.value, field: self.field.clone() })
+    }
+
+    pub fn checked_mul(&self, other: &Fpk) -> Result<Fpk> {
+        self.check_field(other)?;
+        Ok(self.field.elem_dense(&(&self.value * &other.value)))
+    }
+
+    pub fn checked_div(&self, other: &Fpk) -> Result<Fpk> {
+        self.check_field(other)?;
+        if other.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        self.checked_mul(&other.checked_inv()?)
+    }
+}
+
+impl_op_ex!(+ |a: &Fpk, b: &Fpk| -> Fpk {
+    a.checked_add(b).unwrap_or_else(|e| panic!("{}", e))
+});
+
+impl_op_ex!(- |a: &Fpk, b: &Fpk| -> Fpk {
+    a.checked_sub(b).unwrap_or_else(|e| panic!("{}", e))
+});
+
+impl_op_ex!(* |a: &Fpk, b: &Fpk| -> Fpk {
+    a.checked_mul(b).unwrap_or_else(|e| panic!("{}", e))
+});
+
+impl_op_ex!(/ |a: &Fpk, b: &Fpk| -> Fpk {
+    a.checked_div(b).unwrap_or_else(|e| panic!("{}", e))
+});
+
+impl_op_ex!(- |a: &Fpk| -> Fpk {
+    Fpk { value: -&a.value, field: a.field.clone() }
+});
+
+impl_op_ex!(+= |a: &mut Fpk, b: &Fpk| {
+    *a = &*a + b;
+});
+
+impl_op_ex!(-= |a: &mut Fpk, b: &Fpk| {
+    *a = &*a - b;
+});
+
+impl_op_ex!(*= |a: &mut Fpk, b: &Fpk| {
+    *a = &*a * b;
+});
+
+impl_op_ex!(/= |a: &mut Fpk, b: &Fpk| {
+    *a = &*a / b;
+});
+
+/// Fpk ^ n
+impl Power<&BigInt> for Fpk {
+    fn power(&self, n: &BigInt) -> Self {
+        if n < &Zero::zero() {
+            return self.inv().power(&(-n));
+        }
+        Fpk {
+            value: self.value.powmod(n, &self.field.modulus),
+            field: self.field.clone(),
+        }
+    }
+}
+
+impl Power<BigInt> for Fpk {
+    fn power(&self, n: BigInt) -> Self {
+        self.power(&n)
+    }
+}
+
+impl Power<i32> for Fpk {
+    fn power(&self, n: i32) -> Self {
+        self.power(&BigInt::from(n))
+    }
+}
+
+impl FieldElement for Fpk {
+    fn elem_like(&self, n: &BigInt) -> Self {
+        self.field.from_int(n.clone())
+    }
+
+    fn checked_inverse(&self) -> Result<Self> {
+        self.checked_inv()
+    }
+
+    fn characteristic(&self) -> BigInt {
+        self.field.base().p().clone()
+    }
+
+    fn field_order(&self) -> BigInt {
+        self.field.order()
+    }
+
+    fn random_like<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        let base = self.field.base();
+        let coefs = (0..self.field.degree()).map(|_| bigint::random_below(rng, base.p())).collect();
+        self.field.elem_dense(&DensePolynomial::new(base, coefs))
+    }
+}
+
+impl fmt::Display for Fpk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.value.is_zero() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", self.value)
+        }
+    }
+}
+
+#[cfg(test)]
+fn x_pol(coefs: &[i32]) -> Polynomial {
+    use super::term_builder::{self, TermBuildable};
+    let mut pol = Polynomial::new();
+    for (i, c) in coefs.iter().enumerate() {
+        pol += term_builder::TermBuilder::new().coef(*c).xpow(i as i32).build();
+    }
+    pol
+}
+
+#[test]
+fn extension_field_test() {
+    let base = fp::PrimeField::new(&BigInt::from(7));
+    // F_49 = F_7[i] / (i^2 + 1)
+    let f = ExtensionField::new(&base, &x_pol(&[1, 0, 1]));
+    assert_eq!(f.degree(), 2);
+    assert_eq!(f.order(), BigInt::from(49));
+    let i = f.generator();
+    let a = f.elem(&x_pol(&[3, 2]));
+    let b = f.elem(&x_pol(&[5, 6]));
+    assert_eq!(i.square(), -f.one());
+    assert_eq!(&a + &b, f.elem(&x_pol(&[1, 1])));
+    assert_eq!(&a - &b, f.elem(&x_pol(&[5, 3])));
+    // (3 + 2i)(5 + 6i) = 15 - 12 + 28i
+    assert_eq!(&a * &b, f.elem(&x_pol(&[3, 0])));
+    assert_eq!(&(&a / &b) * &b, a);
+    assert_eq!(&a * &a.inv(), f.one());
+    assert!(f.zero().checked_inv().is_err());
+    // Frobenius is the conjugation i -> -i
+    assert_eq!(a.frobenius(), f.elem(&x_pol(&[3, -2])));
+    assert_eq!(a.frobenius_power(2), a);
+    assert_eq!((&a * &a.frobenius()).to_base(), Some(base.elem(13)));
+    assert_eq!(a.power(&f.order()), a);
+    assert_eq!(a.power(-1), a.inv());
+    assert_eq!(f.from_int(10), f.from_int(3));
+    assert_eq!(x_pol(&[1, 0, 1]).to_string(), f.modulus().to_string());
+
+    // half of the 48 nonzero elements of F_49 are squares, and 0: 25 in total
+    let mut squares = 0;
+    for c0 in 0..7 {
+        for c1 in 0..7 {
+            let x = f.elem(&x_pol(&[c0, c1]));
+            if let Some(r) = x.sqrt() {
+                assert_eq!(r.square(), x);
+                squares += 1;
+            }
+            if c1 == 0 {
+                // F_7 is in the squares of F_49
+                assert!(x.sqrt().is_some());
+            }
+        }
+    }
+    assert_eq!(squares, 25);
+
+    let mut rng = rand::thread_rng();
+    // F_{13^3} = F_13[t] / (t^3 + 2), q - 1 = 2^2 * 3 * 183
+    let base = fp::PrimeField::new(&BigInt::from(13));
+    let f = ExtensionField::new(&base, &x_pol(&[2, 0, 0, 1]));
+    for _ in 0..10 {
+        let a = f.one().random_like(&mut rng);
+        if a.is_zero() {
+            continue;
+        }
+        assert_eq!(a.power(&(f.order() - 1u32)), f.one());
+        assert_eq!(a.frobenius_power(3), a);
+        let s = a.square();
+        assert_eq!(s.sqrt().map(|r| r.square()), Some(s));
+    }
+}
+
+#[test]
+fn extension_field_error_test() {
+    let base = fp::PrimeField::new(&BigInt::from(7));
+    // x^2 - 1 and x^4 + 1 = (x^2 + 3x + 1)(x^2 - 3x + 1) are reducible over F_7
+    assert_eq!(ExtensionField::try_new(&base, &x_pol(&[-1, 0, 1])), Err(Error::NotIrreducible));
+    assert_eq!(ExtensionField::try_new(&base, &x_pol(&[1, 0, 0, 0, 1])), Err(Error::NotIrreducible));
+    assert!(ExtensionField::try_new(&base, &x_pol(&[3])).is_err());
+    // 2 x^3 + 4 is made monic
+    let f = ExtensionField::new(&base, &x_pol(&[4, 0, 0, 2]));
+    assert_eq!(f.modulus().to_string(), x_pol(&[2, 0, 0, 1]).to_string());
+    let g = ExtensionField::new(&base, &x_pol(&[1, 0, 1]));
+    assert_eq!(f.one().checked_add(&g.one()), Err(Error::MismatchedField));
+}
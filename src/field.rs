@@ -0,0 +1,168 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use std::{fmt, ops};
+use super::bigint::{self, Power};
+use super::error::{Error, Result};
+use super::fp;
+
+/// element of a finite field F_q, q = p^k
+///
+/// Constants are made from an existing element (zero_like, one_like, elem_like),
+/// since an element carries its field.
+pub trait FieldElement: Clone + PartialEq + fmt::Debug + fmt::Display
+    + ops::Add<Output = Self> + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self> + ops::Div<Output = Self> + ops::Neg<Output = Self>
+    + for<'a> Power<&'a BigInt>
+{
+    /// n as an element of the field of self
+    fn elem_like(&self, n: &BigInt) -> Self;
+
+    fn zero_like(&self) -> Self {
+        self.elem_like(&BigInt::zero())
+    }
+
+    fn one_like(&self) -> Self {
+        self.elem_like(&BigInt::one())
+    }
+
+    fn is_zero_elem(&self) -> bool {
+        *self == self.zero_like()
+    }
+
+    fn checked_inverse(&self) -> Result<Self>;
+
+    /// p
+    fn characteristic(&self) -> BigInt;
+
+    /// p, Err if the element doesn't know its field
+    fn try_characteristic(&self) -> Result<BigInt> {
+        Ok(self.characteristic())
+    }
+
+    /// q = p^k
+    fn field_order(&self) -> BigInt;
+
+    /// uniformly random element of the field of self
+    fn random_like<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Self;
+
+    /// a square root, None if self is not a square
+    fn sqrt(&self) -> Option<Self> {
+        sqrt_tonelli_shanks(self)
+    }
+
+    /// a^p
+    fn frobenius(&self) -> Self {
+        self.power(&self.characteristic())
+    }
+}
+
+/// square root in F_q for odd q by Tonelli-Shanks
+pub fn sqrt_tonelli_shanks<F: FieldElement>(a: &F) -> Option<F> {
+    if a.is_zero_elem() {
+        return Some(a.clone());
+    }
+    let one = a.one_like();
+    let q = a.field_order();
+    let half: BigInt = (&q - 1u32) >> 1usize;
+    if a.power(&half) != one {
+        return None;
+    }
+    if (&q % 4u32) == BigInt::from(3) {
+        return Some(a.power(&((&q + 1u32) >> 2usize)));
+    }
+    // q - 1 = 2^s t
+    let mut t: BigInt = &q - 1u32;
+    let mut s = 0;
+    while t.is_even() {
+        t >>= 1usize;
+        s += 1;
+    }
+    let mut rng = rand::thread_rng();
+    let z = loop {
+        let z = a.random_like(&mut rng);
+        if !z.is_zero_elem() && z.power(&half) != one {
+            break z;
+        }
+    };
+    let mut m = s;
+    let mut c = z.power(&t);
+    let mut x = a.power(&((&t + 1u32) >> 1usize));
+    let mut b = a.power(&t);
+    while b != one {
+        // b has order 2^i
+        let mut i = 0;
+        let mut b2 = b.clone();
+        while b2 != one {
+            b2 = b2.clone() * b2;
+            i += 1;
+        }
+        let mut d = c;
+        for _ in 0..m - i - 1 {
+            d = d.clone() * d;
+        }
+        x = x * d.clone();
+        c = d.clone() * d;
+        b = b * c.clone();
+        m = i;
+    }
+    Some(x)
+}
+
+/// elements without field (Zero::zero(), One::one()) can't make constants and panic,
+/// GenericCurve::try_new binds them to the field of the other coefficient
+impl FieldElement for fp::Fp {
+    fn elem_like(&self, n: &BigInt) -> Self {
+        self.field().expect("element is not bound to a prime field").elem(n.clone())
+    }
+
+    fn checked_inverse(&self) -> Result<Self> {
+        self.checked_inv()
+    }
+
+    fn characteristic(&self) -> BigInt {
+        self.field().expect("element is not bound to a prime field").p().clone()
+    }
+
+    fn try_characteristic(&self) -> Result<BigInt> {
+        self.field().map(|f| f.p().clone()).ok_or(Error::UnknownField)
+    }
+
+    fn field_order(&self) -> BigInt {
+        self.characteristic()
+    }
+
+    fn random_like<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        let p = self.characteristic();
+        self.elem_like(&bigint::random_below(rng, &p))
+    }
+
+    fn sqrt(&self) -> Option<Self> {
+        let p = self.characteristic();
+        bigint::sqrt_modulo(self.value(), &p).map(|r| self.elem_like(&r))
+    }
+
+    fn frobenius(&self) -> Self {
+        self.clone()
+    }
+}
+
+#[test]
+fn sqrt_tonelli_shanks_test() {
+    // 10009 = 2^3 * 1251 + 1 goes through the Tonelli-Shanks loop
+    for p in [10007, 10009, 40961].iter() {
+        let f = fp::PrimeField::new(&BigInt::from(*p));
+        let mut squares = 0;
+        for a in 0..200 {
+            let a = f.elem(a);
+            if let Some(r) = sqrt_tonelli_shanks(&a) {
+                assert_eq!(r.square(), a);
+                assert_eq!(a.sqrt().map(|r| r.square()), Some(a.clone()));
+                squares += 1;
+            } else {
+                assert_eq!(a.sqrt(), None);
+            }
+        }
+        assert!(squares > 80 && squares < 120);
+    }
+}
@@ -0,0 +1,323 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Zero, One};
+use std::{fmt, ops};
+use std::sync::Arc;
+use super::bigint::{Power, PowerModulo, Inverse};
+use super::error::{Error, Result};
+
+/// prime field F_p
+/// the modulus is shared between all elements of the field
+#[derive(Debug, Clone)]
+pub struct PrimeField {
+    p: Arc<BigInt>,
+}
+
+/// element of F_p
+/// value is always in [0, p)
+/// NOTE: Zero::zero() and One::one() can't know p,
+/// so they make an element without field, which adopts the field of the other operand.
+#[derive(Debug, Clone)]
+pub struct Fp {
+    value: BigInt,
+    field: Option<PrimeField>,
+}
+
+impl PrimeField {
+    pub fn new(p: &BigInt) -> PrimeField {
+        PrimeField::try_new(p).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// p must be >= 2, primality is not checked
+    pub fn try_new(p: &BigInt) -> Result<PrimeField> {
+        if p < &BigInt::from(2) {
+            return Err(Error::InvalidModulus(p.clone()));
+        }
+        Ok(PrimeField {
+            p: Arc::new(p.clone()),
+        })
+    }
+
+    /// characteristic
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    /// element of this field, reduced mod p
+    pub fn elem<T: Into<BigInt>>(&self, value: T) -> Fp {
+        Fp {
+            value: value.into().mod_floor(&self.p),
+            field: Some(self.clone()),
+        }
+    }
+
+    pub fn zero(&self) -> Fp {
+        self.elem(0)
+    }
+
+    pub fn one(&self) -> Fp {
+        self.elem(1)
+    }
+}
+
+impl PartialEq for PrimeField {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.p, &other.p) || self.p == other.p
+    }
+}
+impl Eq for PrimeField {}
+
+impl fmt::Display for PrimeField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "F_{}", self.p)
+    }
+}
+
+impl Fp {
+    /// canonical representative in [0, p)
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn to_bigint(&self) -> BigInt {
+        self.value.clone()
+    }
+
+    pub fn field(&self) -> Option<&PrimeField> {
+        self.field.as_ref()
+    }
+
+    pub fn square(&self) -> Fp {
+        self * self
+    }
+
+    /// 1/a
+    pub fn inv(&self) -> Fp {
+        self.checked_inv().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_inv(&self) -> Result<Fp> {
+        let field = self.field.clone().ok_or(Error::UnknownField)?;
+        if self.value.is_zero() {
+            return Err(Error::NotInvertible);
+        }
+        Ok(Fp {
+            value: self.value.inverse(field.p()),
+            field: Some(field),
+        })
+    }
+
+    pub fn checked_add(&self, other: &Fp) -> Result<Fp> {
+        Ok(make(&self.value + &other.value, try_common_field(self, other)?))
+    }
+
+    pub fn checked_sub(&self, other: &Fp) -> Result<Fp> {
+        Ok(make(&self.value - &other.value, try_common_field(self, other)?))
+    }
+
+    pub fn checked_mul(&self, other: &Fp) -> Result<Fp> {
+        Ok(make(&self.value * &other.value, try_common_field(self, other)?))
+    }
+
+    pub fn checked_div(&self, other: &Fp) -> Result<Fp> {
+        let field = try_common_field(self, other)?.ok_or(Error::UnknownField)?;
+        if other.value.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        Ok(self.bind(&field) * other.bind(&field).inv())
+    }
+
+    fn bind(&self, field: &PrimeField) -> Fp {
+        match &self.field {
+            Some(f) => {
+                assert!(f == field, "mismatched field {} {}", f, field);
+                self.clone()
+            }
+            None => field.elem(self.value.clone()),
+        }
+    }
+}
+
+/// field of the result of a binary operation
+fn try_common_field(a: &Fp, b: &Fp) -> Result<Option<PrimeField>> {
+    match (&a.field, &b.field) {
+        (Some(fa), Some(fb)) => {
+            if fa != fb {
+                return Err(Error::MismatchedField);
+            }
+            Ok(Some(fa.clone()))
+        }
+        (Some(fa), None) => Ok(Some(fa.clone())),
+        (None, Some(fb)) => Ok(Some(fb.clone())),
+        (None, None) => Ok(None),
+    }
+}
+
+fn common_field(a: &Fp, b: &Fp) -> Option<PrimeField> {
+    try_common_field(a, b).unwrap_or_else(|e| panic!("{}", e))
+}
+
+fn make(value: BigInt, field: Option<PrimeField>) -> Fp {
+    match field {
+        Some(f) => f.elem(value),
+        None => Fp { value, field: None },
+    }
+}
+
+impl_op_ex!(+ |a: &Fp, b: &Fp| -> Fp {
+    make(&a.value + &b.value, common_field(a, b))
+});
+
+impl_op_ex!(- |a: &Fp, b: &Fp| -> Fp {
+    make(&a.value - &b.value, common_field(a, b))
+});
+
+impl_op_ex!(* |a: &Fp, b: &Fp| -> Fp {
+    make(&a.value * &b.value, common_field(a, b))
+});
+
+impl_op_ex!(/ |a: &Fp, b: &Fp| -> Fp {
+    a.checked_div(b).unwrap_or_else(|e| panic!("{}", e))
+});
+
+impl_op_ex!(- |a: &Fp| -> Fp {
+    make(-&a.value, a.field.clone())
+});
+
+impl_op_ex!(+= |a: &mut Fp, b: &Fp| {
+    *a = &*a + b;
+});
+
+impl_op_ex!(-= |a: &mut Fp, b: &Fp| {
+    *a = &*a - b;
+});
+
+impl_op_ex!(*= |a: &mut Fp, b: &Fp| {
+    *a = &*a * b;
+});
+
+impl_op_ex!(/= |a: &mut Fp, b: &Fp| {
+    *a = &*a / b;
+});
+
+impl PartialEq for Fp {
+    fn eq(&self, other: &Self) -> bool {
+        match common_field(self, other) {
+            Some(f) => self.bind(&f).value == other.bind(&f).value,
+            None => self.value == other.value,
+        }
+    }
+}
+impl Eq for Fp {}
+
+impl Zero for Fp {
+    fn zero() -> Self {
+        Fp { value: Zero::zero(), field: None }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+}
+
+impl One for Fp {
+    fn one() -> Self {
+        Fp { value: One::one(), field: None }
+    }
+}
+
+/// Fp ^ n
+impl Power<&BigInt> for Fp {
+    fn power(&self, n: &BigInt) -> Self {
+        if n < &Zero::zero() {
+            return self.inv().power(&(-n));
+        }
+        match &self.field {
+            Some(f) => Fp {
+                value: self.value.power_modulo(n, f.p()),
+                field: Some(f.clone()),
+            },
+            None => Fp {
+                value: self.value.power(n),
+                field: None,
+            },
+        }
+    }
+}
+
+impl Power<BigInt> for Fp {
+    fn power(&self, n: BigInt) -> Self {
+        self.power(&n)
+    }
+}
+
+impl Power<i32> for Fp {
+    fn power(&self, n: i32) -> Self {
+        self.power(&BigInt::from(n))
+    }
+}
+
+/// 1/Fp
+/// p must be the characteristic of the element
+impl Inverse for Fp {
+    fn inverse(&self, p: &BigInt) -> Self {
+        self.bind(&PrimeField::new(p)).inv()
+    }
+}
+
+impl fmt::Display for Fp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+#[test]
+fn fp_arithmetic_test() {
+    let f = PrimeField::new(&BigInt::from(19));
+    let a = f.elem(7);
+    let b = f.elem(15);
+    assert_eq_str!(&a + &b, "3");
+    assert_eq_str!(&a - &b, "11");
+    assert_eq_str!(&a * &b, "10");
+    assert_eq_str!(-&a, "12");
+    assert_eq_str!(&a / &b, "3");
+    assert_eq!(&(&a / &b) * &b, a);
+    assert_eq_str!(b.inv(), "14");
+    assert_eq_str!(b.inverse(&BigInt::from(19)), "14");
+    assert_eq_str!(a.power(18), "1");
+    assert_eq_str!(a.power(-1), "11");
+    assert_eq_str!(f.elem(-1), "18");
+}
+
+#[test]
+fn fp_zero_one_test() {
+    let f = PrimeField::new(&BigInt::from(5));
+    let a = f.elem(3);
+    assert_eq!(&a + Fp::zero(), a);
+    assert_eq!(&a * Fp::one(), a);
+    assert_eq_str!(Fp::one() - &a, "3");
+    assert_eq!(Fp::one(), f.elem(6));
+    assert!(f.elem(10).is_zero());
+    assert_eq!((Fp::one() - &a).field(), Some(&f));
+}
+
+#[test]
+#[should_panic]
+fn fp_mismatched_field_test() {
+    let a = PrimeField::new(&BigInt::from(5)).elem(1);
+    let b = PrimeField::new(&BigInt::from(7)).elem(1);
+    let _ = a + b;
+}
+
+#[test]
+fn fp_checked_test() {
+    let f = PrimeField::new(&BigInt::from(19));
+    let g = PrimeField::new(&BigInt::from(23));
+    assert_eq!(PrimeField::try_new(&BigInt::from(1)).err(), Some(Error::InvalidModulus(BigInt::from(1))));
+    assert_eq!(f.zero().checked_inv(), Err(Error::NotInvertible));
+    assert_eq!(Fp::one().checked_inv(), Err(Error::UnknownField));
+    assert_eq!(f.elem(7).checked_div(&f.zero()), Err(Error::DivisionByZero));
+    assert_eq!(f.elem(7).checked_add(&g.elem(1)), Err(Error::MismatchedField));
+    assert_eq_str!(f.elem(7).checked_div(&f.elem(15)).unwrap(), "3");
+    assert_eq_str!(f.elem(7).checked_mul(&Fp::one()).unwrap(), "7");
+}
@@ -0,0 +1,294 @@
+use num_bigint::BigInt;
+use num_traits::Signed;
+use std::fmt;
+use super::bigint::Power;
+use super::elliptic_curve::ECPoint;
+use super::error::{Error, Result};
+use super::field::FieldElement;
+
+/// y^2 = x^3 + a x + b over a finite field F, F_p (fp::Fp) or F_{p^k} (extension_field::Fpk)
+/// the characteristic must be > 3
+///
+/// Points are affine, one inversion per addition. EllipticCurve keeps its own
+/// Jacobian plus and multiply_scalar for F_p, the hot path of point counting and ECDSA,
+/// and takes lift_x and random_point from its GenericCurve<Fp> (EllipticCurve::to_generic).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericCurve<F: FieldElement> {
+    pub a: F,
+    pub b: F,
+}
+
+/// affine point of GenericCurve
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenericPoint<F: FieldElement> {
+    Infinity,
+    Affine(F, F),
+}
+
+impl<F: FieldElement> GenericPoint<F> {
+    pub fn is_infinity(&self) -> bool {
+        matches!(self, GenericPoint::Infinity)
+    }
+}
+
+/// #E(F_{q^k}) from the trace t of Frobenius over F_q
+///
+/// #E(F_{q^k}) = q^k + 1 - (alpha^k + beta^k) where alpha + beta = t, alpha beta = q.
+pub fn cardinality_over_extension(q: &BigInt, t: &BigInt, k: u32) -> BigInt {
+    // s_0 = 2, s_1 = t, s_i = t s_{i-1} - q s_{i-2}
+    let mut s0 = BigInt::from(2);
+    let mut s1 = t.clone();
+    for _ in 1..k {
+        let s2 = t * &s1 - q * &s0;
+        s0 = std::mem::replace(&mut s1, s2);
+    }
+    let s = if k == 0 { s0 } else { s1 };
+    q.power(k as i32) + 1u32 - s
+}
+
+impl<F: FieldElement> GenericCurve<F> {
+    pub fn new(a: &F, b: &F) -> GenericCurve<F> {
+        GenericCurve::try_new(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// the characteristic must be > 3 and 4 a^3 + 27 b^2 != 0
+    /// a coefficient without field (Fp::zero(), Fp::one()) takes the field of the other one
+    pub fn try_new(a: &F, b: &F) -> Result<GenericCurve<F>> {
+        let (a, b) = match (a.try_characteristic(), b.try_characteristic()) {
+            (Ok(_), Ok(_)) => (a.clone(), b.clone()),
+            (Ok(_), Err(_)) => (a.clone(), b.clone() + a.zero_like()),
+            (Err(_), Ok(_)) => (a.clone() + b.zero_like(), b.clone()),
+            (Err(e), Err(_)) => return Err(e),
+        };
+        let p = a.characteristic();
+        if p <= BigInt::from(3) {
+            return Err(Error::UnsupportedPrime(p));
+        }
+        if b.field_order() != a.field_order() {
+            return Err(Error::MismatchedField);
+        }
+        let ec = GenericCurve { a, b };
+        if ec.discriminant().is_zero_elem() {
+            return Err(Error::SingularCurve);
+        }
+        Ok(ec)
+    }
+
+    fn elem(&self, n: i32) -> F {
+        self.a.elem_like(&BigInt::from(n))
+    }
+
+    /// 4 a^3 + 27 b^2
+    pub fn discriminant(&self) -> F {
+        self.elem(4) * self.a.power(&BigInt::from(3)) + self.elem(27) * self.b.clone() * self.b.clone()
+    }
+
+    /// 1728 4 a^3 / (4 a^3 + 27 b^2)
+    pub fn j_invariant(&self) -> F {
+        let n = self.elem(4) * self.a.power(&BigInt::from(3));
+        self.elem(1728) * n / self.discriminant()
+    }
+
+    /// x^3 + a x + b
+    pub fn rhs(&self, x: &F) -> F {
+        x.power(&BigInt::from(3)) + self.a.clone() * x.clone() + self.b.clone()
+    }
+
+    /// q = p^k
+    pub fn field_order(&self) -> BigInt {
+        self.a.field_order()
+    }
+
+    pub fn is_on_curve(&self, point: &GenericPoint<F>) -> bool {
+        match point {
+            GenericPoint::Infinity => true,
+            GenericPoint::Affine(x, y) => y.clone() * y.clone() == self.rhs(x),
+        }
+    }
+
+    /// (x, y), which must be on the curve
+    pub fn point(&self, x: &F, y: &F) -> Result<GenericPoint<F>> {
+        let point = GenericPoint::Affine(x.clone(), y.clone());
+        if !self.is_on_curve(&point) {
+            return Err(Error::PointNotOnCurve);
+        }
+        Ok(point)
+    }
+
+    /// point of E(F_p) as a point of this curve
+    pub fn point_from(&self, point: &ECPoint) -> GenericPoint<F> {
+        if point.is_infinity() {
+            return GenericPoint::Infinity;
+        }
+        let zinv = self.a.elem_like(point.z().value()).checked_inverse().unwrap();
+        let zinv2 = zinv.clone() * zinv.clone();
+        GenericPoint::Affine(
+            self.a.elem_like(point.x().value()) * zinv2.clone(),
+            self.a.elem_like(point.y().value()) * zinv2 * zinv)
+    }
+
+    /// a point with the x coordinate, None if x^3 + a x + b is not a square
+    pub fn lift_x(&self, x: &F) -> Option<GenericPoint<F>> {
+        self.rhs(x).sqrt().map(|y| GenericPoint::Affine(x.clone(), y))
+    }
+
+    /// uniformly random point except O
+    pub fn random_point<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> GenericPoint<F> {
+        loop {
+            let x = self.a.random_like(rng);
+            if let Some(GenericPoint::Affine(x, y)) = self.lift_x(&x) {
+                // x with y = 0 has one point instead of two
+                if y.is_zero_elem() && rng.gen::<bool>() {
+                    continue;
+                }
+                let y = if rng.gen::<bool>() { -y } else { y };
+                return GenericPoint::Affine(x, y);
+            }
+        }
+    }
+
+    pub fn negate(&self, point: &GenericPoint<F>) -> GenericPoint<F> {
+        match point {
+            GenericPoint::Infinity => GenericPoint::Infinity,
+            GenericPoint::Affine(x, y) => GenericPoint::Affine(x.clone(), -y.clone()),
+        }
+    }
+
+    pub fn plus(&self, point1: &GenericPoint<F>, point2: &GenericPoint<F>) -> GenericPoint<F> {
+        let (x1, y1, x2, y2) = match (point1, point2) {
+            (GenericPoint::Infinity, _) => return point2.clone(),
+            (_, GenericPoint::Infinity) => return point1.clone(),
+            (GenericPoint::Affine(x1, y1), GenericPoint::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        let lambda = if x1 == x2 {
+            if (y1.clone() + y2.clone()).is_zero_elem() {
+                return GenericPoint::Infinity;
+            }
+            (self.elem(3) * x1.clone() * x1.clone() + self.a.clone()) / (self.elem(2) * y1.clone())
+        } else {
+            (y2.clone() - y1.clone()) / (x2.clone() - x1.clone())
+        };
+        let x3 = lambda.clone() * lambda.clone() - x1.clone() - x2.clone();
+        let y3 = lambda * (x1.clone() - x3.clone()) - y1.clone();
+        GenericPoint::Affine(x3, y3)
+    }
+
+    /// [n] P by double-and-add
+    pub fn multiply_scalar(&self, point: &GenericPoint<F>, n: &BigInt) -> GenericPoint<F> {
+        if n.is_negative() {
+            return self.negate(&self.multiply_scalar(point, &(-n)));
+        }
+        let mut r = GenericPoint::Infinity;
+        for bit in n.to_str_radix(2).chars() {
+            r = self.plus(&r, &r);
+            if bit == '1' {
+                r = self.plus(&r, point);
+            }
+        }
+        r
+    }
+
+    /// Frobenius endomorphism (x, y) -> (x^p, y^p)
+    pub fn frobenius(&self, point: &GenericPoint<F>) -> GenericPoint<F> {
+        match point {
+            GenericPoint::Infinity => GenericPoint::Infinity,
+            GenericPoint::Affine(x, y) => GenericPoint::Affine(x.frobenius(), y.frobenius()),
+        }
+    }
+
+    /// smallest n with [n] P = O where [m] P = O
+    pub fn order_from_multiple(&self, point: &GenericPoint<F>, m: &BigInt) -> BigInt {
+        super::bigint::order_from_multiple(m, |d| self.multiply_scalar(point, d).is_infinity())
+    }
+}
+
+impl<F: FieldElement> fmt::Display for GenericCurve<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "F_{}: y^2 = x^3 + ({}) x + ({})", self.field_order(), self.a, self.b)
+    }
+}
+
+impl<F: FieldElement> fmt::Display for GenericPoint<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenericPoint::Infinity => write!(f, "O"),
+            GenericPoint::Affine(x, y) => write!(f, "({}, {})", x, y),
+        }
+    }
+}
+
+#[test]
+fn cardinality_over_extension_test() {
+    let q = BigInt::from(1009);
+    // k = 1 is q + 1 - t
+    assert_eq!(cardinality_over_extension(&q, &BigInt::from(30), 1), BigInt::from(980));
+    // #E(F_{q^2}) = (q + 1)^2 - t^2
+    assert_eq!(cardinality_over_extension(&q, &BigInt::from(30), 2), BigInt::from(1010 * 1010 - 900));
+    // supersingular t = 0: #E(F_{q^2}) = (q + 1)^2
+    assert_eq!(cardinality_over_extension(&q, &BigInt::from(0), 2), BigInt::from(1010 * 1010));
+}
+
+#[test]
+fn generic_curve_prime_field_test() {
+    use num_traits::{One, Zero};
+    let mut rng = rand::thread_rng();
+    let ec = super::elliptic_curve::EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    let gc = ec.to_generic();
+    assert_eq!(gc.j_invariant().to_bigint(), ec.j_invariant());
+    for _ in 0..10 {
+        let p = ec.random_point(&mut rng);
+        let q = ec.random_point(&mut rng);
+        let k = super::bigint::random_below(&mut rng, &BigInt::from(5000)) - 2500;
+        assert_eq!(gc.plus(&gc.point_from(&p), &gc.point_from(&q)), gc.point_from(&ec.plus(&p, &q)));
+        assert_eq!(gc.multiply_scalar(&gc.point_from(&p), &k), gc.point_from(&ec.multiply_scalar(&p, &k)));
+        assert_eq!(gc.plus(&gc.point_from(&p), &gc.negate(&gc.point_from(&p))), GenericPoint::Infinity);
+        assert!(gc.is_on_curve(&gc.random_point(&mut rng)));
+    }
+    let f = ec.field();
+    assert_eq!(GenericCurve::try_new(&f.elem(0), &f.elem(0)), Err(Error::SingularCurve));
+    // Fp::zero() and Fp::one() have no field, they take the field of the other coefficient
+    let gc0 = GenericCurve::try_new(&super::fp::Fp::zero(), &f.elem(7)).unwrap();
+    assert_eq!(&gc0.field_order(), ec.p());
+    assert!(gc0.is_on_curve(&gc0.random_point(&mut rng)));
+    assert_eq!(GenericCurve::try_new(&f.elem(3), &super::fp::Fp::one()).unwrap().b, f.elem(1));
+    assert_eq!(GenericCurve::try_new(&super::fp::Fp::zero(), &super::fp::Fp::one()), Err(Error::UnknownField));
+    assert_eq!(gc.point(&f.elem(1), &f.elem(1)), Err(Error::PointNotOnCurve));
+}
+
+#[test]
+fn generic_curve_extension_field_test() {
+    use super::dense_polynomial::DensePolynomial;
+    use super::extension_field::ExtensionField;
+    let mut rng = rand::thread_rng();
+    // #E(F_1009) = 980, t = 30
+    let p = BigInt::from(1009);
+    let ec = super::elliptic_curve::EllipticCurve::new(&BigInt::from(-3), &BigInt::from(0), &p);
+    let t = BigInt::from(30);
+    assert_eq!(BigInt::from(ec.cardinality()), &p + 1u32 - &t);
+    for k in 2..4 {
+        // F_{p^k} = F_p[x] / (x^k - c)
+        let field = (2..).find_map(|c| {
+            let mut coefs = vec![BigInt::from(-c)];
+            coefs.resize(k, BigInt::from(0));
+            coefs.push(BigInt::from(1));
+            ExtensionField::try_from_dense(&DensePolynomial::new(ec.field(), coefs)).ok()
+        }).unwrap();
+        let gc = ec.over_extension(&field);
+        assert_eq!(gc.j_invariant(), field.from_int(ec.j_invariant()));
+        let n = cardinality_over_extension(&p, &t, k as u32);
+        for _ in 0..4 {
+            let point = gc.random_point(&mut rng);
+            assert!(gc.is_on_curve(&point));
+            assert!(gc.multiply_scalar(&point, &n).is_infinity());
+            // pi^2 - [t] pi + [p] = 0
+            let pi = gc.frobenius(&point);
+            let pi2 = gc.frobenius(&pi);
+            let r = gc.plus(&gc.plus(&pi2, &gc.multiply_scalar(&pi, &(-&t))), &gc.multiply_scalar(&point, &p));
+            assert!(r.is_infinity(), "{}", point);
+            // E(F_p) is fixed by Frobenius
+            let base = gc.point_from(&ec.random_point(&mut rng));
+            assert_eq!(gc.frobenius(&base), base);
+            assert!(gc.multiply_scalar(&base, &(&p + 1u32 - &t)).is_infinity());
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::One;
+use super::bigint::{self, Power};
+use super::discrete_log;
+use super::elliptic_curve::{ECPoint, EllipticCurve};
+use super::error::{Error, Result};
+use super::schoof;
+
+/// E(F_p) = <P1> + <P2> = Z/n1 x Z/n2 with n1 | n2
+///
+/// p1 has order n1 and p2 has order n2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupStructure {
+    pub n1: BigInt,
+    pub n2: BigInt,
+    pub p1: ECPoint,
+    pub p2: ECPoint,
+}
+
+/// primes below this are counted by enumerating the points, the others by Schoof
+const ENUMERATION_LIMIT: u64 = 1 << 16;
+
+/// group structure of E(F_p), #E is counted by enumeration for small p and by Schoof otherwise
+pub fn group_structure(ec: &EllipticCurve) -> Result<GroupStructure> {
+    let n = if ec.p() < &BigInt::from(ENUMERATION_LIMIT) {
+        BigInt::from(ec.cardinality())
+    } else {
+        schoof::try_count_points(ec.a_fp().value(), ec.b_fp().value(), ec.p())?
+    };
+    group_structure_with_order(ec, &n)
+}
+
+/// random points sampled for each prime l^v of #E before giving up, per power of l
+const SAMPLES_PER_POWER: usize = 64;
+
+/// group structure of E(F_p) where n = #E(F_p)
+/// an error is returned if a sampled point P has [n] P != O,
+/// or if the l-parts of n are not reached after SAMPLES_PER_POWER v samples.
+/// A proper divisor of #E that is a multiple of the exponent is not detected.
+pub fn group_structure_with_order(ec: &EllipticCurve, n: &BigInt) -> Result<GroupStructure> {
+    let mut rng = rand::thread_rng();
+    let mut result = GroupStructure {
+        n1: One::one(),
+        n2: One::one(),
+        p1: ECPoint::infinity(),
+        p2: ECPoint::infinity(),
+    };
+    for (l, v) in bigint::factorize(n) {
+        // l-primary part Z/l^j x Z/l^c
+        let cofactor = n / l.power(v as i32);
+        let mut random = || {
+            let r = ec.random_point(&mut rng);
+            if !ec.multiply_scalar(&r, n).is_infinity() {
+                return Err(Error::InvalidArgument(format!("{} is not a multiple of the order of {}", n, r)));
+            }
+            Ok(ec.multiply_scalar(&r, &cofactor))
+        };
+        let exponent = |point: &ECPoint| {
+            let mut point = point.clone();
+            let mut e = 0;
+            while !point.is_infinity() {
+                point = ec.multiply_scalar(&point, &l);
+                e += 1;
+            }
+            e
+        };
+        let (mut g2, mut c) = (ECPoint::infinity(), 0);
+        let (mut g1, mut j) = (ECPoint::infinity(), 0);
+        // E[l] is not full unless l | p - 1, then the l-part is cyclic
+        let cyclic = v == 1 || !(ec.p() - BigInt::one()).is_multiple_of(&l);
+        let mut samples = 0;
+        while c + j < v {
+            if samples == SAMPLES_PER_POWER * v as usize {
+                return Err(Error::InvalidArgument(format!("{} is not #E", n)));
+            }
+            samples += 1;
+            let r = random()?;
+            let e = exponent(&r);
+            if e > c {
+                g2 = r;
+                c = e;
+                g1 = ECPoint::infinity();
+                j = 0;
+                continue;
+            }
+            if cyclic {
+                continue;
+            }
+            // smallest i with [l^i] R = [t] G2, then Q = R - [t / l^i] G2 has order l^i
+            // and <G2> and <Q> are independent
+            let order = l.power(c as i32);
+            let mut s = r.clone();
+            let mut li = BigInt::one();
+            for i in 0..=e {
+                if let Some(t) = discrete_log::bsgs(ec, &g2, &s, &order) {
+                    if i > j {
+                        g1 = ec.plus(&r, &ec.negate(&ec.multiply_scalar(&g2, &(t / &li))));
+                        j = i;
+                    }
+                    break;
+                }
+                s = ec.multiply_scalar(&s, &l);
+                li *= &l;
+            }
+        }
+        result.n1 *= l.power(j as i32);
+        result.n2 *= l.power(c as i32);
+        result.p1 = ec.plus(&result.p1, &g1);
+        result.p2 = ec.plus(&result.p2, &g2);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+fn check_group_structure(ec: &EllipticCurve, g: &GroupStructure, n: &BigInt) {
+    assert_eq!(&(&g.n1 * &g.n2), n);
+    assert!(g.n2.is_multiple_of(&g.n1));
+    assert_eq!(ec.point_order(&g.p1), g.n1);
+    assert_eq!(ec.point_order(&g.p2), g.n2);
+    // <P1> and <P2> are independent
+    let mut q = g.p1.clone();
+    let mut i = BigInt::one();
+    while i < g.n1 {
+        assert_eq!(discrete_log::bsgs(ec, &g.p2, &q, &g.n2), None);
+        q = ec.plus(&q, &g.p1);
+        i += 1;
+    }
+}
+
+#[test]
+fn group_structure_test() {
+    // y^2 = x^3 - x over F_23 is Z/2 x Z/12
+    let ec = EllipticCurve::new(&BigInt::from(-1), &BigInt::from(0), &BigInt::from(23));
+    let g = group_structure(&ec).unwrap();
+    assert_eq!((g.n1.clone(), g.n2.clone()), (BigInt::from(2), BigInt::from(12)));
+    check_group_structure(&ec, &g, &BigInt::from(24));
+
+    for (a, b, p) in [(-1, 0, 71), (0, 1, 103), (1132, 278, 2003), (3, 5, 1009), (-4, 0, 1013), (0, 3, 1117)].iter() {
+        let ec = EllipticCurve::new(&BigInt::from(*a), &BigInt::from(*b), &BigInt::from(*p));
+        let n = BigInt::from(ec.cardinality());
+        // n2 is the exponent of the group, the largest order of the points
+        let exponent = ec.rational_points().iter().map(|point| ec.point_order(point)).max().unwrap();
+        let g = group_structure(&ec).unwrap();
+        assert_eq!(g.n2, exponent, "{}", ec);
+        check_group_structure(&ec, &g, &n);
+    }
+
+    // supersingular y^2 = x^3 - x over F_1000003 is Z/2 x Z/500002, #E by Schoof
+    let ec = EllipticCurve::try_new(&BigInt::from(-1), &BigInt::from(0), &BigInt::from(1_000_003)).unwrap();
+    let g = group_structure(&ec).unwrap();
+    assert_eq!((g.n1.clone(), g.n2.clone()), (BigInt::from(2), BigInt::from(500_002)));
+    check_group_structure(&ec, &g, &BigInt::from(1_000_004));
+
+    // n must be #E
+    let ec = EllipticCurve::new(&BigInt::from(-1), &BigInt::from(0), &BigInt::from(23));
+    assert!(matches!(group_structure_with_order(&ec, &BigInt::from(25)), Err(Error::InvalidArgument(_))));
+    assert!(matches!(group_structure_with_order(&ec, &BigInt::from(48)), Err(Error::InvalidArgument(_))));
+}
@@ -0,0 +1,310 @@
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use sha2::{Digest, Sha256};
+use super::bigint::{self, Power};
+use super::dense_polynomial::DensePolynomial;
+use super::elliptic_curve::{ECPoint, EllipticCurve};
+use super::error::{Error, Result};
+use super::fp;
+
+/// security level k of RFC 9380 in bits
+const SECURITY_BITS: usize = 128;
+
+/// expand_message_xmd of RFC 9380 section 5.3.1 with SHA-256
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>> {
+    const B_IN_BYTES: usize = 32;
+    const S_IN_BYTES: usize = 64;
+    let ell = len.div_ceil(B_IN_BYTES);
+    if ell > 255 || len > 65535 || dst.len() > 255 {
+        return Err(Error::InvalidArgument(format!("expand_message_xmd length {} dst {}", len, dst.len())));
+    }
+    let mut dst_prime = dst.to_vec();
+    dst_prime.push(dst.len() as u8);
+
+    let b0 = Sha256::new()
+        .chain_update([0u8; S_IN_BYTES])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut bi = Sha256::new()
+        .chain_update(b0)
+        .chain_update([1u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut uniform = bi.to_vec();
+    for i in 2..=ell {
+        let xor: Vec<u8> = b0.iter().zip(bi.iter()).map(|(a, b)| a ^ b).collect();
+        bi = Sha256::new()
+            .chain_update(xor)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        uniform.extend_from_slice(&bi);
+    }
+    uniform.truncate(len);
+    Ok(uniform)
+}
+
+/// hash_to_field of RFC 9380 section 5.2 for F_p
+pub fn hash_to_field(msg: &[u8], dst: &[u8], count: usize, p: &BigInt) -> Result<Vec<BigInt>> {
+    let l = (p.bits() + SECURITY_BITS).div_ceil(8);
+    let uniform = expand_message_xmd(msg, dst, count * l)?;
+    Ok(uniform.chunks(l)
+        .map(|chunk| BigInt::from_bytes_be(Sign::Plus, chunk).mod_floor(p))
+        .collect())
+}
+
+/// parity of x, sgn0 of RFC 9380 for prime fields
+fn sgn0(x: &fp::Fp) -> bool {
+    x.value().is_odd()
+}
+
+/// rational map (x_num / x_den, y y_num / y_den) of an isogeny E' -> E
+#[derive(Debug, Clone)]
+pub struct IsogenyMap {
+    pub x_num: DensePolynomial,
+    pub x_den: DensePolynomial,
+    pub y_num: DensePolynomial,
+    pub y_den: DensePolynomial,
+}
+
+impl IsogenyMap {
+    /// image of (x, y), infinity if a denominator vanishes
+    pub fn map(&self, x: &fp::Fp, y: &fp::Fp) -> ECPoint {
+        let x_den = self.x_den.eval(x);
+        let y_den = self.y_den.eval(x);
+        if x_den.is_zero() || y_den.is_zero() {
+            return ECPoint::infinity();
+        }
+        let x2 = self.x_num.eval(x) / x_den;
+        let y2 = y * self.y_num.eval(x) / y_den;
+        ECPoint::new(&x2, &y2, &x.field().unwrap().one())
+    }
+}
+
+/// simplified SWU map of RFC 9380 section 6.6.2 onto E
+///
+/// The map works on y^2 = x^3 + A' x + B' with A' B' != 0.
+/// For curves with a = 0 or b = 0 it runs on an isogenous curve E' and the isogeny maps the point to E.
+#[derive(Debug, Clone)]
+pub struct SswuMap {
+    ec: EllipticCurve,
+    a: fp::Fp,
+    b: fp::Fp,
+    z: fp::Fp,
+    isogeny: Option<IsogenyMap>,
+    /// h_eff of clear_cofactor
+    pub cofactor: BigInt,
+}
+
+impl SswuMap {
+    /// map directly onto ec, which must have a b != 0
+    /// Z is chosen by find_z_sswu of RFC 9380 appendix H.2, cofactor is 1
+    pub fn new(ec: &EllipticCurve) -> Result<SswuMap> {
+        let (a, b) = (ec.a_fp(), ec.b_fp());
+        if a.is_zero() || b.is_zero() {
+            return Err(Error::InvalidArgument("simplified SWU needs a b != 0, use an isogeny".to_string()));
+        }
+        let z = find_z_sswu(ec.field(), &a, &b);
+        Ok(SswuMap { ec: ec.clone(), a, b, z, isogeny: None, cofactor: One::one() })
+    }
+
+    /// map onto E' : y^2 = x^3 + a x + b and then onto ec by the isogeny
+    pub fn with_isogeny(ec: &EllipticCurve, a: &BigInt, b: &BigInt, z: &BigInt, isogeny: IsogenyMap) -> SswuMap {
+        let field = ec.field();
+        SswuMap {
+            ec: ec.clone(),
+            a: field.elem(a.clone()),
+            b: field.elem(b.clone()),
+            z: field.elem(z.clone()),
+            isogeny: Some(isogeny),
+            cofactor: One::one(),
+        }
+    }
+
+    /// secp256k1_XMD:SHA-256_SSWU_RO_ of RFC 9380 section 8.7 and appendix E.1
+    pub fn secp256k1(ec: &EllipticCurve) -> SswuMap {
+        let field = ec.field();
+        let hex = |s: &str| BigInt::parse_bytes(s.as_bytes(), 16).unwrap();
+        let pol = |coefs: &[&str]| DensePolynomial::new(field, coefs.iter().map(|c| hex(c)).collect());
+        let isogeny = IsogenyMap {
+            x_num: pol(&[
+                "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+                "7d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+                "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+                "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c"]),
+            x_den: pol(&[
+                "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+                "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+                "1"]),
+            y_num: pol(&[
+                "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+                "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+                "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+                "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84"]),
+            y_den: pol(&[
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+                "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+                "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+                "1"]),
+        };
+        SswuMap::with_isogeny(
+            ec,
+            &hex("3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533"),
+            &BigInt::from(1771),
+            &BigInt::from(-11),
+            isogeny)
+    }
+
+    /// x^3 + A' x + B'
+    fn g(&self, x: &fp::Fp) -> fp::Fp {
+        x.power(3) + &self.a * x + &self.b
+    }
+
+    /// map_to_curve_simple_swu followed by the isogeny
+    pub fn map_to_curve(&self, u: &BigInt) -> ECPoint {
+        let field = self.ec.field();
+        let u = field.elem(u.clone());
+        let zu2 = &self.z * u.square();
+        let tv1 = zu2.square() + &zu2;
+        let x1 = if tv1.is_zero() {
+            &self.b / (&self.z * &self.a)
+        } else {
+            -(&self.b / &self.a) * (field.one() + tv1.inv())
+        };
+        let gx1 = self.g(&x1);
+        let (x, gx) = if bigint::legendre(gx1.value(), field.p()) != -1 {
+            (x1, gx1)
+        } else {
+            let x2 = zu2 * x1;
+            let gx2 = self.g(&x2);
+            (x2, gx2)
+        };
+        let y = field.elem(bigint::sqrt_modulo(gx.value(), field.p()).unwrap());
+        let y = if sgn0(&u) != sgn0(&y) { -y } else { y };
+        match &self.isogeny {
+            Some(isogeny) => isogeny.map(&x, &y),
+            None => ECPoint::new(&x, &y, &field.one()),
+        }
+    }
+
+    fn clear_cofactor(&self, point: &ECPoint) -> ECPoint {
+        if self.cofactor.is_one() {
+            point.clone()
+        } else {
+            self.ec.multiply_scalar(point, &self.cofactor)
+        }
+    }
+
+    /// hash_to_curve of RFC 9380 (random oracle encoding)
+    pub fn hash_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<ECPoint> {
+        let u = hash_to_field(msg, dst, 2, self.ec.p())?;
+        let q0 = self.map_to_curve(&u[0]);
+        let q1 = self.map_to_curve(&u[1]);
+        Ok(self.clear_cofactor(&self.ec.plus(&q0, &q1)))
+    }
+
+    /// encode_to_curve of RFC 9380 (nonuniform encoding)
+    pub fn encode_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<ECPoint> {
+        let u = hash_to_field(msg, dst, 1, self.ec.p())?;
+        Ok(self.clear_cofactor(&self.map_to_curve(&u[0])))
+    }
+}
+
+/// find_z_sswu of RFC 9380 appendix H.2
+fn find_z_sswu(field: &fp::PrimeField, a: &fp::Fp, b: &fp::Fp) -> fp::Fp {
+    let p = field.p();
+    let g = |x: &fp::Fp| x.power(3) + a * x + b;
+    let mut ctr = BigInt::one();
+    loop {
+        for z in [field.elem(ctr.clone()), -field.elem(ctr.clone())].iter() {
+            if bigint::legendre(z.value(), p) != -1 || z == &-field.one() {
+                continue;
+            }
+            // g(x) - Z has no root in F_p
+            let gz = DensePolynomial::new(field, vec![(b - z).to_bigint(), a.to_bigint(), Zero::zero(), One::one()]);
+            let x = DensePolynomial::x(field);
+            let xp = x.powmod(p, &gz);
+            if !(xp - &x).gcd(&gz).is_one() {
+                continue;
+            }
+            if bigint::legendre(g(&(b / (z * a))).value(), p) == 1 {
+                return z.clone();
+            }
+        }
+        ctr += 1;
+    }
+}
+
+#[test]
+fn expand_message_xmd_test() {
+    // RFC 9380 appendix K.1
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    let hex = |v: Vec<u8>| v.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+    assert_eq!(hex(expand_message_xmd(b"", dst, 0x20).unwrap()),
+        "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235");
+    assert_eq!(hex(expand_message_xmd(b"abc", dst, 0x20).unwrap()),
+        "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615");
+    assert_eq!(expand_message_xmd(b"abc", dst, 0x80).unwrap().len(), 0x80);
+    assert!(expand_message_xmd(b"abc", dst, 256 * 32).is_err());
+}
+
+#[test]
+fn secp256k1_hash_to_curve_test() {
+    use super::secp256k1::Secp256k1;
+
+    // RFC 9380 appendix J.8.1
+    let curve = Secp256k1::new();
+    let map = SswuMap::secp256k1(&curve.ec);
+    let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+    let hex = |s: &str| BigInt::parse_bytes(s.as_bytes(), 16).unwrap();
+    // msg, P.x, P.y, u0, Q0.x
+    let vectors = [
+        ("",
+         "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+         "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+         "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+         "74519ef88b32b425a095e4ebcc84d81b64e9e2c2675340a720bb1a1857b99f1e"),
+        ("abc",
+         "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+         "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+         "128aab5d3679a1f7601e3bdf94ced1f43e491f544767e18a4873f397b08a2b61",
+         "07dd9432d426845fb19857d1b3a91722436604ccbbbadad8523b8fc38a5322d7"),
+        ("abcdef0123456789",
+         "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+         "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+         "ea67a7c02f2cd5d8b87715c169d055a22520f74daeb080e6180958380e2f98b9",
+         "576d43ab0260275adf11af990d130a5752704f79478628761720808862544b5d"),
+    ];
+    for (msg, px, py, u0, q0x) in vectors.iter() {
+        let u = hash_to_field(msg.as_bytes(), dst, 2, curve.ec.p()).unwrap();
+        assert_eq!(u[0], hex(u0), "msg:{}", msg);
+        let q0 = map.map_to_curve(&u[0]);
+        assert!(curve.ec.is_on_curve(&q0));
+        assert_eq!(q0.x().value(), &hex(q0x), "msg:{}", msg);
+        let point = map.hash_to_curve(msg.as_bytes(), dst).unwrap();
+        assert_eq!(point, curve.ec.point(&hex(px), &hex(py)), "msg:{}", msg);
+    }
+    assert_eq!(curve.ec.hash_to_curve(b"abc", dst).unwrap().x().value(), &hex(vectors[1].1));
+    assert!(curve.ec.is_on_curve(&map.encode_to_curve(b"abc", b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_NU_").unwrap()));
+}
+
+#[test]
+fn sswu_test() {
+    let ec = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    let map = SswuMap::new(&ec).unwrap();
+    // Z is a non square with g(B / (Z A)) square
+    assert_eq!(bigint::legendre(map.z.value(), ec.p()), -1);
+    for u in 0..200 {
+        let point = map.map_to_curve(&BigInt::from(u));
+        assert!(ec.is_on_curve(&point) && !point.is_infinity(), "u:{}", u);
+    }
+    let point = ec.hash_to_curve(b"abc", b"DST").unwrap();
+    assert!(ec.is_on_curve(&point));
+    let ec0 = EllipticCurve::new(&BigInt::from(0), &BigInt::from(7), &BigInt::from(2003));
+    assert!(SswuMap::new(&ec0).is_err());
+    assert!(ec0.hash_to_curve(b"abc", b"DST").is_err());
+}
@@ -0,0 +1,443 @@
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+use std::fmt;
+use super::dense_polynomial::DensePolynomial;
+use super::division_polynomial;
+use super::elliptic_curve::{ECPoint, EllipticCurve};
+use super::error::{Error, Result};
+use super::fp::Fp;
+use super::polynomial::Polynomial;
+use super::term_builder;
+
+/// separable isogeny phi: E -> E', phi(x, y) = (X(x), y Y(x))
+///
+/// X(x) = x_num / x_den and Y(x) = y_num / y_den in lowest terms,
+/// the denominators vanish exactly at the x coordinates of the kernel.
+#[derive(Debug, Clone)]
+pub struct Isogeny {
+    pub domain: EllipticCurve,
+    pub codomain: EllipticCurve,
+    /// size of the kernel
+    pub degree: usize,
+    x_num: DensePolynomial,
+    x_den: DensePolynomial,
+    y_num: DensePolynomial,
+    y_den: DensePolynomial,
+}
+
+/// num / den in lowest terms with a monic denominator
+fn reduce(num: &DensePolynomial, den: &DensePolynomial) -> (DensePolynomial, DensePolynomial) {
+    let g = num.gcd(den);
+    let (num, den) = (num.divrem(&g).0, den.divrem(&g).0);
+    let c = den.leading_coef().inv();
+    (&num * &c, &den * &c)
+}
+
+/// power sums p_1, p_2, p_3 of the roots of a monic polynomial
+fn power_sums(pol: &DensePolynomial) -> (Fp, Fp, Fp) {
+    let n = pol.degree();
+    let field = pol.field();
+    // elementary symmetric polynomials
+    let e = |k: usize| {
+        if k > n {
+            field.zero()
+        } else if k % 2 == 1 {
+            -pol.coef(n - k)
+        } else {
+            pol.coef(n - k)
+        }
+    };
+    let (e1, e2, e3) = (e(1), e(2), e(3));
+    let p2 = e1.square() - field.elem(2) * &e2;
+    let p3 = &e1 * e1.square() - field.elem(3) * &e1 * &e2 + field.elem(3) * e3;
+    (e1, p2, p3)
+}
+
+impl Isogeny {
+    /// Velu's formulas for the kernel <P>
+    pub fn from_kernel_point(ec: &EllipticCurve, point: &ECPoint) -> Result<Isogeny> {
+        if !ec.is_on_curve(point) {
+            return Err(Error::PointNotOnCurve);
+        }
+        let point = ec.to_affine(point);
+        let mut kernel = Vec::new();
+        let mut q = point.clone();
+        while !q.is_infinity() {
+            kernel.push(q.clone());
+            q = ec.plus(&q, &point);
+        }
+        Isogeny::velu(ec, &kernel)
+    }
+
+    /// Velu's formulas for the kernel given as a list of points
+    /// the points and O must form a subgroup of E(F_p)
+    pub fn from_kernel(ec: &EllipticCurve, points: &[ECPoint]) -> Result<Isogeny> {
+        let mut kernel: Vec<ECPoint> = Vec::new();
+        for point in points {
+            if !ec.is_on_curve(point) {
+                return Err(Error::PointNotOnCurve);
+            }
+            let point = ec.to_affine(point);
+            if !point.is_infinity() && !kernel.contains(&point) {
+                kernel.push(point);
+            }
+        }
+        for p in kernel.iter() {
+            for q in kernel.iter() {
+                let r = ec.plus(p, q);
+                if !r.is_infinity() && !kernel.contains(&r) {
+                    return Err(Error::InvalidArgument(format!("kernel is not a subgroup: {} + {} = {}", p, q, r)));
+                }
+            }
+        }
+        Isogeny::velu(ec, &kernel)
+    }
+
+    /// kernel is the subgroup without O
+    fn velu(ec: &EllipticCurve, kernel: &[ECPoint]) -> Result<Isogeny> {
+        let field = ec.field();
+        if ec.discriminant().is_zero() {
+            return Err(Error::SingularCurve);
+        }
+        // S = G_2 + R where G \ {O} = G_2 + R + (-R)
+        let mut s: Vec<&ECPoint> = Vec::new();
+        for q in kernel {
+            if !s.iter().any(|r| r.x().value() == q.x().value()) {
+                s.push(q);
+            }
+        }
+        let x = DensePolynomial::x(field);
+        let mut h = DensePolynomial::one(field);
+        for q in s.iter() {
+            h = &h * (&x - DensePolynomial::constant(&field.elem(q.x().value().clone())));
+        }
+        let h2 = &h * &h;
+        let h3 = &h2 * &h;
+        let mut x_num = &x * &h2;
+        let mut y_num = h3.clone();
+        let mut v = field.zero();
+        let mut w = field.zero();
+        for q in s.iter() {
+            let xq = field.elem(q.x().value().clone());
+            let yq = field.elem(q.y().value().clone());
+            // g^x_Q = 3 x_Q^2 + a, g^y_Q = -2 y_Q
+            let gx = field.elem(3) * xq.square() + ec.a_fp();
+            let gy = -(field.elem(2) * &yq);
+            let vq = if yq.is_zero() { gx } else { field.elem(2) * gx };
+            let uq = gy.square();
+            v += &vq;
+            w += &uq + &xq * &vq;
+
+            // h_Q = h / (x - x_Q)
+            let l = &x - DensePolynomial::constant(&xq);
+            let hq = h.divrem(&l).0;
+            let hq2 = &hq * &hq;
+            let hq3 = &hq2 * &hq;
+            // X += v_Q / (x - x_Q) + u_Q / (x - x_Q)^2
+            x_num += (&l * &vq + DensePolynomial::constant(&uq)) * &hq2;
+            // Y -= 2 u_Q / (x - x_Q)^3 + v_Q / (x - x_Q)^2
+            y_num -= &hq3 * &(field.elem(2) * &uq) + &(&l * &hq3) * &vq;
+        }
+        let a = ec.a_fp() - field.elem(5) * v;
+        let b = ec.b_fp() - field.elem(7) * w;
+        let codomain = EllipticCurve::try_new(&a.to_bigint(), &b.to_bigint(), ec.p())?;
+        let (x_num, x_den) = reduce(&x_num, &h2);
+        let (y_num, y_den) = reduce(&y_num, &h3);
+        Ok(Isogeny {
+            domain: ec.clone(),
+            codomain,
+            degree: kernel.len() + 1,
+            x_num,
+            x_den,
+            y_num,
+            y_den,
+        })
+    }
+
+    /// Kohel's formulas for the kernel given by its polynomial D(x) = prod (x - x_Q)
+    ///
+    /// D is monic in x with one root for each pair {Q, -Q} of the kernel without O,
+    /// it must divide psi_l for the size l of the kernel and its roots must form a subgroup.
+    pub fn from_kernel_polynomial(ec: &EllipticCurve, kernel: &Polynomial) -> Result<Isogeny> {
+        let field = ec.field();
+        if ec.discriminant().is_zero() {
+            return Err(Error::SingularCurve);
+        }
+        let d = DensePolynomial::try_from_polynomial(kernel, field)?;
+        if d.is_zero() || !d.leading_coef().is_one() {
+            return Err(Error::InvalidArgument(format!("kernel polynomial is not monic: {}", kernel)));
+        }
+        if !d.gcd(&d.derivative()).is_one() {
+            return Err(Error::InvalidArgument(format!("kernel polynomial is not square-free: {}", kernel)));
+        }
+        let (a, b) = (ec.a_fp(), ec.b_fp());
+        let x = DensePolynomial::x(field);
+        // f = x^3 + a x + b, the roots of d2 are the points of order 2
+        let f = DensePolynomial::new(field, vec![b.to_bigint(), a.to_bigint(), BigInt::zero(), BigInt::from(1)]);
+        let df = f.derivative();
+        let d2 = d.gcd(&f);
+        let d1 = d.divrem(&d2).0;
+        let (n1, n2) = (d1.degree(), d2.degree());
+        let l = 1 + 2 * n1 + n2;
+        let mut psi = division_polynomial::psi_fp(&a, &b, l);
+        if l % 2 == 0 {
+            // psi_l / y does not vanish on E[2]
+            psi = &psi * &f;
+        }
+        if !(&psi % &d).is_zero() {
+            return Err(Error::InvalidArgument(format!("kernel polynomial does not divide psi_{}: {}", l, kernel)));
+        }
+
+        let (s1, q2, q3) = power_sums(&d1);
+        let (t1, r2, r3) = power_sums(&d2);
+        let (n1, n2) = (field.elem(n1 as u64), field.elem(n2 as u64));
+        // X = x + sum over d1 of (2 f'(x_Q) / (x - x_Q) + 4 f(x_Q) / (x - x_Q)^2)
+        //       + sum over d2 of f'(x_Q) / (x - x_Q)
+        //   = c x + k - 2 f' d1' / d1 - 4 f (d1' / d1)' + f' d2' / d2
+        let c = field.one() + field.elem(2) * &n1 - field.elem(3) * &n2;
+        let k = -(field.elem(2) * &s1) - field.elem(3) * &t1;
+        let (dd1, dd2) = (d1.derivative(), d2.derivative());
+        let d1d2 = &d1 * &d2;
+        let den = &d1 * &d1d2;
+        let num = (&x * &c + DensePolynomial::constant(&k)) * &den
+            - &df * &dd1 * &d1d2 * &field.elem(2)
+            - &f * (&dd1.derivative() * &d1 - &dd1 * &dd1) * &d2 * &field.elem(4)
+            + &df * &dd2 * &d1 * &d1;
+        // phi is normalized, so Y = y X'(x)
+        let y_num = &num.derivative() * &den - &num * &den.derivative();
+        let y_den = &den * &den;
+
+        let v = field.elem(6) * q2 + field.elem(2) * &a * &n1 + field.elem(3) * r2 + &a * &n2;
+        let w = field.elem(10) * q3 + field.elem(6) * &a * &s1 + field.elem(4) * &b * &n1
+            + field.elem(3) * r3 + &a * &t1;
+        let codomain = EllipticCurve::try_new(&(a - field.elem(5) * v).to_bigint(),
+                                                  &(b - field.elem(7) * w).to_bigint(), ec.p())?;
+        let (x_num, x_den) = reduce(&num, &den);
+        let (y_num, y_den) = reduce(&y_num, &y_den);
+        // the roots of D divide psi_l but need not be a subgroup: then the poles of X differ from
+        // the roots of D or (X, y Y) is off E', a rational map E -> E' fixing O is a homomorphism
+        let (a2, b2) = (codomain.a_fp(), codomain.b_fp());
+        let x_den2 = &x_den * &x_den;
+        let rhs = (&x_num * &x_num * &x_num + &x_num * &x_den2 * &a2 + &x_den2 * &x_den * &b2) * &y_den * &y_den;
+        if x_den != den || &f * &y_num * &y_num * &x_den2 * &x_den != rhs {
+            return Err(Error::InvalidArgument(format!("kernel polynomial is not a subgroup: {}", kernel)));
+        }
+        Ok(Isogeny {
+            domain: ec.clone(),
+            codomain,
+            degree: l,
+            x_num,
+            x_den,
+            y_num,
+            y_den,
+        })
+    }
+
+    /// kernel polynomial prod (x - x_Q), one factor for each pair {Q, -Q}
+    pub fn kernel_polynomial(&self) -> Polynomial {
+        let g = self.x_den.gcd(&self.x_den.derivative());
+        // x_den = d1^2 d2
+        self.x_den.divrem(&g).0.to_polynomial()
+    }
+
+    /// X(x) = numerator / denominator as Polynomials in x
+    pub fn x_map(&self) -> (Polynomial, Polynomial) {
+        (self.x_num.to_polynomial(), self.x_den.to_polynomial())
+    }
+
+    /// Y(x, y) = numerator / denominator as Polynomials, the numerator has the factor y
+    pub fn y_map(&self) -> (Polynomial, Polynomial) {
+        let y = term_builder::TermBuilder::new().ypow(1).build();
+        (self.y_num.to_polynomial() * y, self.y_den.to_polynomial())
+    }
+
+    /// phi(P), the points of the kernel go to O
+    pub fn eval(&self, point: &ECPoint) -> ECPoint {
+        let point = self.domain.to_affine(point);
+        if point.is_infinity() {
+            return ECPoint::infinity();
+        }
+        let field = self.domain.field();
+        let (x, y) = (point.x(), point.y());
+        let x_den = self.x_den.eval(x);
+        if x_den.is_zero() {
+            return ECPoint::infinity();
+        }
+        let xx = self.x_num.eval(x) / x_den;
+        let yy = y * self.y_num.eval(x) / self.y_den.eval(x);
+        ECPoint::new(&xx, &yy, &field.one())
+    }
+}
+
+impl fmt::Display for Isogeny {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (xn, xd) = self.x_map();
+        let (yn, yd) = self.y_map();
+        write!(f, "({}) / ({}), ({}) / ({})", xn, xd, yn, yd)
+    }
+}
+
+#[cfg(test)]
+fn check_homomorphism(iso: &Isogeny) {
+    let mut rng = rand::thread_rng();
+    let (ec, ec2) = (&iso.domain, &iso.codomain);
+    assert_eq!(ec.cardinality(), ec2.cardinality());
+    for _ in 0..10 {
+        let p = ec.random_point(&mut rng);
+        let q = ec.random_point(&mut rng);
+        let fp = iso.eval(&p);
+        assert!(ec2.is_on_curve(&fp));
+        assert_eq!(iso.eval(&ec.plus(&p, &q)), ec2.plus(&fp, &iso.eval(&q)));
+        assert_eq!(iso.eval(&ec.negate(&p)), ec2.negate(&fp));
+    }
+}
+
+#[cfg(test)]
+fn rational_kernel(iso: &Isogeny) -> Vec<&ECPoint> {
+    iso.domain.rational_points().iter().filter(|p| iso.eval(p).is_infinity()).collect()
+}
+
+/// every point of the kernel is rational
+#[cfg(test)]
+fn check_isogeny(iso: &Isogeny) {
+    check_homomorphism(iso);
+    assert_eq!(rational_kernel(iso).len(), iso.degree);
+}
+
+#[test]
+fn velu_test() {
+    let ec = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    // 2-isogeny with the kernel (1702, 0), the maps of polynomial::isogeny_test
+    let p = ec.lift_x(&BigInt::from(1702), false).unwrap();
+    let iso = Isogeny::from_kernel_point(&ec, &p).unwrap();
+    assert_eq!((iso.codomain.a_fp().to_bigint(), iso.codomain.b_fp().to_bigint()), (BigInt::from(500), BigInt::from(1005)));
+    assert_eq!(iso.degree, 2);
+    assert_eq_str!(iso, "(x^2 + 301 x + 527) / (x + 301), (x^2 y + 602 x y + 1942 y) / (x^2 + 602 x + 466)");
+    check_isogeny(&iso);
+
+    // #E = 1956 = 2^2 * 3 * 163, kernels of order 3 and 163
+    let n = BigInt::from(1956);
+    let g = ec.rational_points().iter().find(|p| ec.point_order(p) == n).unwrap();
+    for l in [3, 163].iter() {
+        let point = ec.multiply_scalar(g, &(&n / l));
+        let iso = Isogeny::from_kernel_point(&ec, &point).unwrap();
+        assert_eq!(iso.degree, *l as usize);
+        check_isogeny(&iso);
+        if *l == 3 {
+            // Phi_3(j(E), j(E')) = 0
+            let phi = super::modular_polynomial::modular_polynomial_cached(3);
+            let v = phi.eval_xy(&ec.j_invariant(), &iso.codomain.j_invariant());
+            assert_eq!(num_integer::Integer::mod_floor(&v, ec.p()), BigInt::zero());
+        }
+    }
+}
+
+#[test]
+fn velu_kernel_test() {
+    let ec = EllipticCurve::new(&BigInt::from(-1), &BigInt::from(0), &BigInt::from(1009));
+    // E[2] = {O, (0, 0), (1, 0), (-1, 0)} is rational
+    let e2 = ec.division_points(&BigInt::from(2));
+    assert_eq!(e2.len(), 4);
+    let iso = Isogeny::from_kernel(&ec, &e2).unwrap();
+    assert_eq!(iso.degree, 4);
+    check_isogeny(&iso);
+    // the kernel is E[2], so the codomain is isomorphic to E
+    assert_eq!(iso.codomain.j_invariant(), ec.j_invariant());
+    let i = Isogeny::from_kernel(&ec, &[]).unwrap();
+    assert_eq!((i.codomain.a_fp(), i.codomain.b_fp()), (ec.a_fp(), ec.b_fp()));
+
+    let p = ec.rational_points().iter().find(|p| ec.point_order(p) == BigInt::from(5)).unwrap();
+    assert!(Isogeny::from_kernel(&ec, std::slice::from_ref(p)).is_err());
+    let bad = ec.point(&BigInt::from(1), &BigInt::from(1));
+    assert_eq!(Isogeny::from_kernel_point(&ec, &bad).err(), Some(Error::PointNotOnCurve));
+}
+
+#[test]
+fn kohel_test() {
+    use super::term_builder::TermBuildable;
+    let ec = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    let kernel = term_builder::TermBuilder::new().xpow(1).build() + term_builder::TermBuilder::new().coef(301).build();
+    let iso = Isogeny::from_kernel_polynomial(&ec, &kernel).unwrap();
+    assert_eq!((iso.codomain.a_fp().to_bigint(), iso.codomain.b_fp().to_bigint()), (BigInt::from(500), BigInt::from(1005)));
+    assert_eq_str!(iso, "(x^2 + 301 x + 527) / (x + 301), (x^2 y + 602 x y + 1942 y) / (x^2 + 602 x + 466)");
+
+    // the same maps as Velu's formulas, kernels of order 2, 3, 4, 6, 12
+    let n = BigInt::from(1956);
+    let g = ec.rational_points().iter().find(|p| ec.point_order(p) == n).unwrap();
+    for l in [2, 3, 4, 6, 12].iter() {
+        let velu = Isogeny::from_kernel_point(&ec, &ec.multiply_scalar(g, &(&n / l))).unwrap();
+        let kernel = velu.kernel_polynomial();
+        assert_eq!(kernel.degree_x() as usize, l / 2);
+        let iso = Isogeny::from_kernel_polynomial(&ec, &kernel).unwrap();
+        assert_eq!(iso.degree, *l);
+        assert_eq!((iso.codomain.a_fp(), iso.codomain.b_fp()), (velu.codomain.a_fp(), velu.codomain.b_fp()));
+        assert_eq!(iso.to_string(), velu.to_string());
+        check_isogeny(&iso);
+    }
+
+    // non-cyclic kernel E[2], D = x^3 - x
+    let ec = EllipticCurve::new(&BigInt::from(-1), &BigInt::from(0), &BigInt::from(1009));
+    let velu = Isogeny::from_kernel(&ec, &ec.division_points(&BigInt::from(2))).unwrap();
+    assert_eq_str!(velu.kernel_polynomial(), "x^3 + 1008 x");
+    let iso = Isogeny::from_kernel_polynomial(&ec, &velu.kernel_polynomial()).unwrap();
+    assert_eq!(iso.to_string(), velu.to_string());
+    assert_eq!((iso.codomain.a_fp(), iso.codomain.b_fp()), (velu.codomain.a_fp(), velu.codomain.b_fp()));
+}
+
+#[test]
+fn kohel_elkies_test() {
+    use super::schoof_elkies_atkins;
+    // kernel polynomials found by the Elkies procedure give isogenies of degree l
+    let mut found = 0;
+    for (a, b) in [(2, 3), (5, 1), (3, 11), (1, 7)].iter() {
+        let ec = EllipticCurve::new(&BigInt::from(*a), &BigInt::from(*b), &BigInt::from(1009));
+        for l in schoof_elkies_atkins::MODULAR_POLYNOMIAL_PRIMES.iter() {
+            if schoof_elkies_atkins::frobenius_order(&ec, *l) != Some(1) {
+                continue;
+            }
+            let result = schoof_elkies_atkins::elkies(&ec, *l);
+            if result.kernel_polynomial.degree_x() != (l - 1) / 2 {
+                continue;
+            }
+            let iso = Isogeny::from_kernel_polynomial(&ec, &result.kernel_polynomial).unwrap();
+            assert_eq!(iso.degree, *l as usize);
+            check_homomorphism(&iso);
+            // the kernel may have non-rational points, the rational ones are a subgroup
+            assert_eq!(iso.degree % rational_kernel(&iso).len(), 0);
+            let phi = super::modular_polynomial::modular_polynomial_cached(*l);
+            let v = phi.eval_xy(&ec.j_invariant(), &iso.codomain.j_invariant());
+            assert_eq!(num_integer::Integer::mod_floor(&v, ec.p()), BigInt::zero());
+            found += 1;
+        }
+    }
+    assert!(found > 0);
+}
+
+#[test]
+fn kohel_error_test() {
+    use super::term_builder::TermBuildable;
+    let ec = EllipticCurve::new(&BigInt::from(1132), &BigInt::from(278), &BigInt::from(2003));
+    let x = term_builder::TermBuilder::new().xpow(1).build();
+    let c = |v: i32| term_builder::TermBuilder::new().coef(v).build();
+    // not monic
+    assert!(Isogeny::from_kernel_polynomial(&ec, &(x.clone() * c(2) + c(602))).is_err());
+    // x = 1 is not the x coordinate of a point of order 2
+    assert!(Isogeny::from_kernel_polynomial(&ec, &(x.clone() - c(1))).is_err());
+    // not square-free
+    let d = (x.clone() + c(301)) * (x.clone() + c(301));
+    assert!(Isogeny::from_kernel_polynomial(&ec, &d).is_err());
+    let y = term_builder::TermBuilder::new().ypow(1).build();
+    assert_eq!(Isogeny::from_kernel_polynomial(&ec, &(x.clone() + y)).err(), Some(Error::NotUnivariate));
+
+    // {O, P, -P, T} with P of order 4 and T != 2P of order 2 divides psi_4 f, but is not a subgroup
+    let ec = EllipticCurve::new(&BigInt::from(-1), &BigInt::from(0), &BigInt::from(1009));
+    let p = ec.rational_points().iter().find(|p| ec.point_order(p) == BigInt::from(4)).unwrap();
+    let p2 = ec.to_affine(&ec.multiply_scalar(p, &BigInt::from(2)));
+    let e2 = ec.division_points(&BigInt::from(2));
+    let t = e2.iter().find(|t| !t.is_infinity() && t.x() != p2.x()).unwrap();
+    let root = |q: &ECPoint| x.clone() - term_builder::TermBuilder::new().coef(q.x().value()).build();
+    let d = root(p) * root(&p2);
+    assert!(Isogeny::from_kernel_polynomial(&ec, &d).is_ok());
+    let d = root(p) * root(t);
+    assert!(Isogeny::from_kernel_polynomial(&ec, &d).is_err());
+}
@@ -0,0 +1,242 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::Zero;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+use super::bigint;
+use super::dense_polynomial::DensePolynomial;
+use super::error::{Error, Result};
+use super::fp;
+use super::modular_polynomial;
+use super::polynomial::Polynomial;
+
+/// connected component of the l-isogeny graph over F_p
+///
+/// The vertices are j-invariants, j and j' are adjacent when Phi_l(j, j') = 0.
+#[derive(Debug, Clone)]
+pub struct IsogenyGraph {
+    pub l: i32,
+    pub p: BigInt,
+    /// vertices in the order of the traversal
+    pub vertices: Vec<BigInt>,
+    /// neighbours of each vertex with multiplicity
+    pub adjacency: BTreeMap<BigInt, Vec<BigInt>>,
+}
+
+/// levels of an l-volcano, level 0 is the crater and level depth the floor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Volcano {
+    pub crater: Vec<BigInt>,
+    pub floor: Vec<BigInt>,
+    pub depth: usize,
+    pub levels: BTreeMap<BigInt, usize>,
+}
+
+/// Phi_l(x, y) mod p
+fn modular_polynomial_fp(l: i32, p: &BigInt) -> Result<Polynomial> {
+    if l < 2 || !primes::is_prime(l as u64) {
+        return Err(Error::InvalidArgument(format!("l:{}", l)));
+    }
+    if !bigint::is_probable_prime(p) {
+        return Err(Error::NotPrime(p.clone()));
+    }
+    let mut mpol = modular_polynomial::modular_polynomial_cached(l);
+    mpol.modular_assign(p);
+    Ok(mpol)
+}
+
+/// roots of Phi_l(x, j) for the reduced modular polynomial
+fn neighbors_of(mpol: &Polynomial, field: &fp::PrimeField, j: &BigInt) -> Vec<BigInt> {
+    let pol = mpol.eval_y(j);
+    DensePolynomial::from_polynomial(&pol, field).roots()
+}
+
+/// j-invariants l-isogenous to j over F_p, the roots of Phi_l(x, j) with multiplicity
+pub fn neighbors(l: i32, p: &BigInt, j: &BigInt) -> Result<Vec<BigInt>> {
+    let mpol = modular_polynomial_fp(l, p)?;
+    Ok(neighbors_of(&mpol, &fp::PrimeField::new(p), &j.mod_floor(p)))
+}
+
+fn traverse(mpol: &Polynomial, l: i32, p: &BigInt, j: &BigInt, depth_first: bool) -> IsogenyGraph {
+    let field = fp::PrimeField::new(p);
+    let mut vertices: Vec<BigInt> = Vec::new();
+    let mut adjacency: BTreeMap<BigInt, Vec<BigInt>> = BTreeMap::new();
+    let mut pending: VecDeque<BigInt> = VecDeque::new();
+    pending.push_back(j.mod_floor(p));
+    while let Some(v) = if depth_first { pending.pop_back() } else { pending.pop_front() } {
+        if adjacency.contains_key(&v) {
+            continue;
+        }
+        let ns = neighbors_of(mpol, &field, &v);
+        // the smallest neighbour is visited first in both orders
+        let mut next: Vec<&BigInt> = ns.iter().filter(|n| !adjacency.contains_key(n)).collect();
+        next.dedup();
+        if depth_first {
+            next.reverse();
+        }
+        pending.extend(next.into_iter().cloned());
+        vertices.push(v.clone());
+        adjacency.insert(v, ns);
+    }
+    IsogenyGraph { l, p: p.clone(), vertices, adjacency }
+}
+
+/// connected component of j by breadth-first search
+pub fn bfs(l: i32, p: &BigInt, j: &BigInt) -> Result<IsogenyGraph> {
+    let mpol = modular_polynomial_fp(l, p)?;
+    Ok(traverse(&mpol, l, p, j, false))
+}
+
+/// connected component of j by depth-first search
+pub fn dfs(l: i32, p: &BigInt, j: &BigInt) -> Result<IsogenyGraph> {
+    let mpol = modular_polynomial_fp(l, p)?;
+    Ok(traverse(&mpol, l, p, j, true))
+}
+
+/// all connected components of the l-isogeny graph over F_p
+pub fn components(l: i32, p: &BigInt) -> Result<Vec<IsogenyGraph>> {
+    let mpol = modular_polynomial_fp(l, p)?;
+    let mut seen: BTreeSet<BigInt> = BTreeSet::new();
+    let mut components: Vec<IsogenyGraph> = Vec::new();
+    for j in num_iter::range(BigInt::zero(), p.clone()) {
+        if seen.contains(&j) {
+            continue;
+        }
+        let graph = traverse(&mpol, l, p, &j, false);
+        seen.extend(graph.vertices.iter().cloned());
+        components.push(graph);
+    }
+    Ok(components)
+}
+
+impl IsogenyGraph {
+    /// number of edges from j with multiplicity
+    pub fn degree(&self, j: &BigInt) -> usize {
+        self.adjacency.get(j).map_or(0, |ns| ns.len())
+    }
+
+    /// (j, neighbours of j) in ascending order of j
+    pub fn adjacency_list(&self) -> Vec<(BigInt, Vec<BigInt>)> {
+        self.adjacency.iter().map(|(j, ns)| (j.clone(), ns.clone())).collect()
+    }
+
+    /// crater and floor of an ordinary component
+    ///
+    /// The floor is found as the vertices of degree <= 1 and the level of a vertex
+    /// is depth - (distance to the floor). None if the component is not shaped as a volcano,
+    /// e.g. supersingular components or the irregular edges at j = 0, 1728.
+    pub fn volcano(&self) -> Option<Volcano> {
+        let mut dist: BTreeMap<&BigInt, usize> = BTreeMap::new();
+        let mut queue: VecDeque<&BigInt> = VecDeque::new();
+        if self.adjacency.keys().all(|j| self.degree(j) <= 2) {
+            // depth 0, the crater is a cycle
+            dist.extend(self.adjacency.keys().map(|j| (j, 0)));
+        } else {
+            for j in self.adjacency.keys().filter(|j| self.degree(j) <= 1) {
+                dist.insert(j, 0);
+                queue.push_back(j);
+            }
+        }
+        while let Some(v) = queue.pop_front() {
+            let d = dist[v] + 1;
+            for n in self.adjacency[v].iter() {
+                if !dist.contains_key(n) {
+                    dist.insert(n, d);
+                    queue.push_back(n);
+                }
+            }
+        }
+        if dist.len() != self.adjacency.len() {
+            return None;
+        }
+        let depth = *dist.values().max()?;
+        let levels: BTreeMap<BigInt, usize> = dist.iter().map(|(j, d)| ((*j).clone(), depth - d)).collect();
+        for (j, ns) in self.adjacency.iter() {
+            let level = levels[j];
+            let up = ns.iter().filter(|n| levels[*n] + 1 == level).count();
+            let same = ns.iter().filter(|n| levels[*n] == level).count();
+            let down = ns.iter().filter(|n| levels[*n] == level + 1).count();
+            let valid = if level == 0 { same <= 2 } else { up == 1 && same == 0 };
+            if !valid || up + same + down != ns.len() {
+                return None;
+            }
+        }
+        let at = |level: usize| levels.iter().filter(|(_, l)| **l == level).map(|(j, _)| j.clone()).collect();
+        Some(Volcano { crater: at(0), floor: at(depth), depth, levels })
+    }
+}
+
+/// adjacency lists, one line "j: j1 j2 ..." for each vertex
+impl fmt::Display for IsogenyGraph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (j, ns) in self.adjacency.iter() {
+            let ns: Vec<String> = ns.iter().map(|n| n.to_string()).collect();
+            writeln!(f, "{}: {}", j, ns.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn isogeny_graph_test() {
+    let ints = |v: &[i32]| v.iter().map(|j| BigInt::from(*j)).collect::<Vec<BigInt>>();
+    let p = BigInt::from(103);
+    assert_eq!(neighbors(3, &p, &BigInt::from(22)).unwrap(), ints(&[17, 50, 91, 102]));
+    // loops at 89 with multiplicity 2
+    assert_eq!(neighbors(3, &p, &BigInt::from(89 + 103)).unwrap(), ints(&[58, 89, 89, 97]));
+
+    let g = bfs(3, &p, &BigInt::from(17)).unwrap();
+    assert_eq_str!(g, "17: 22\n19: 102\n22: 17 50 91 102\n50: 22\n55: 102\n57: 102\n91: 22\n102: 19 22 55 57\n");
+    assert_eq!(g.vertices, ints(&[17, 22, 50, 91, 102, 19, 55, 57]));
+    assert_eq!(g.adjacency_list().len(), 8);
+
+    let components = components(3, &p).unwrap();
+    assert_eq!(components.iter().map(|c| c.vertices.len()).sum::<usize>(), 103);
+    for c in components.iter() {
+        for (j, ns) in c.adjacency.iter() {
+            assert!(ns.len() <= 4);
+            // undirected
+            for n in ns {
+                assert!(c.adjacency[n].contains(j));
+            }
+        }
+    }
+
+    // depth first visits a neighbour of a visited vertex
+    let p = BigInt::from(1009);
+    let g = dfs(3, &p, &BigInt::from(17)).unwrap();
+    assert_eq!(g.adjacency, bfs(3, &p, &BigInt::from(17)).unwrap().adjacency);
+    for (i, v) in g.vertices.iter().enumerate().skip(1) {
+        assert!(g.vertices[..i].iter().any(|u| g.adjacency[u].contains(v)));
+    }
+
+    assert!(neighbors(4, &p, &BigInt::from(1)).is_err());
+    assert_eq!(bfs(3, &BigInt::from(1007), &BigInt::from(1)).err(), Some(Error::NotPrime(BigInt::from(1007))));
+}
+
+#[test]
+fn volcano_test() {
+    let strs = |v: &[BigInt]| v.iter().map(|j| j.to_string()).collect::<Vec<String>>().join(" ");
+    // the depth is v_3(v) for t^2 - 4 p = v^2 d_K
+    // p = 103, j = 17: t = 14, t^2 - 4 p = -216 = 3^2 * -24
+    let v = bfs(3, &BigInt::from(103), &BigInt::from(17)).unwrap().volcano().unwrap();
+    assert_eq!(v.depth, 1);
+    assert_eq!(strs(&v.crater), "22 102");
+    assert_eq!(strs(&v.floor), "17 19 50 55 57 91");
+    // loops on the crater, t = -4, t^2 - 4 p = 6^2 * -11
+    let v = bfs(3, &BigInt::from(103), &BigInt::from(58)).unwrap().volcano().unwrap();
+    assert_eq!((v.depth, strs(&v.crater)), (1, "89".to_string()));
+    // p = 1009, t = 38, t^2 - 4 p = 18^2 * -8
+    let v = bfs(3, &BigInt::from(1009), &BigInt::from(17)).unwrap().volcano().unwrap();
+    assert_eq!(v.depth, 2);
+    assert_eq!(strs(&v.crater), "173 780");
+    assert_eq!(v.floor.len(), 12);
+    assert_eq!(v.levels.values().filter(|l| **l == 1).count(), 4);
+    // p = 1021, t = 14, t^2 - 4 p = 36^2 * -3
+    let v = bfs(3, &BigInt::from(1021), &BigInt::from(52)).unwrap().volcano().unwrap();
+    assert_eq!((v.depth, v.crater.len(), v.floor.len()), (2, 2, 18));
+
+    // supersingular vertices in F_p form no volcano
+    let g = bfs(3, &BigInt::from(1031), &BigInt::from(294)).unwrap();
+    assert!(g.volcano().is_none());
+}
@@ -1,14 +1,45 @@
+// The original code predates these lints; keep it as written.
+#![allow(unstable_name_collisions)]
+#![allow(
+    clippy::assertions_on_constants,
+    clippy::assign_op_pattern,
+    clippy::clone_on_copy,
+    clippy::extra_unused_lifetimes,
+    clippy::for_kv_map,
+    clippy::len_zero,
+    clippy::manual_swap,
+    clippy::needless_borrow,
+    clippy::needless_lifetimes,
+    clippy::needless_return,
+    clippy::never_loop,
+    clippy::new_without_default,
+    clippy::op_ref,
+    clippy::ptr_arg,
+    clippy::redundant_field_names,
+    clippy::single_char_add_str,
+    clippy::to_string_in_format_args,
+    clippy::unnecessary_cast,
+)]
+
 #[macro_use] extern crate impl_ops;
 #[macro_use] mod assert_eq_str;
 
+pub mod error;
 pub mod bigint;
+pub mod fp;
+pub mod field;
 pub mod term;
 pub mod term_builder;
 pub mod polynomial;
+pub mod dense_polynomial;
+pub mod extension_field;
+pub mod multiplication;
 pub mod division_polynomial;
 pub mod schoof;
 pub mod elliptic_curve;
+pub mod generic_curve;
 pub mod modular_polynomial;
+pub mod modular_polynomial_table;
 pub mod schoof_elkies_atkins;
 pub mod divisor;
 pub mod eisenstein;
@@ -16,4 +47,13 @@ pub mod delta;
 pub mod j_invariant;
 pub mod subscripted_variable;
 pub mod simultaneous_equation;
+pub mod secp256k1_field;
 pub mod secp256k1;
+pub mod ecdsa;
+pub mod schnorr;
+pub mod hash_to_curve;
+pub mod isogeny;
+pub mod discrete_log;
+pub mod group_structure;
+pub mod pairing;
+pub mod isogeny_graph;
@@ -1,12 +1,16 @@
 use primes;
 use num_bigint::BigInt;
 use num_traits::One;
+use super::error::{Error, Result};
 use super::polynomial;
 use super::term_builder;
 use super::term_builder::TermBuildable;
 use super::j_invariant;
+use super::modular_polynomial_table;
 use super::subscripted_variable;
+use std::collections::HashMap;
 use std::ops::Neg;
+use std::sync::{Mutex, OnceLock};
 use super::simultaneous_equation;
 
 /// calculate modular polynomial
@@ -66,6 +70,17 @@ pub fn subscripted_variable_modular_polynomial_q(p: i32) -> polynomial::Polynomi
     pol2
 }
 
+/// p must be prime
+pub fn try_modular_polynomial(p: i32) -> Result<polynomial::Polynomial> {
+    if p < 2 {
+        return Err(Error::InvalidModulus(p.into()));
+    }
+    if !primes::is_prime(p as u64) {
+        return Err(Error::NotPrime(p.into()));
+    }
+    Ok(modular_polynomial(p))
+}
+
 pub fn modular_polynomial(p: i32) -> polynomial::Polynomial {
     let list = subscripted_variable_modular_polynomial_list(p);
     let converter = subscripted_variable::SubscriptedVariableConverter::new(p);
@@ -111,6 +126,27 @@ pub fn modular_polynomial(p: i32) -> polynomial::Polynomial {
     return pol;
 }
 
+/// Phi_p from modular_polynomial_table, None for p not in the table
+pub fn precomputed_modular_polynomial(p: i32) -> Option<polynomial::Polynomial> {
+    let coefs = modular_polynomial_table::coefficients(p)?;
+    let mut pol = polynomial::Polynomial::new();
+    for (i, j, c) in coefs.iter() {
+        let coef = BigInt::parse_bytes(c.as_bytes(), 10).unwrap();
+        pol += term_builder::TermBuilder::new().coef(&coef).xpow(*i).ypow(*j).build();
+        if i != j {
+            pol += term_builder::TermBuilder::new().coef(&coef).xpow(*j).ypow(*i).build();
+        }
+    }
+    Some(pol)
+}
+
+/// modular_polynomial(p), taken from the table or calculated once per process
+pub fn modular_polynomial_cached(p: i32) -> polynomial::Polynomial {
+    static CACHE: OnceLock<Mutex<HashMap<i32, polynomial::Polynomial>>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+    cache.entry(p).or_insert_with(|| precomputed_modular_polynomial(p).unwrap_or_else(|| modular_polynomial(p))).clone()
+}
+
 #[test]
 fn subscripted_variable_modular_polynomial_p2_test() {
     let p = 2;
@@ -176,3 +212,21 @@ fn modular_polynomial_test5() {
     assert_eq_str!(pol, "x^6 - x^5 y^5 + 3720 x^5 y^4 - 4550940 x^5 y^3 + 2028551200 x^5 y^2 - 246683410950 x^5 y + 1963211489280 x^5 + 3720 x^4 y^5 + 1665999364600 x^4 y^4 + 107878928185336800 x^4 y^3 + 383083609779811215375 x^4 y^2 + 128541798906828816384000 x^4 y + 1284733132841424456253440 x^4 - 4550940 x^3 y^5 + 107878928185336800 x^3 y^4 - 441206965512914835246100 x^3 y^3 + 26898488858380731577417728000 x^3 y^2 - 192457934618928299655108231168000 x^3 y + 280244777828439527804321565297868800 x^3 + 2028551200 x^2 y^5 + 383083609779811215375 x^2 y^4 + 26898488858380731577417728000 x^2 y^3 + 5110941777552418083110765199360000 x^2 y^2 + 36554736583949629295706472332656640000 x^2 y + 6692500042627997708487149415015068467200 x^2 - 246683410950 x y^5 + 128541798906828816384000 x y^4 - 192457934618928299655108231168000 x y^3 + 36554736583949629295706472332656640000 x y^2 - 264073457076620596259715790247978782949376 x y + 53274330803424425450420160273356509151232000 x + y^6 + 1963211489280 y^5 + 1284733132841424456253440 y^4 + 280244777828439527804321565297868800 y^3 + 6692500042627997708487149415015068467200 y^2 + 53274330803424425450420160273356509151232000 y + 141359947154721358697753474691071362751004672000");
 }
 
+
+#[test]
+fn precomputed_modular_polynomial_test() {
+    use num_traits::Zero;
+    for p in [2, 3] {
+        assert_eq!(precomputed_modular_polynomial(p).unwrap().to_string(), modular_polynomial(p).to_string());
+    }
+    assert_eq_str!(precomputed_modular_polynomial(5).unwrap(), "x^6 - x^5 y^5 + 3720 x^5 y^4 - 4550940 x^5 y^3 + 2028551200 x^5 y^2 - 246683410950 x^5 y + 1963211489280 x^5 + 3720 x^4 y^5 + 1665999364600 x^4 y^4 + 107878928185336800 x^4 y^3 + 383083609779811215375 x^4 y^2 + 128541798906828816384000 x^4 y + 1284733132841424456253440 x^4 - 4550940 x^3 y^5 + 107878928185336800 x^3 y^4 - 441206965512914835246100 x^3 y^3 + 26898488858380731577417728000 x^3 y^2 - 192457934618928299655108231168000 x^3 y + 280244777828439527804321565297868800 x^3 + 2028551200 x^2 y^5 + 383083609779811215375 x^2 y^4 + 26898488858380731577417728000 x^2 y^3 + 5110941777552418083110765199360000 x^2 y^2 + 36554736583949629295706472332656640000 x^2 y + 6692500042627997708487149415015068467200 x^2 - 246683410950 x y^5 + 128541798906828816384000 x y^4 - 192457934618928299655108231168000 x y^3 + 36554736583949629295706472332656640000 x y^2 - 264073457076620596259715790247978782949376 x y + 53274330803424425450420160273356509151232000 x + y^6 + 1963211489280 y^5 + 1284733132841424456253440 y^4 + 280244777828439527804321565297868800 y^3 + 6692500042627997708487149415015068467200 y^2 + 53274330803424425450420160273356509151232000 y + 141359947154721358697753474691071362751004672000");
+    assert!(precomputed_modular_polynomial(23).is_none());
+    for l in modular_polynomial_table::PRIMES {
+        // Kronecker congruence Phi_l = (x^l - y)(x - y^l) mod l
+        let pol = precomputed_modular_polynomial(l).unwrap();
+        let term = |i: i32, j: i32| term_builder::TermBuilder::new().xpow(i).ypow(j).build().to_pol();
+        let mut diff = pol - (term(l, 0) - term(0, 1)) * (term(1, 0) - term(0, l));
+        diff.modular_assign(&BigInt::from(l));
+        assert!(diff.is_zero(), "l:{}", l);
+    }
+}
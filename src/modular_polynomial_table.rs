@@ -0,0 +1,684 @@
+// Phi_l computed from the q-expansion of j: the power sums of the roots j(l tau), j((tau + k) / l)
+// written as polynomials in j(tau), then Newton's identities.
+// checked by Phi_l(j(q), j(q^l)) = 0 and Phi_l = (x^l - y)(x - y^l) mod l.
+
+/// primes l with a precomputed classical modular polynomial Phi_l(x, y)
+pub const PRIMES: [i32; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+/// coefficients (i, j, c) of c x^i y^j with i >= j, Phi_l(x, y) = Phi_l(y, x) gives the others
+/// None if l is not in PRIMES
+pub fn coefficients(l: i32) -> Option<&'static [(i32, i32, &'static str)]> {
+    match l {
+        2 => Some(&PHI_2),
+        3 => Some(&PHI_3),
+        5 => Some(&PHI_5),
+        7 => Some(&PHI_7),
+        11 => Some(&PHI_11),
+        13 => Some(&PHI_13),
+        17 => Some(&PHI_17),
+        19 => Some(&PHI_19),
+        _ => None,
+    }
+}
+
+const PHI_2: [(i32, i32, &str); 7] = [
+    (3, 0, "1"),
+    (2, 2, "-1"),
+    (2, 1, "1488"),
+    (2, 0, "-162000"),
+    (1, 1, "40773375"),
+    (1, 0, "8748000000"),
+    (0, 0, "-157464000000000"),
+];
+
+const PHI_3: [(i32, i32, &str); 10] = [
+    (4, 0, "1"),
+    (3, 3, "-1"),
+    (3, 2, "2232"),
+    (3, 1, "-1069956"),
+    (3, 0, "36864000"),
+    (2, 2, "2587918086"),
+    (2, 1, "8900222976000"),
+    (2, 0, "452984832000000"),
+    (1, 1, "-770845966336000000"),
+    (1, 0, "1855425871872000000000"),
+];
+
+const PHI_5: [(i32, i32, &str); 22] = [
+    (6, 0, "1"),
+    (5, 5, "-1"),
+    (5, 4, "3720"),
+    (5, 3, "-4550940"),
+    (5, 2, "2028551200"),
+    (5, 1, "-246683410950"),
+    (5, 0, "1963211489280"),
+    (4, 4, "1665999364600"),
+    (4, 3, "107878928185336800"),
+    (4, 2, "383083609779811215375"),
+    (4, 1, "128541798906828816384000"),
+    (4, 0, "1284733132841424456253440"),
+    (3, 3, "-441206965512914835246100"),
+    (3, 2, "26898488858380731577417728000"),
+    (3, 1, "-192457934618928299655108231168000"),
+    (3, 0, "280244777828439527804321565297868800"),
+    (2, 2, "5110941777552418083110765199360000"),
+    (2, 1, "36554736583949629295706472332656640000"),
+    (2, 0, "6692500042627997708487149415015068467200"),
+    (1, 1, "-264073457076620596259715790247978782949376"),
+    (1, 0, "53274330803424425450420160273356509151232000"),
+    (0, 0, "141359947154721358697753474691071362751004672000"),
+];
+
+const PHI_7: [(i32, i32, &str); 35] = [
+    (8, 0, "1"),
+    (7, 7, "-1"),
+    (7, 6, "5208"),
+    (7, 5, "-10246068"),
+    (7, 4, "9437674400"),
+    (7, 3, "-4079701128594"),
+    (7, 2, "720168419610864"),
+    (7, 1, "-34993297342013192"),
+    (7, 0, "104545516658688000"),
+    (6, 6, "312598931380281"),
+    (6, 5, "177089350028475373552"),
+    (6, 4, "4460942463213898353207432"),
+    (6, 3, "16125487429368412743622133040"),
+    (6, 2, "10685207605419433304631062899228"),
+    (6, 1, "1038063543615451121419229773824000"),
+    (6, 0, "3643255017844740441130401792000000"),
+    (5, 5, "-18300817137706889881369818348"),
+    (5, 4, "14066810691825882583305340438456800"),
+    (5, 3, "-901645312135695263877115693740562092344"),
+    (5, 2, "11269804827778129625111322263056523132928000"),
+    (5, 1, "-40689839325168186578698294668599003971584000000"),
+    (5, 0, "42320664241971721884753245384947305283584000000000"),
+    (4, 4, "88037255060655710247136461896264828390470"),
+    (4, 3, "17972351380696034759035751584170427941396480000"),
+    (4, 2, "308718989330868920558541707287296140145328128000000"),
+    (4, 1, "553293497305121712634517214392820316998991872000000000"),
+    (4, 0, "41375720005635744770247248526572116368162816000000000000"),
+    (3, 3, "-5397554444336630396660447092290576395211374592000000"),
+    (3, 2, "72269669689202948469186346100000679630099972096000000000"),
+    (3, 1, "-129686683986501811181602978946723823397619367936000000000000"),
+    (3, 0, "13483958224762213714698012883865296529472356352000000000000000"),
+    (2, 2, "-46666007311089950798495647194817495401448341504000000000000"),
+    (2, 1, "-838538082798149465723818021032241603179964268544000000000000000"),
+    (2, 0, "1464765079488386840337633731737402825128271675392000000000000000000"),
+    (1, 1, "1221349308261453750252370983314569119494710493184000000000000000000"),
+];
+
+const PHI_11: [(i32, i32, &str); 79] = [
+    (12, 0, "1"),
+    (11, 11, "-1"),
+    (11, 10, "8184"),
+    (11, 9, "-28278756"),
+    (11, 8, "53686822816"),
+    (11, 7, "-61058988656490"),
+    (11, 6, "42570393135641712"),
+    (11, 5, "-17899526272883039048"),
+    (11, 4, "4297837238774928467520"),
+    (11, 3, "-529134841844639613861795"),
+    (11, 2, "27209811658056645815522600"),
+    (11, 1, "-374642006356701393515817612"),
+    (11, 0, "296470902355240575283200000"),
+    (10, 10, "1608331026427734378"),
+    (10, 9, "30134971854812981978547264"),
+    (10, 8, "12407796387712093514736413264496"),
+    (10, 7, "645470833566425875717489618904152240"),
+    (10, 6, "7848482999227584325448694633580010490867"),
+    (10, 5, "28890545335855949285086003898461917345026160"),
+    (10, 4, "35372414460361796790312007060191890803134127320"),
+    (10, 3, "14131378888778142661582693947549844785863493325800"),
+    (10, 2, "1587728122949690904187089204116332301200302760915266"),
+    (10, 1, "33446467926379842030532687838341039552110187929600000"),
+    (10, 0, "29298331981110197366602526090413106879319244800000000"),
+    (9, 9, "-573388748843683532691009051194955437"),
+    (9, 8, "24228593349948582884094197811518266845689352"),
+    (9, 7, "-51135193038502008150804190472844550800569441050500"),
+    (9, 6, "14690460927260804690751501000083244161647396386205851440"),
+    (9, 5, "-994774826102691960922410649494629085486856242714439003812180"),
+    (9, 4, "22148485195925584385790489089697473918894904664093860668378292000"),
+    (9, 3, "-199188452917764242987050083089364860927274115441197382331866126825820"),
+    (9, 2, "804436418307995738740132598166893365099468842089705900525050627891200000"),
+    (9, 1, "-1458178254597295207839980786768623018650234306932331393013634952069120000000"),
+    (9, 0, "965122546660349298406724063940884252743873633176129290337528305418240000000000"),
+    (8, 8, "29211180544704743418963619709378403797452606969172658"),
+    (8, 7, "636861023141767565580039581191818069063579259290464688398880"),
+    (8, 6, "987807801334019988631500819088661487281712947788833523552559299560"),
+    (8, 5, "208334210762751500564946204497082337222910461284651050215872586641463200"),
+    (8, 4, "8498500708725193890718329655230574962816784139443636591086906768989729050095"),
+    (8, 3, "79513247125057906492841989395207442300133781750924860449090230806481243648000000"),
+    (8, 2, "171790435018380416903247878610824648919543398246401012395341432490921925017600000000"),
+    (8, 1, "66806304467998310581793391194791115184805127528413091235284315294143736709120000000000"),
+    (8, 0, "1338586400912357073420399795635643400599836918986297982928179335149920452608000000000000"),
+    (7, 7, "-64999046469909490143435875140651300541119093852394968074094803537810"),
+    (7, 6, "247900233561939294388612799857476424364856251769094880288086537904279396400"),
+    (7, 5, "-75948585201267973403627533631138995089882647284307484579413691458563029509971992"),
+    (7, 4, "2973119672716212219456471881112888569835575578534065127175856819648732682854604800000"),
+    (7, 3, "-22093249696627933419655226823604057638897222562682635800269909178325710985117040640000000"),
+    (7, 2, "44681231489418997440503069818655052635806384532381152777755381649015689662976491520000000000"),
+    (7, 1, "-24155957253764418975307742823129586187061243620756339515602571075061236992294518784000000000000"),
+    (7, 0, "618840723107761889896363016885251574078635388443306832549992828319945330157158400000000000000000"),
+    (6, 6, "1168150167526575837857761510359647773943258089269992605255478096499695783789300124"),
+    (6, 5, "224080399886627495149771654692369177094059649940825305182078225594292057242702643200000"),
+    (6, 4, "1938738373821740121470446368665797412833082873875468530371642913339302678999680942080000000"),
+    (6, 3, "-7211912299746007510535159486199919697482960389278446632552985263875183091897870581760000000000"),
+    (6, 2, "30494044246550310117871895628421273379173050630568397072391110688366558535804457582592000000000000"),
+    (6, 1, "-95333447356443287210404497374050404132491763274506548619337189691919811046970438451200000000000000000"),
+    (6, 0, "95356266594731795079493309965756674711058734831164489212811553129058773080352804044800000000000000000000"),
+    (5, 5, "-15057297311708922526580514410563848478334693758624999774108600968667487260827388477440000000"),
+    (5, 4, "-177994641867075262695184980920462608604060357466681128822395417442867019643767352197120000000000"),
+    (5, 3, "-1328993907465108152135763886999825071444084099881098607565574716140191426369978927939584000000000000"),
+    (5, 2, "9718148718139346647384449201643833517488848029697396574289278515913329360524510494720000000000000000000"),
+    (5, 1, "-7840379248214196729643062796493269425081859930100141304047932909346022483171510017064960000000000000000000"),
+    (5, 0, "-3111357148902865912417988391836350251682805385917571877568422664218078901010004935966720000000000000000000000"),
+    (4, 4, "15043423165563966645618284609730360176005265392518745580151910727157028699006028388237312000000000000"),
+    (4, 3, "-51038778870467375317174627414281203016789153392265449880353463871004348816411677478092800000000000000000"),
+    (4, 2, "378494977797549959360178068152933818044335078157093771639955480261351930169113765048483840000000000000000000"),
+    (4, 1, "59659609577030961637541110289112021078091104767187787822549078869394205439302452893450240000000000000000000000"),
+    (4, 0, "43714682637171236021367604966833305309923746974850894665325331604362303109715777067941888000000000000000000000000"),
+    (3, 3, "-925461466455522523607980072366478440235575959511945288268604770825451300845059605937520640000000000000000000"),
+    (3, 2, "1038677201789914991362090465961377302769147065985487222285672689158918175716097236444119040000000000000000000000"),
+    (3, 1, "493751729222149651035457063068642305508233453469401395944974296438196687728770695603159040000000000000000000000000"),
+    (3, 0, "-337500037290942764495395868386562971754016116785390841072048221617443316658082155384012800000000000000000000000000000"),
+    (2, 2, "-301851634381591833346238394387907563828793379391119445614595161272769455527698270716428288000000000000000000000000"),
+    (2, 1, "-4175190947377089941611452135383204997172948465221368432119554418845446929655566146994176000000000000000000000000000000"),
+    (2, 0, "1509199706449264373105244249368970977209959173066491449939153900434037998316228131684352000000000000000000000000000000000"),
+    (1, 1, "6950986496704390042399105433049126860396103535300642728895074819467726754375236055025582080000000000000000000000000000000"),
+    (1, 0, "-3708476896661234261166595138586620846782660237574536888784393380944856551532392652692520960000000000000000000000000000000000"),
+    (0, 0, "3924233450945276549086964624087200490995247233706746270899364206426701740619416867392454656000000000000000000000000000000000000"),
+];
+
+const PHI_13: [(i32, i32, &str); 104] = [
+    (14, 0, "1"),
+    (13, 13, "-1"),
+    (13, 12, "9672"),
+    (13, 11, "-40616316"),
+    (13, 10, "97116140576"),
+    (13, 9, "-145742356534710"),
+    (13, 8, "142727120530755696"),
+    (13, 7, "-91944131414745883208"),
+    (13, 6, "38373375189621696878784"),
+    (13, 5, "-9980376107988974265288009"),
+    (13, 4, "1508484527780717514871680200"),
+    (13, 3, "-117589277940072151921466095740"),
+    (13, 2, "3813066975450671721121304807712"),
+    (13, 1, "-32685702714621175092948209889806"),
+    (13, 0, "15787756016985099663979167744000"),
+    (12, 12, "63336131453282305176"),
+    (12, 11, "5339704017492387472276862944"),
+    (12, 10, "7038227861570702862399825051262104"),
+    (12, 9, "1017131468961830048705766611220442641072"),
+    (12, 8, "32988905472599070890328795217808043240900816"),
+    (12, 7, "333551826778342195432371586876023049547129080896"),
+    (12, 6, "1234257162452453722866237618078783279952599399679176"),
+    (12, 5, "1787206767475651398304042906319887696372425891847417480"),
+    (12, 4, "1010922460622081033367079280521141037085193349093095277208"),
+    (12, 3, "207577177886168263601723424708043354620195244558620874018272"),
+    (12, 2, "12893770087100209197778927627416397147602669299324665034127451"),
+    (12, 1, "157870586217596053304332218736965888119051656824626442141696000"),
+    (12, 0, "83084413350616406183495875982586495825900375128760385536000000"),
+    (11, 11, "-936062849021824119784660671862200161988"),
+    (11, 10, "214191411057420328765018422101187988893741675744"),
+    (11, 9, "-1967575998834670421411906070499119710120923910594022072"),
+    (11, 8, "2117324199178304244393290847066787694415213468957410146838208"),
+    (11, 7, "-481806591005250661668209263946913789583739163176277250633369496316"),
+    (11, 6, "33157532644992168541479115114277423707920632043639237944990254217082784"),
+    (11, 5, "-874174690463455858478740034973677797874649720724911207202908349653368101836"),
+    (11, 4, "10335702376336052876569385632176208762756384874046214470799722804104208232161120"),
+    (11, 3, "-60259084880308652560754125957376955923094701831235097378932424092592846288059835756"),
+    (11, 2, "179312619437995268862785568892538140587316635932472934686318597956817819648897662976000"),
+    (11, 1, "-260241334661897724169148477062778090370575619826743149104887568856318553170833833984000000"),
+    (11, 0, "145746271865985701303006968690727073623110154189151557978520314340489760352149438464000000000"),
+    (10, 10, "2303156526339236416244981158503557124969923397655602595936"),
+    (10, 9, "333376714930461597630366410672145363642373801348744230962709165120"),
+    (10, 8, "2965269806029300518982153645576999878343315273199400249881587616072766840"),
+    (10, 7, "3319074015126775003340627498451966608621776985617068464040481273875824853713440"),
+    (10, 6, "707602306954335961264387747392830714609124951294341249227988393380722334150416923424"),
+    (10, 5, "36877562398966114743254895852508154513817343754571889820596205093997469123113726984508320"),
+    (10, 4, "539434066952838633601058314080351829728768185613881497302494155281483862817525900116623514601"),
+    (10, 3, "2308916580373705363546321120346521865137649088713708960950564814885950596793631208268755124224000"),
+    (10, 2, "2678665736689769049900018109140598264035750069305308244518131035743577819824227828206936260608000000"),
+    (10, 1, "618365025729687208026621844082518672586866478732183940869747889968364543178129991952544825344000000000"),
+    (10, 0, "7605348735017212625875837184978457615081634815943367015020891775626681233374752203029348352000000000000"),
+    (9, 9, "-344642844610887365333843812260789022299828714507153260278660403308943561718"),
+    (9, 8, "11510485988607799847944664306226745280653016997751179971212105953518910829665118960"),
+    (9, 7, "-28971833722004769608218351898602997023873718918496584569542741468721604925350565276800952"),
+    (9, 6, "8968707059877929793953816639999625053085656781146444057912686388706404082753228694260847129920"),
+    (9, 5, "-474980656775733704222417133934306465523573652393831168608700490473956434788522583600537536840594898"),
+    (9, 4, "5716677920985743655201500120101677007190102608912515081206876829642793929337037298192242022307430400000"),
+    (9, 3, "-20678078537212882761694153848026684161510425619867392882628417971589808513139875419201055859633291264000000"),
+    (9, 2, "25872463908449289016750628555567372710185328848483463083494077182570444339188517407317465229936295936000000000"),
+    (9, 1, "-8674072694766581259832161984558424258242345509461562068916284333261672299485935075259027823494430720000000000000"),
+    (9, 0, "132287948592242819730686388197721726586421046648941198415164132202495387061267918873489002706501632000000000000000"),
+    (8, 8, "763629377534280239525001752797018342037897631130969295340196615666330614048031692849601680"),
+    (8, 7, "2155218753344782821853617766133779473725138989326106677408530224250256987904613455196577522696384"),
+    (8, 6, "415431723402642702720731130934926941857797474097020970018619513668017459051573659373309870938643397563"),
+    (8, 5, "5757558921048446015266554919402344737333501100152974630225108131920384126722107536788649181513676013568000"),
+    (8, 4, "-6095414391440954795178869663499425828291538452766653566256327921063584062137305104052711687223009869824000000"),
+    (8, 3, "-62333021735677560171642749900635564915892941745383692317263013992372210489562891779314959788281383878656000000000"),
+    (8, 2, "367699880302507769522184906338576349930282889799687609612600740135262931410546189503475085055061919793152000000000000"),
+    (8, 1, "-913844005726821508929480521086904504761295550807304466343649705885472617699094229816628221421776732684288000000000000000"),
+    (8, 0, "767013621315952423931475176267170123577142608595930709148835175130350223089832292329376203694232005771264000000000000000000"),
+    (7, 7, "-3539294606963747267479265746594748156709881306171284362655032102198235369837795589356541679185977279848"),
+    (7, 6, "187433051934148497537178792064160144226449743146562769523813325806108271927829978476604969216803944169472000"),
+    (7, 5, "-3702665127143760979998154278812085426166716114551745045128607584536820099329002243268464660519705479479296000000"),
+    (7, 4, "-18313220589707554303919628836565371160582541687979396960418053123247399413186658869150749995799620001726464000000000"),
+    (7, 3, "303628396849623247388501617704769126069627806954925724909207701265590212162332663163323999037945093480775680000000000000"),
+    (7, 2, "-226668496996199203777352229716417461096995804909768763297196647245168959821482189931394270493086737753964544000000000000000"),
+    (7, 1, "-465337020884877935874185748520218965445631193822519111113045800260798180133962179115662432186399226106740736000000000000000000"),
+    (7, 0, "66829334150181693395733549605487911633242059793148257435222656254771339933627547003847032182942337299644416000000000000000000000"),
+    (6, 6, "21919503989502556482532977985659185423685666886088290313930781118854798926106308297736210617657464845238272000000"),
+    (6, 5, "-1410473999113376096921325206927033932443299808279922080543730137710923836158828899053966820213587545583255552000000000"),
+    (6, 4, "17722361050304472620163034691211680403065699682566045788144444570455590725483253301914282961928612252886237184000000000000"),
+    (6, 3, "-17733806301048501011486217516580565338695560468655559232106708808776991496975958558628543386809658957681917952000000000000000"),
+    (6, 2, "175801761541721296614163144760797961999581545737966242399898402245904424096892942484369837626392492960431210496000000000000000000"),
+    (6, 1, "34208636313948962505255416382800378890590483698550917680568729071142350960549152337412536609529405160000847872000000000000000000000"),
+    (6, 0, "3268240030696916778423724456839641770009309037438345492166218927315814548015978322807870290034191070539022336000000000000000000000000"),
+    (5, 5, "5627576194161215810088198676115700033241050131121473877965970475637724125302025889733550246015725064794669056000000000000"),
+    (5, 4, "828973674649555922651050874150305990627094598448649047796953362599591050742151260055665892525003926982843432960000000000000000"),
+    (5, 3, "-941802378462465511244447050809161114536892868345640328360842000821724559505492381497133977607854427475915309056000000000000000000"),
+    (5, 2, "-5648591949659254685659692003344338379638954758557151198844390691020983772484333009507611037427149946420681768960000000000000000000000"),
+    (5, 1, "1617796325733693961426612991967106010346218233891170279500742895526209242404102299051177796077528512644260036608000000000000000000000000"),
+    (5, 0, "95888722830042559821615002218841595211920062873311035820055532712656384110985948315484610123352758708871364608000000000000000000000000000"),
+    (4, 4, "4081674117329728804489206772464831122415122070151308117835102044725072517715001683094459791402673386965744746496000000000000000000"),
+    (4, 3, "-24885848452127894014624454936412695642180132782686131038890849143846266810389567025962091921161996214123131568128000000000000000000000"),
+    (4, 2, "58405353917014162404952148388731205467622015248477898593099624781969985828433123084038663979821981572463218130944000000000000000000000000"),
+    (4, 1, "-4772454395099970588376889812892387899584728241524331459452038527296029061412099051047499510623295031345026170880000000000000000000000000000"),
+    (4, 0, "1885223597142817735215521923030446116923320678716240056759672332116990135924145606946025364033903751052868452352000000000000000000000000000000"),
+    (3, 3, "-4983534780898623837208148120899538170442693994917976285662769716226848993219053110271292940660067899070381817856000000000000000000000000"),
+    (3, 2, "60459932962707148685750780439295720777105469153376987257360608129644675668266607620124314344109550426506206904320000000000000000000000000000"),
+    (3, 1, "-185232507560749354757488264428490031076630581809117895374513401195331750782161966573976898709883093065359517810688000000000000000000000000000000"),
+    (3, 0, "22236398027215399937779019690353966999876882002081199329677306063131993047041542443852802352851578390365960404992000000000000000000000000000000000"),
+    (2, 2, "26281453854686565480854489645262487309390226496990889730097271768767754182467308700379350639320763133343165317120000000000000000000000000000000"),
+    (2, 1, "-37066027755072565194081927511328660876696510055655033788696425898925604370808677258232777955584843608603884519424000000000000000000000000000000000"),
+    (2, 0, "147213371414156573713539483874043827500390696883068187579053600467101994104225901089258359895920442702174699388928000000000000000000000000000000000000"),
+    (1, 1, "-33905309938808933226695939390198532869912468194279700917160273935527359588865865248595689625551089671051614879744000000000000000000000000000000000000"),
+];
+
+const PHI_17: [(i32, i32, &str); 172] = [
+    (18, 0, "1"),
+    (17, 17, "-1"),
+    (17, 16, "12648"),
+    (17, 15, "-71933868"),
+    (17, 14, "243057494560"),
+    (17, 13, "-543107538085134"),
+    (17, 12, "845403773043689712"),
+    (17, 11, "-940834526805431190536"),
+    (17, 10, "756269550836626353971136"),
+    (17, 9, "-438493979066274155797170885"),
+    (17, 8, "181258419993507714348169154760"),
+    (17, 7, "-52223043610467843989294551790844"),
+    (17, 6, "10116910271467186525015468031585952"),
+    (17, 5, "-1248215296266475245333926664747624142"),
+    (17, 4, "90336587393075838765443533218971103600"),
+    (17, 3, "-3367271883828654344450602714413581988552"),
+    (17, 2, "51640192300469514986068567579715308330944"),
+    (17, 1, "-205268640098051056539848762369487648144402"),
+    (17, 0, "44771028181385452801142987974975193088000"),
+    (16, 16, "45268321023563019195816"),
+    (16, 15, "56433132181274077348307560338912"),
+    (16, 14, "590680765405050150988349309107207619916"),
+    (16, 13, "504014727972394450311510687861881897603860336"),
+    (16, 12, "83640338267620703446176135587067446162072729161104"),
+    (16, 11, "4131257466208958565665792027885370531285156717507804480"),
+    (16, 10, "76742426511222732530304277120403149719561214584114717116469"),
+    (16, 9, "614068256620013324189278067511564998082385714189064062291128504"),
+    (16, 8, "2288223806521122920681979560560862127259497984787834649886392272416"),
+    (16, 7, "4128723330499913828358434470147585081807836456946136192323582961445472"),
+    (16, 6, "3635992601574290668568260243221818468770884852303590236423100945685806136"),
+    (16, 5, "1528729600207617900247410946944045994641440479775991468922656907730790183216"),
+    (16, 4, "289547963693326301934636868157211077840629183403730838996639384120753245726576"),
+    (16, 3, "22101336619747593955508531302384988092379623211124712259686137049053689452864320"),
+    (16, 2, "551351367040307715403041819642881213795834321840986390344793975786913267615662233"),
+    (16, 1, "2869380259303305752665726683219345430182200931908432539271568475244105174482944000"),
+    (16, 0, "668148321472803468401994928148347860947764805367113104414357546279297351680000000"),
+    (15, 15, "-514439740025263280711232820787012638427550908"),
+    (15, 14, "2346502378663082806457928904372003843215534612677260704"),
+    (15, 13, "-290040956713566783917299583360052668780120420569637024074997688"),
+    (15, 12, "3191683477367870258462424857623073876997867993673025266727874228053952"),
+    (15, 11, "-6146059167345383825777754960094717527208565900344813692179106931743686392794"),
+    (15, 10, "3091207587983678784185303905221997849147564797948776229151040553435198605641203888"),
+    (15, 9, "-529413231224687026355563282041515529027236635242612149141425793052987814451463757498380"),
+    (15, 8, "37235833390124041243839631189918599612868215804065778651598343585891375025545246325058744800"),
+    (15, 7, "-1233578701291430507481881297596122671372094996549740286737288579505992102319624159264455322542268"),
+    (15, 6, "21283971796051417411139419515907095389691165033877492425409890186028699597154969346752421729827644576"),
+    (15, 5, "-205248931601665030546848510328077804936530659086776402082783404977971908666899239544278739110178863072024"),
+    (15, 4, "1155442254780236129613291389390393255252896809689371591508216263229050208378575201546348182271201151675256000"),
+    (15, 3, "-3856489974206308611304064614290553763899742354554034413436458900697503322774287255397161546112938678639984878384"),
+    (15, 2, "7479397422487021698297599746521890448565721868163047445895716786717683141374721125644256661801523948571770257408000"),
+    (15, 1, "-7760895445024592991803674090386706084802144628071070253675415445477067947707231948167628108994824217032537407488000000"),
+    (15, 0, "3323743036667141238998992881020740173983534115801298151533273226034685026021601636349764157264473238515136593920000000000"),
+    (14, 14, "1193394679493621912483080891752063590190598681589675997844979612768"),
+    (14, 13, "4665984191876853492108115742924085058394661237241490856820668553655074765632"),
+    (14, 12, "839638978214792026096314023397171584709872600169448688805259917488796919291126968795"),
+    (14, 11, "15799194957652072490236806656442802673119851606937263926758152995790595471523056631116329424"),
+    (14, 10, "50650994035197461802722668179408564896047209775335105869568865326070268830279262158611087028129536"),
+    (14, 9, "37931502953219105010152656408583239618737268962771481659545043844002351397140014595049115745123172084512"),
+    (14, 8, "8208063020508014917026389326256757084182196573331591060674251743679402473693878525849744282447241387642147928"),
+    (14, 7, "592948511749970354860487327362029727973875315945262813457769696608089532684258484028563610045916709330508922594720"),
+    (14, 6, "15700601876765017629572153058872377470579357381141286060564919327916299752978405979014840173241681675265440068543018336"),
+    (14, 5, "160183014567187290795941574884492239162730539054025957790121381831543792893973365766951360354113825444800352416984723956800"),
+    (14, 4, "633209819507206032948871232693536813726741946354436598273873964137316709682748417416972730050544636943424892908838762584476660"),
+    (14, 3, "921820602725385323603948357218857048580094356011445959264656829483230166497443805897433538630178896230971334185503413149859840000"),
+    (14, 2, "427541286068153315354168762606723330305453283766919256831537603742778497328175382076489014253749507755038527952396104335622144000000"),
+    (14, 1, "43789100477316303667577356454242626988689366643332176688597148608712841431099413908879555067399739316049409694575298227470336000000000"),
+    (14, 0, "245493849282054903582871283717436369880128101744287253184176476258278370011132257430628050486221083586073914130998155542528000000000000"),
+    (13, 13, "-260609750701610762878188273171026263308021538882666868268339516198256503798141337855714"),
+    (13, 12, "341794985535187419817441376350382644371205721732102718577673497394371688991385153115279246969856"),
+    (13, 11, "-28387497711035772286341947218995487054660751926125675490874270501008408374107660172291944877654739216632"),
+    (13, 10, "264309724335807165195482013586132307727421202042540144629307768193116433745970809433534195108924207231578910784"),
+    (13, 9, "-398122053632084502196802599903438971335901251593351700373148321743732448251499969006833815391688031286309647944434642"),
+    (13, 8, "125029667688646273972138460079855535567690241977676345156966379368218507008272877409307793349323297093518189160231560765520"),
+    (13, 7, "-9922540759518282656688449800121049746731058348045974853200003594179032686341077309642821784946764834616701683862628033958833672"),
+    (13, 6, "235570833139357631223027144434407646047922978677261196535939137581890446024758774453412410330798447063437017927095390043554173616320"),
+    (13, 5, "-1977868096695001399891500209337443448652590260834609861997923146354625568828931052544427289306593262056513509417261400417866599158624632"),
+    (13, 4, "6794612144819422257023607377961342909856254173695813951867587308168165482619770526626236973456071307762896531492592005860755686421463040000"),
+    (13, 3, "-10150682807934099101624460282808514343939198248069730107593617934037481683511985738209553241846456580358094048877893063637483999719325696000000"),
+    (13, 2, "6018384833084777212503351453385280326905632283115053868999657332312897010273669959562385361291687794408411388500425124909503827230588928000000000"),
+    (13, 1, "-908211786869389901023189268422036047759604054478727584450057517807501338210285087833166350120739836405901720429182577478631563032788992000000000000"),
+    (13, 0, "6044112210246261420701822144337698371047774028967962853733344088416022598223290820006634336051680071889790062608505043580152176443392000000000000000"),
+    (12, 12, "1779631056965042423204091712204630145885338956811483694275359578782423513694449347576744295188856962294208"),
+    (12, 11, "351785319140791095306474222679383382584810671040932454969106176942045033134645959031679611334786693766724161968576"),
+    (12, 10, "4951301800803861019254241961360112341189901323885111456542260626418465204743910189812919876387728141893751829008769152880"),
+    (12, 9, "7298926762106520663744672519865232392553736496375820119919434481602146901962616663576565473637617296661614923130854413624565520"),
+    (12, 8, "1412706796438791315579819591850891276336245213468079015108728260763746262077586539418441837068169009770098543419045086120367441983376"),
+    (12, 7, "37187416787214532986509769672694521537991969761757587079870693115006569330954464153941209971093059253395130186766216963723039423773383232"),
+    (12, 6, "39669325660731915144723194862300456796660825674005821237046035820100009476700353835671569849861105294262938655747332173306354825067026204804"),
+    (12, 5, "-447855451319220253957923133083676068684202348417589006484421919649024375571082407284416266157044966687577845179476705036887084916500176470016000"),
+    (12, 4, "1867016995578330711429449539543579531219126782704986862105819918870428524768786353926414383601597344269615067989005729756315977466768873488384000000"),
+    (12, 3, "-12029389216287966194483635239758192410071997801579638451320533742816678200188133177608324669530564858168956470316409677682524272867612538437632000000000"),
+    (12, 2, "44670938275546722232659033723935677630732793444228132520464039164749485422314731572213824802956562060075737549071549048536899952498863985655808000000000000"),
+    (12, 1, "-77211359086370035306557012740293725068362847185860622784869539791563784636986953353140389953398052631577065197759655728936931050119945141616640000000000000000"),
+    (12, 0, "49602446454139559851605551201140272955070820706542984795891960349650564964889335350473424671644910216983993662672324074770326910548421518557184000000000000000000"),
+    (11, 11, "-96413386314528259674344335382385006058443060413023454479649098047227066608444040520570410323923610409613120481027161160008"),
+    (11, 10, "1125895210740372173090726432959420336488203132269520447553573224471149258534584120088526391503969057334516757572367262054104339392"),
+    (11, 9, "-821042272231645640099735257753536736829831556450057588512069870787432184698197723536520513676318723708615691792521525039520135234673688"),
+    (11, 8, "46912103187427711384179013468642116088297980130488308869633807474008211755782394074007852006884408814190523530286181138923037014860612232640"),
+    (11, 7, "-624410377325359660174668322258867687501431588825487179064018652225326927966429809328745387906008122165352928497880972662381695552075409227897936"),
+    (11, 6, "1274178021928836205487790349928659015272006123036720987118610016484894709961243844045309816710052731122369632262693338911410590539353848537055232000"),
+    (11, 5, "158611259485495810909419253736937897151013568578274213456002625735605937065138165405864501497192624615889292585898386791870388127122214798063828992000000"),
+    (11, 4, "-10822013408603805550925503540474635898649909342564828645053126456934701140477974419984298339685016912133723792674124946476744982620334123368054784000000000"),
+    (11, 3, "-2340533069041767606059031683951198948529462329051059918390167113657796770726551957738006991766258491282407509074834770697091281159144930636074057728000000000000"),
+    (11, 2, "3102771640957406213047255235163233562268336251236018614627306062531437051220488661427279860858437536140891089296213796068752196092427196593512382464000000000000000"),
+    (11, 1, "277411780596412134458585341253478099149409543231357125164414438836973265844831460435041203925902712798535655639773499703851918757412922457927450624000000000000000000"),
+    (11, 0, "-147136985073446374259620768605198828884698393776717783299296424359572487062728183459383438753981499828690583347208512680142050837350431469820968960000000000000000000000"),
+    (10, 10, "5836755325705057985724873204762161933383344661981582404174951728629469006643705295443358732970683344077775433393441156750305037525407552"),
+    (10, 9, "668667167088607593622196241273370460164664657748783744910614785636930746818799746953392460338779633767361977114942662713190266969807315028800"),
+    (10, 8, "149601565394445880606399709707269555762108445340261232100808815192117297417765813350284342318184346287581848430160705288348842096307247288998341358"),
+    (10, 7, "-12445763292316504649723657061499663446511291168750181383846465122320113446365974942860388571550877427944661973525999137399814397413497136609853341696000"),
+    (10, 6, "313477718372580399229473060262426138640009212038915795917724738435749017222995671825739037319344744669876133973020714131405991738868268670379280564224000000"),
+    (10, 5, "-2153169437371220619245431961040695251538417592678684441094120345194748041193454423718753935438219035074327957777696463842443093329649225027132388802560000000000"),
+    (10, 4, "12631106994533432663110637783142869277311626243100303909808211820014859451187712281618723276238562103986725339340992483369894334283462856524833610530816000000000000"),
+    (10, 3, "3033849954465722835674719410046250757553031232140160604198122117563121928712201247177290725506553190722928517507880622872875826401299049468803357343744000000000000000"),
+    (10, 2, "27827616502580585025494131663125050588673037339203641888661680382052590443864432741533395328494772167948178623013394952171248703672956683929612485394432000000000000000000"),
+    (10, 1, "1891145960151738242273427851286615253328652810351347809271768346324297980300816745426091785021656823068926475921032245723359671267738969728372606763008000000000000000000000"),
+    (10, 0, "190212077793983904130736929819163049801961891633612728370585620669702986380198047759874667589974167999019060254135638489782834112073307982211011575808000000000000000000000000"),
+    (9, 9, "-1048253299238619396361691544454468486383641002462891521196812918660067482249125484500760414738206030176625861598325810573739739027773058187511539180"),
+    (9, 8, "-126142209896282330135844901683977945793459487086713866118772736889627551089649054379447595208665075492164084522668558776652889396698663666418039685120000"),
+    (9, 7, "1016043337978865314119845924036975427692925909628974757333536454141551983335352334072463290115824555888435482925303201206471652536302160787618165424128000000"),
+    (9, 6, "128920500333084240668945875288766915124278087241066645353628895384830293503710006058378681846902311023106229347712241896819826261924095060670368240893952000000000"),
+    (9, 5, "2281145952219130508295536029328317176474522490397759578551353291196819797617984303221041647846453967855200961082823079392847467994306833337422904844877824000000000000"),
+    (9, 4, "-14904845226694298413260621870448568498329529131217002856591536606236661314698771464826156162233643037395632191395043509085203132694711081981234541592838144000000000000000"),
+    (9, 3, "115034645623697137532555259890923142142241200274550624740375163876755755927107591745084529563549623867899684783454005619724210994149944063867055872409600000000000000000000"),
+    (9, 2, "23750782487143407462543470978510080575400192646224866102806126492202818271525284891560468722251208630941528371916613761023864596950122602792275156718845952000000000000000000000"),
+    (9, 1, "-1626853092117999200277624022536590485803200789405605330066788355468839193582578367103792930789196467582590932051220584276263558190199380149298786440577024000000000000000000000000"),
+    (9, 0, "-136357091392128360074882503770749973535051779389221020568912273211587413105850067566301231073804804570082555308146540824103513112677776712189912643796992000000000000000000000000000"),
+    (8, 8, "38862393104752255182411982035883272993283944161840873394262710724156663087405472244189673310468084730539156386083028133471320370558747094149175346987008000000"),
+    (8, 7, "4310388923412084154932357681160257762028531400602250529152683411983016885993708660593990788269541058391466562095430968192018066872400042724311264705642496000000000"),
+    (8, 6, "81079952394663435515105818478639224868942277616093653083177336466651165981371255362834595146345148732493310569067035708923564977543804811223194334784389120000000000000"),
+    (8, 5, "-70472521598339858182119737110582779831257196723464682926083266337975368406550288524338961311489641697138717660011888549485432043561138744986094463872925696000000000000000"),
+    (8, 4, "1284989887227458819893307836920717748607327364982057656441578106983971610746523607135696295058552356206730560536499460297384207137919038784219570073109528576000000000000000000"),
+    (8, 3, "-4564888994874967111262697172341970540480526056468473504367745354178509307067021929789824858445269358578531577241375992986465234731262554762189697668406575104000000000000000000000"),
+    (8, 2, "4109791572083392995356378805138176445517884749091561233637016580006837594528237566465684374138879602777050954877883545143148304584529660533477913121077067776000000000000000000000000"),
+    (8, 1, "-3948218072956495526400064192012744745683978082866134755595316185741213246182222762672539820499296832703398621958966964797730632573617915147581263341158400000000000000000000000000000"),
+    (8, 0, "57086679166006162093389741982886895323056521344319307312400322525325460763667487624295286077784504205421464401984284676667033972522670779773107485961879552000000000000000000000000000000"),
+    (7, 7, "45744515385242115160911415763216532551195941304575530449529385080952223472868599807359737700285909602859889543289454726257862047361364229376107187678478336000000000000"),
+    (7, 6, "-4211975040020686543057330375984077272593740732846831152763721958447651854335306423580554908781238566270983692877110185588736405257851612161552994224205463552000000000000000"),
+    (7, 5, "7754587967548542168070728345383094217152052730306957456677577110164813887651908641946397103268795625636206030172030020446511907525098569468733406279028965376000000000000000000"),
+    (7, 4, "19768386705959824892329571079218393316044285923949784185512196032335677164903968281959494638960956157525658476379602458722103999839477744649489549677670957056000000000000000000000"),
+    (7, 3, "-16814657601610841421315498734319443126843265653906794832860928985188002707783368440266653729449420080513167325161561318919138551865490170217982153791547375616000000000000000000000000"),
+    (7, 2, "-118072569283651647089498035217402505692192153391422756732995889702634278283604616961314758317022281493903540912483370708308907814573025587798084424047142109184000000000000000000000000000"),
+    (7, 1, "131352173359489656621823598374380825432499567750642538067836647961382083868598575673048099452073876834859053041926958781435102354096142527050924200362248241152000000000000000000000000000000"),
+    (7, 0, "-13224704996320262527578187503732007684704734156226381134593887539297807800685574875787613921199635654515291502005522646370144215437377529219985077794274017280000000000000000000000000000000000"),
+    (6, 6, "82675373631868921591359719821910864392004573438533193893917995967100709359330956101904702716690222561609133294431090213189242155190914421087924154182907461632000000000000000000"),
+    (6, 5, "-374590665835843261811674367796425998928324740015228066637403858547506281088089248031908329745067176092744282776751352581684220230619447473996851752014723416064000000000000000000000"),
+    (6, 4, "537471751044858292373652031521061824043047413864001391441137144711889987656805199892941536741055423837178928548878813803056492912137226707717904759814914834432000000000000000000000000"),
+    (6, 3, "590092317225177646237433967198185020375308984142480717904076025428632964204218896901051467414854029489396723423065209968438062427472697397781179263569358749696000000000000000000000000000"),
+    (6, 2, "-961552804322587839354308919930294462148484548469945529915268233738328732908865763545276571521404377080913516421107659346900543409337157464603428989381495488512000000000000000000000000000000"),
+    (6, 1, "-1282519063547511486457192065253440526641535039583272751962681219088937183603795512937872258791613212607480219202653245461461064852638933705629171975063257219072000000000000000000000000000000000"),
+    (6, 0, "1321389606723250832117937234838329768332715461434914441709989478970470176152966514080009709241729655104118070852322928181036889343116235695507147205856970407936000000000000000000000000000000000000"),
+    (5, 5, "3270319757238533741212386918261091500479037561101850063432178639143483964069189008968014167287025610888577368809694495984025558897865144507303963863642410057728000000000000000000000000"),
+    (5, 4, "-12016563126937116318374687981463025990413255848166093033040632071369182605018576606748076208269560946236887981236418284728446212643473849405747032692445000237056000000000000000000000000000"),
+    (5, 3, "11024027198715741610522304156022238164004101381060835496428351760379470207391094149512549345161712399715042447536050974382952346385938802362638352102713129435136000000000000000000000000000000"),
+    (5, 2, "15946631944658533012940802608303159478392388836692244748741797524412980729899364870000408325289050417076404008021860752541092124075485036296798435379855027601408000000000000000000000000000000000"),
+    (5, 1, "-21396661861531227077049914851921529917315650142345857510506895590384476858355974462336546848409873050113567899082893730463096081938240590660766571360754058919936000000000000000000000000000000000000"),
+    (5, 0, "3857075299930886658299338158825513606161972357446899514244992810519365265274074218043069591950006137378650968508160981392511728255070246067287704016299587796992000000000000000000000000000000000000000"),
+    (4, 4, "80936411413247190579512597615545911440235952973547340707955015830633127505865090939861908672236797336657502833342937470719922778408604342559642498252913841799168000000000000000000000000000000"),
+    (4, 3, "-176006246978227256481555743931349051421578634281369842513203050547165576645921270018667695836098831446255905198339255923375888774129420097157831959634220467355648000000000000000000000000000000000"),
+    (4, 2, "97649002351737538536956847926116469626463278436925792672887673222606431252831691205492896308944167944782159785297096022316511707411564820854488004175372516065280000000000000000000000000000000000000"),
+    (4, 1, "41432849259609475568130168922173018106038703181793533572428412432699076410787760284649523877559735440190592826723621863613870444320031740047323979522690244411392000000000000000000000000000000000000000"),
+    (4, 0, "5700803081816887702642342320887600715260853896386024857666100784813156937465955635848720873873754081607668823868116831406166112724855607971953472014405569871872000000000000000000000000000000000000000000"),
+    (3, 3, "537676888341647374331809878002902749451161558627502027156782045559356086371739340576133217266973482551348837074834290983809867562180357475195587766654429751672832000000000000000000000000000000000000"),
+    (3, 2, "-492693920777181761683979155663999237195092319358216060778479432721606241382559704736622426714655080229076365394015108104434505959133024191540958254069202162810880000000000000000000000000000000000000000"),
+    (3, 1, "32307381245480688981062551477746295813624899236381017925603604366620221750387115301417356624526114297538697040929093215499755181948471996036097942765585272143872000000000000000000000000000000000000000000"),
+    (3, 0, "5002234951167744456992543113123695788957661929598278950835417497165448421698087033875199958828218000184729929951889502377126547875558711912980077054013315481600000000000000000000000000000000000000000000000"),
+    (2, 2, "483335837614562073237706970859221176139087133309204649530314862672924683491051714167635007091696213804293862414021891369962154224199494459168387204357983135858688000000000000000000000000000000000000000000"),
+    (2, 1, "-47939918666212576307576300231677421162857212125090631146282253608308619052282772627789147146509134677268645852632298260710135858527363360556560347739850406887424000000000000000000000000000000000000000000000"),
+    (2, 0, "2803931022848209090040552503831912598117673655086271650983593541743242937315101792067190642901425693162170678981992045283522362553754800598366688223557876449280000000000000000000000000000000000000000000000000"),
+    (1, 1, "-17441686029009212175318851166827481860769685516544792640504987240748359475259209554213835616268455283984486896808031872898804939221119693398756787747414851190784000000000000000000000000000000000000000000000000"),
+    (1, 0, "935089802862149882795371327430319532815056136498290380065454949558110752787505729010172847381558449265788193437755750506980022982972689299350844037667684876288000000000000000000000000000000000000000000000000000"),
+    (0, 0, "159207530860014156978376569030128237596892190022723904273137340611610187235672674911169572369553316330172033409776497214589962401330525148167343142463365709824000000000000000000000000000000000000000000000000000000"),
+];
+
+const PHI_19: [(i32, i32, &str); 209] = [
+    (20, 0, "1"),
+    (19, 19, "-1"),
+    (19, 18, "14136"),
+    (19, 17, "-90913860"),
+    (19, 16, "352158823328"),
+    (19, 15, "-916741051741722"),
+    (19, 14, "1694657213749255536"),
+    (19, 13, "-2291938751226804835016"),
+    (19, 12, "2302719626909651820686400"),
+    (19, 11, "-1727794667808398702956199067"),
+    (19, 10, "965555992038796230927253207848"),
+    (19, 9, "-397819760685881762165285431847052"),
+    (19, 8, "118677762524572948812522007745083872"),
+    (19, 7, "-24938719923069377549212323611121503830"),
+    (19, 6, "3546994512109782266492667496719863481744"),
+    (19, 5, "-322318457889572580384143808576082060270776"),
+    (19, 4, "17183434386670284132745984151000004708620736"),
+    (19, 3, "-470582156281933560242424598285161438618588261"),
+    (19, 2, "5274696764540151448449127999256767037511651480"),
+    (19, 1, "-15209749570389227793202178409058299760194810260"),
+    (19, 0, "2384160010112315427969076500641214071623680000"),
+    (18, 18, "901336949223449007625254"),
+    (18, 17, "3833594938292922033892519796232960"),
+    (18, 16, "102959060519345807251347435944229717613392"),
+    (18, 15, "195365300091780705881571885838384887554744861616"),
+    (18, 14, "66486770108588230696526824301081251531603413508948014"),
+    (18, 13, "6449597563048048557204142212446594997196673000382847593504"),
+    (18, 12, "231378713236986145810086595374168877790131423180382019057492144"),
+    (18, 11, "3590159117383792330002780847798294484014707197214952829318272488680"),
+    (18, 10, "26543580428015191502221649130019062632572915078747441921052517827550690"),
+    (18, 9, "99153342586390707946289431420137888768726435967296611049475684617277034112"),
+    (18, 8, "193067493767420065011123168452686702022450138936313899735752676755072850783664"),
+    (18, 7, "197698757111681252005253456931057631052247187759452941688544050455772971336204496"),
+    (18, 6, "105141844001892519382000156336504644908228300752724397178795419386460830362197423258"),
+    (18, 5, "28000424199250380760101182639720475054355331164547689910129130963314531350821247257312"),
+    (18, 4, "3486178039175498704009231482265868687143378870213672097166676432651402253700378255067344"),
+    (18, 3, "180075625362487526175243127870271300506960203535387180316301138718313896108917304095864600"),
+    (18, 2, "3109746953950213058427596112510523080878134646221202633916549536846590955341620352611890110"),
+    (18, 1, "11400928913436913420323955220590050910012840633891729543644150282495174773463163465113600000"),
+    (18, 0, "1894739651272918667917476724761848613743140887394274971499463937176475713761175404544000000"),
+    (17, 17, "-209612921944238395135803983221279937811002005551"),
+    (17, 16, "3698714015144425448819864939764537451993175455358767569176"),
+    (17, 15, "-1478405628556295326906565905655302958323837433287806047320290416188"),
+    (17, 14, "46266363181557365120906740022101379953044715375012087191431868016660554048"),
+    (17, 13, "-232387331779169776971904048975048330059729062196244506355371615697780735847752278"),
+    (17, 12, "285577756637015928821155769721142918016423058222659776692207661190229512286680859822640"),
+    (17, 11, "-113383123889977219165030896664374167371612548892605263565847781367700299884693795209555100052"),
+    (17, 10, "17692626226169810967390242970020460263416611553540130613831339785623611953755298417938144384456896"),
+    (17, 9, "-1253974102554547123029200197321888581768537561695113117162539920686866164438788926776353275707814246918"),
+    (17, 8, "45038576703566328742979000601272575785440118279040214232312895868955966232722709725664731072436294321010160"),
+    (17, 7, "-890592106875853589584856035529877967641106216397220651661275392492055735418053918237420107766181761947983882848"),
+    (17, 6, "10304293608660014444384627781455755984945273763092789514878965366567246683774571157782660483160523070567627755696352"),
+    (17, 5, "-72688974822627028076155952121414977875990008030648430631642822086950079168726676094462542675806018147378357937353631832"),
+    (17, 4, "319456726575038206220568040819043117518110438372876179102292865336979883339852047669438873821802364302140860553090562051520"),
+    (17, 3, "-874116133803660906519231137379087641194073870261506643011145692176086116512847210481793338250124991788777384882079018840873460"),
+    (17, 2, "1442283724711151405081811049132839381085248044930325455831820047846387947995612466841091912961730073895206849006553975170048000000"),
+    (17, 1, "-1309879982172093496649142526280827718443258506367169309163714510847316215572040999899944267692381708170635850093107188340883456000000"),
+    (17, 0, "501929167348782975428223845768270974033154020255632779710355707938250756408092932943686854561965518859179407996561537829437440000000000"),
+    (16, 16, "10695586550956494842291041792339660466102700229843871650055876488258538"),
+    (16, 15, "182421041978100503881652457474820030340676242597554281592842286119560126793714528"),
+    (16, 14, "124799132065516101416914941756925428972083125736483210744952146795156851536770613071401872"),
+    (16, 13, "8128214140846281147871632458111446439670899503243207404701981245911976974351817024071824815388144"),
+    (16, 12, "84529819810532840493878905761941286945515410490037856888750615959905754315443084296038616954435581307821"),
+    (16, 11, "196642826268004220392556552177113849593840914585599315179584079191342396950124954125404326048945222203600335552"),
+    (16, 10, "128938928579568882346323362263755020850172859141018040429312624428025012924248067837629148574456873918502488408644160"),
+    (16, 9, "28050347657539259898587293533716930936418971230565308453800283325421626342277996264903136710513947633353198821841534874736"),
+    (16, 8, "2271911473886547254557061430647709711494815792746375543185039463253449741796584649298288447273898647363704704230658170571456564"),
+    (16, 7, "74097013242014402599873241737945925759350234016377455035719188429743670769415409397510518222236378697211596266502847340605961713152"),
+    (16, 6, "1019778444768450224858745408809968356919931228259944156777287220001770210305099940029847451633165971876476305415038853342158051775072368"),
+    (16, 5, "6017343769473248035574220045116965137626107977270579308480987434670291246137855848506319381678010410471132039026866210823170870648086686400"),
+    (16, 4, "14922038336928913244073107460465139832478255817007344739067908481752742106632436075539395598391824312136431670509867939572681486638344390056685"),
+    (16, 3, "14490273446009139410805585249621231977881524363252418038028918842813195008422191078488285363556920416050941878163209768620337265095147086336000000"),
+    (16, 2, "4688664562279913864241101315704822538125048352330934176151133347756566967968368171374588483598114832998159457611557672645213388631008522272768000000"),
+    (16, 1, "344855706900772385075249698112088485569016997445760834221448944439328257255807612552418220865636074867527177807616435739725174181422505656320000000000"),
+    (16, 0, "1394767232330711861054744276692003070207906161535328828094495371751432561163356917637109809615065847305045598155306978929745571905483571200000000000000"),
+    (15, 15, "-59901702992212738650503499939204681526415734020027197396462660965399214505127787061021935103"),
+    (15, 14, "393156260581668360346478759128010772773689659606696212550359210031046000333067844109785190706504858728"),
+    (15, 13, "-148496136267762268250871431258966710313617887895943401356640619829260812372390214123002614168642227370468591348"),
+    (15, 12, "5921663189226600321414264346970121369087661308092799869701450444453187571451056868719719844178486289613019370421635616"),
+    (15, 11, "-36853107727904336786948168225241149783810205261102865613444242130089390155658194387261038688748869310502928552438582633910818"),
+    (15, 10, "46838065500644745947021091624817021213919389148661139464173964086262751214783992648810663792164352010074033988611083100019044339440"),
+    (15, 9, "-14786742063398871441659872612869972050325633106247114585749892521462293119365171721982690147232264657374164719616420512634669487487925904"),
+    (15, 8, "1352614685394661819747372506523227000185196437430560492149203657890135258495780422306601788512808088963184947598721338542953598300815870557312"),
+    (15, 7, "-41083877525684196325785689905726951026836890904562852412223977931245645259760633311196402933112436128851665202513124985586322390133006589112350732"),
+    (15, 6, "473843611183423545051692089513212438841859407691748673043346114826530557923133927655180296393899455451656546320793466771088729417315372628497039162400"),
+    (15, 5, "-2352074621413276837241456710163745068579676736705471327817533066417537133258519565390648645570483723375775013511180514261054945408250508450391746924341904"),
+    (15, 4, "5447775582043115562915343628466618429605046295818918924562471530063634152388562762572622785638107528839482849116586008440354554694171479425999939419422720000"),
+    (15, 3, "-5864539236211891014269332768934257774390901407708799492268965627117169978050143898135163454770423270322070463740817107226422445548561111458494310401441792000000"),
+    (15, 2, "2542511801052167898878900676315493240620508694143157360639359115163532078161937655032387506567052338682563038794286699398285714608615181078093217692385280000000000"),
+    (15, 1, "-273408717727358920922829338817184465640739948611867191274502078952478229624070412705860228654356592228174246201793445482365910506118055116225939301728256000000000000"),
+    (15, 0, "1291932379102500847406708383657803133122376018699674323525319010547601723444923221242579214649794439892127053226020109277730546412652082951036390604800000000000000000"),
+    (14, 14, "13518133792538018464992446112234193146391321607214198635135037641819789028647460512398394824451457897758534573322"),
+    (14, 13, "16162098841097333357021787948105525226293279191471912954516034234956184674320294936400675462524865578301763899277385585152"),
+    (14, 12, "1322193709017681986763822250403275405236702381052116466313504734032850832685666567843146833831368904018789782005501347428010658448"),
+    (14, 11, "11334061978522922657842168316584801010701132153071417892786222040474068466933157123429243932191823653929065082236295511668034689173732400"),
+    (14, 10, "13434671114222174132478414820235220843376784704392380968264322375934439849451713934808004096186645615571384176965163202457901269658112454058950"),
+    (14, 9, "2602879097483115223126537944260698229666875704177959110586423096786395304764334652590764285312879262803707068242400497432965879585355819264838079264"),
+    (14, 8, "84751114542179777231911242082654421279188486157532146629119933776912537671971404543441354206306324279828774587436476152518441066333368315687166428670416"),
+    (14, 7, "252132726114600555654956717608088465943475572161044533778138456532563438694048066722989810902217248431291364570387518451937831257671314392930042246523766560"),
+    (14, 6, "-1164474782934832777452811173938424071804573768030201513711037753068557125641471676933464071282815868298545622631070986840065162511721300076349108751702299808920"),
+    (14, 5, "-1483871692143764933059729598621983584968332367129991113788455029202413492679458543674302494204761233316187808820535625809406826599069134921627858517918711152640000"),
+    (14, 4, "26562913515246398511644180553791072742407139752073901029262131651087692665358270240746989499892898076485095019433015902186585257954959478394270551465001329098752000000"),
+    (14, 3, "-153073591733168913585086995600777814391145426169063038445705881745232333105230984610576166521712553886445315600974165869714324658231473608165978870113676044533760000000000"),
+    (14, 2, "465291786318356995154652142785405887996185585508011009681899531748245407516855325519322956670047365157334679109292872316791281558249288836037207769697616450813952000000000000"),
+    (14, 1, "-693991359397718858197533749082390109845354213561212575479398556223032292265734679960647675727964871364162905386423136085706193905583824895228584081179406615183360000000000000000"),
+    (14, 0, "398893145059975568785434089070792207102430386276153499967855060630076060536962309165909058200542147909464748420743164018966882730485164979129285157034548017496064000000000000000000"),
+    (13, 13, "-36523316893720363293670309379536810470083774242651371057717729678717457551709230702635520669219137918126561568318197669599902199812"),
+    (13, 12, "3522910279628194202686508681171446492671017572774661556939483593067678950833888896222957965621969646510381202998077262869509755454092117024"),
+    (13, 11, "-22495141507945056909280980473525968405347448772788821119225373773720564667432110350897714856984956422362149318116612431155418027777934907438129752"),
+    (13, 10, "12451805623440916398255281526489338203996379944050221003888480073737717460186394505516712367096840664811534694109667619696787560958243661292214420057376"),
+    (13, 9, "-747369145332017813226884569539347354053688768207515863414782982204235313029087820067140997623590481319517291428057998007660385206149735651119135659219627624"),
+    (13, 8, "5880713334246831075962243387742720929568845871632667948637360181887807626039546409621510576024866524871053843115798613167505393253163879498284645113228050350400"),
+    (13, 7, "6325589005721640888803568570728158274633239598111225695201450483624402384750847338049781308414038192304383011577619872471170474753520284870659276396413168537750320"),
+    (13, 6, "2927920265014001353108124249599137416148533207981372840553828913888640356493800606286235644751286315916245302622793799640496187137780155418004241799245333329833164800000"),
+    (13, 5, "594208163760151211088561060510231823804147052867598131638183436534673066997971897939197192414027736667868519822805902465752414135717523661110206864220570605081067520000000"),
+    (13, 4, "-90125155426346238781767551888362114966508447119528803467966413819412546905041238702181490163495069842989256278808424120652659310885813218177922090623096159973891112960000000000"),
+    (13, 3, "152512736290683138073584668979440354925011251196688548381738631092109472332274345198169968205010336740143378165681885949270347052656176696819369114323528628806954778624000000000000"),
+    (13, 2, "57006189831468590179344049298611630332383250022518313712585789889111995429600616735716286264428995586764205983413010122822703304193839913874893704069052314010507018240000000000000000"),
+    (13, 1, "-130564231023508596274325988303165625118779919786582657244384807867760568517075764828020596189019825071177977406900651019221576332810750800605894774435285319689487515648000000000000000000"),
+    (13, 0, "-1391375277195550149276669591951882088945014192824483289889206484996472348500290646235333300208980333997501172465212134280752814972348296209437049952417490589471211520000000000000000000000"),
+    (12, 12, "233325504433425838627283256178177099893912288176562166364416712467759671448287840547446219918800992583983268915652449902259903420273955023993741016"),
+    (12, 11, "570449283404631389396759478314626354587589049163931805569391533673784135421422996292443227839836846950357374299105274385281466263994729551873579598018496"),
+    (12, 10, "75408688513445932376018023132730007317670962124980212072768106794506493596872683740572439075700018267764532521465347482694080488748225442784533636781063374064"),
+    (12, 9, "-11105214075267440873220562535881118394935616634627129828273947587640964744873411649290320232629502920940831603348710684285023075158638419008644859226803453892314560"),
+    (12, 8, "1098671191413377017352449390668047701816646822007291896866567752985568216479442221142028483122545640943544607024757031801411914839052220578054622989576253891838044480530"),
+    (12, 7, "-36763656601228678994112094793280456183567992843236199440466957995574257737935243960729144029866868255741166073665584185347800892521086911541505055301718850094905837568000000"),
+    (12, 6, "429592623515684054230339512900838327202288525656565505777347079254584069009100892418188955843765849898551614234736487890119032201328881119799419811088665826678285407354880000000"),
+    (12, 5, "-1546357233757734425777281082346113845689449579625047864889195650539139138936759506151989724633743550465853635524196624436867443737886344748489720491629289999167205146624000000000000"),
+    (12, 4, "8210221377907174630724395737991772430902376499394788202053754284740515229928192011534549747818662247920075406838873430005678975783591043925235961159994984676341030791938048000000000000"),
+    (12, 3, "2901825011727669028119924887707894081563167365616157794962124050344198589687112806520424161031525220824492722507898303003273515245331685691043338805317520529385999407513600000000000000000"),
+    (12, 2, "9651603955885587757124853665889918644568281873872245804563796000422440384840324484796577025527678493490027306624748593372639040156512695113104501552546872204238063660957696000000000000000000"),
+    (12, 1, "422774942434327656051009488378430492711387225624557536789110189760778408920150280138565849123529070272066995009208959365735467309375259825921905160134834646090242892759040000000000000000000000"),
+    (12, 0, "9692000825599178431980800444123018494563368048405272827232772678005914661647609060002233413604107019011837728982810003454290458156310831747339683005512222481579511906304000000000000000000000000"),
+    (11, 11, "-445817144963594800747729122993810156072812233149123619407623338673487799759975778975789626315986693589065690796147443102365559549480858142903155053794435971486"),
+    (11, 10, "-451169457382104793400029947162804303745080879336801653127314840878487595311032163796830548082665950374616437368711598394438229650268680908296609944056405397977218800"),
+    (11, 9, "-24893756390729510831513965769215598683885040417357346448127482246954362584082620735156448833404310287593657577272726880674537518445545710238523248698816430535585332761880"),
+    (11, 8, "3466663294830863598809357257357154101825004357454304442347997187878029289567073913191299262748051992191962304516742029540905796263631892615710319034242542497112298792345600000"),
+    (11, 7, "-34091542832667171572141368250049806876404662735648684319454418590745642253006003732722401152512860514813558388890825650264743572292566689706896605465176757831414639799304192000000"),
+    (11, 6, "-2053588256461896812927284161952414102921891222167824688821138385616323196225362309379720858976963195964901392136186738566400590106362306611933449497234891905493348288634552320000000000"),
+    (11, 5, "10303705094138028423371003097941965063014126674049221743219882348072820851987460720156567137063165828897383395167265541035020468037932446081080814602624618849124230575838724096000000000000"),
+    (11, 4, "26581648213015913845064792849838229011022398262278274033901289279605393764006796781649380869115666099024541265919456667757216547850413015337805167542328155120937328992006963200000000000000000"),
+    (11, 3, "-67140575475081550513733840364476155526105368374845201966108291099388979098560747073379137968639354359874222590334090808381834238093861274474493004008477894520781122598156107776000000000000000000"),
+    (11, 2, "6861455781669469006021810578347487438656310786492927089856212247112436547550596712464892207905585321824526627373601626034009919065356697753625057686615436521319746964113326080000000000000000000000"),
+    (11, 1, "3950403486845839259353580078172664571598646506663067092138476437195114571982355165402558657888642704814275855342865966034005271362541408821994440581327063537908805660548530176000000000000000000000000"),
+    (11, 0, "-19449995683052232067571635803397915594831089072388729705277323912899395839742980734165787442776666430185253231126915994870340878239234522362546817441211720651183044847206400000000000000000000000000000"),
+    (10, 10, "277555105371787216601950994045690155236269261548159007735176493831077717903470674643337913841896430455199194586243050021469646235777449858492674980353008900172220016837556"),
+    (10, 9, "48146432596720576346565946020534679348263063018170519027380327378595339581715957775097214701269021201592538854439287055860135007056425666362663362575391456464532156710666240000"),
+    (10, 8, "8393837068215596484676473442083425113324142220842853218314808266926811399380057701145265361842640242771505941521127916227275562646161919178509608962329597495876539874675785728000000"),
+    (10, 7, "232208374480902258822870867265896562972582887124055089911007899488781044981314995964793409892757797947277651163699980087473076781022661715534196512460416712479667488392772321280000000000"),
+    (10, 6, "1319128988437113465425460789255935895020695118739701408955682217252366134611815892291262008600894417131064721681775578567688851668880942605609489203700690807666236877052764487680000000000000"),
+    (10, 5, "-5319439696576351058667231580035909272714299070055078394108266306213244091318093444571421077838351111736238018064210898364892839141144305637895366507096123601378245560009574318080000000000000000"),
+    (10, 4, "17051617359701883533956628839304179192718309221104762964830996863038684155600488795378168979084744374005322212025262656422634620721706990375123849035443434492151280456963136159744000000000000000000"),
+    (10, 3, "-42698254682140856629663297233714612794511762546951846179087897847097896429641947406533592261213035893224130022709441623171863057029003831808612848311150813944151230709525622292480000000000000000000000"),
+    (10, 2, "39024734307401836429668539515642397403177448722479037386112250409476315316872604788373567165490885937079907975206247730494373931460960051836630281294873601172237459139334712066048000000000000000000000000"),
+    (10, 1, "-3946549194852649030297123596609902603785695697881798263458339672526894196694769047266266207322374312105308841374526179286591524592977465696926480800013267611816954528381282549760000000000000000000000000000"),
+    (10, 0, "65503858340121360202722619436342985635127754105711984897551344714634275225462834499357306864844437707597062847906000480184730957591145672104743959266360066249009398348678955008000000000000000000000000000000"),
+    (9, 9, "-20949809491254957334901166988872885432323018764794292709735759474152530744112871406544922332636166633845322394627930418801943655895598393942945083179757515723457293307298709504000000"),
+    (9, 8, "-839064849554742754831471573270326277231548275903765203900259923962677446814982745926257445254627608755286267728189289469709936191322097065442859210481773071240354102825482977280000000000"),
+    (9, 7, "-24954343049032947686684493600570367519109839617515808610716516237874774995425713415097516058944930901903563065008743449806731969862037434184493146703011864678472760367612669460480000000000000"),
+    (9, 6, "123438777644742445475055665165271762437890309203789945996452036056189121501043986352163222169601706813836168553696780943016872238959314671626249190368119128208569262331464463155200000000000000000"),
+    (9, 5, "26018977655872176578089550861296698034719643243456949309763723969065520859468525880707654403387320934285426249734359904222396862543493919006219347689788028879764481744719231057920000000000000000000"),
+    (9, 4, "-981321271402688739048231628483585320847923858583820356559877996620988476129325933694814095728658949637779387595560094685358417161890379864620133026997574840984961768691811305390080000000000000000000000"),
+    (9, 3, "-30060672981001602724276862080623555150166604532389304382822740385110236928296492343188612511874531395180318726952744996340686957482194608531318021822343530299267377246389716123648000000000000000000000000"),
+    (9, 2, "4139062948499205718832866544569592864237751936156100546163320765608138519590132146997132798340750829822615814384733121420507804437953153396966832685599725980846626143933510367313920000000000000000000000000000"),
+    (9, 1, "-3084953243803798584128450593765796012475102174436111197996030393989954472865886590348279005691453599011942878309236747533923729428972346120906208046006561518764241993407404246564864000000000000000000000000000000"),
+    (9, 0, "-64043147368931396476136687830078085640852634053462668775285870430048537019218795631915761791939834238109769131402624230902847009243752936688853138034629964057268415318069304360960000000000000000000000000000000000"),
+    (8, 8, "154489198564256199428270525391021222892646676970160769734878131667916850422240702366740721343256221264143720021832891356150993470902509765793500858382926111721390800044103168950272000000000000"),
+    (8, 7, "-1671963081974838390653826571090823318349057444915171943276742669622109710222812652154880679157005327763180470042588682984302448863712276707567691136572946506344052548423713921433600000000000000000"),
+    (8, 6, "14187286164292993493624244146855577174427126240444353940261077757503662761366257597569071257660794014345817388784663792605416141824125473241256776741429160562441389056819919558017024000000000000000000"),
+    (8, 5, "-51242326470237046203530834871889911650301798671908647292535264245442436070138497494088608088566586380071376564502602627593354405993041315940431382640237149878622676983744222923325440000000000000000000000"),
+    (8, 4, "17572314688935743917386521526124636381760272032349197357053980750718284380842049765999099996211257887865438253016938958053854107286536017158901554179045785485663443779078106942275584000000000000000000000000"),
+    (8, 3, "185285175492658308431971976034619552037461989790876695759900388048295302959083307921769609383966031526712168648309192398440045677509813570165192385840606658384788929516475362039562240000000000000000000000000000"),
+    (8, 2, "-163227889322270216538991509986579551639794840529515348934283149529820426133250490847078011468413545964866511590836020120898399227407098078833072126395753585106762702027112287098109952000000000000000000000000000000"),
+    (8, 1, "-120596168589843436791205577566916376694488242928198376564065862224194298911847167742251547112034160902689955769358443431584005033071684869956105495736204640503587024040318457861898240000000000000000000000000000000000"),
+    (8, 0, "123270344352217521571578390663928212821122804667434251237403813190125740378755852578859517850356033077246645111285994974730913583672415887728417692263961792910187418058912514873229312000000000000000000000000000000000000"),
+    (7, 7, "502015781736428415906684005306428342019697020816846692671548381347789892962733818392644466110886210835229248121665198850731113694921355315142048249708047643456740972304311204708352000000000000000000"),
+    (7, 6, "147460150906389478171512270036449598519923970735147306026779747462459413058035216965661562091802130622412643318410715900517138509295419769908313852485141863264047459202356832790118400000000000000000000000"),
+    (7, 5, "-1404350462803525068367182294203905864971965505679693742164422616502558106937710227184071749691814744270519743766033090757890343292582053088410139918252138665460396527857473812086915072000000000000000000000000"),
+    (7, 4, "4640010006312503696723190653497697957137754045057414568282767650480974045337784482117474625107131189294937094539406407494386866297469995288963282579520940668237606859128079059685212160000000000000000000000000000"),
+    (7, 3, "-3671596974069676389362239231509053699140937857842412072486134310031497396803056402762069077532190505069354262937816783917189346174535593858338095794242294963796801720486902252357812224000000000000000000000000000000"),
+    (7, 2, "-5755086846764435044821701393932800827618679353230741707135144518805289995610622506610793295247684513244371116856340346817200606840545161713250409218610456686178880367847229372508405760000000000000000000000000000000000"),
+    (7, 1, "9014989412762803060409857307890604744253031338378459752405921554644925029117857392462774077060057398332495621995699055426214079191150153206775923146649630808218990594216999510993797120000000000000000000000000000000000000"),
+    (7, 0, "-2152291213477177247862894737545353041340957784439474468991217010673831528333896801469173732413804504422192166861949893535425733209219170501627350779395965976075319860952405722299105280000000000000000000000000000000000000000"),
+    (6, 6, "-1826088664312120179422131638651504734238535978202722319066923644493275504811952084652557738067746172903748952768462906182439831643090678971825152760564453959469615489488904369926569984000000000000000000000000"),
+    (6, 5, "2561759291602592835230234623383005178560372926440832831151056545895855556939768761287244232138082534108555436581134233639005409111754843743942868208991095166827761339601440682635427840000000000000000000000000000"),
+    (6, 4, "33349306831084836657090846904463502319838599773464989287746150641678329555230184055199335159403486109165153222791995542206757508537441737492685066797810624018462596560581209296780394496000000000000000000000000000000"),
+    (6, 3, "-133468055055518442665187392489845247728838036988084599205994398760465497453393380700836753739514303258185304008550143033071751202555311492673989145887944648565123195566527118763318312960000000000000000000000000000000000"),
+    (6, 2, "159253964841599076347759998058434348453752923619290747837209777082395174308658194774386440389966756527914606007488124433598397874187487978856162524647306618313398222449470336465060757504000000000000000000000000000000000000"),
+    (6, 1, "-49809019318064046766772590203099178845848389263562754654812790528075387507431677782424517453136248184289833523426455887389050851681533035518179692712666105564793533920991772161972633600000000000000000000000000000000000000000"),
+    (6, 0, "16948090905597251700886793204015700238411804726682176626532923630485003321722347038806487104203214360848544825551503709356799422389817178543978109942959506015977047263151423425504870400000000000000000000000000000000000000000000"),
+    (5, 5, "65237062509210844202291588576193250757425749222674390250947684038926586215984362067673452219460941676024500185988377272896914965881029193229313730618781605630184270781888595402971676672000000000000000000000000000000"),
+    (5, 4, "-313819734306054578435158304919376346148239961692059731072235339193446051214632904290068715098396653733447256185595629759395914201770470787425593242527755184220250009299510791338280878080000000000000000000000000000000000"),
+    (5, 3, "387199893929695379313141066086636345134598406102230651597290681910319741263822252912746722578953460620356299533506845883180638544061445351125372310338397113055672783050389542472781922304000000000000000000000000000000000000"),
+    (5, 2, "-9763221394523062002894904996495166328249789002541774078288695145909241001787177629213176629103212107995522539353413077506015296112030200389819042960709264063442448850260978634913218560000000000000000000000000000000000000000"),
+    (5, 1, "-8295474396950439740630419625884261582065447969831084164859979305561106772484185062882422438882749825895564337156446539528279035591920336184331337347856320555417223260662077106453217280000000000000000000000000000000000000000000"),
+    (5, 0, "-75684682212714937300995906472395854488089257202310241140812022264337607329455177075014377436477907331387893143871573730443699697975125889671357009829713788201104984896671688440152064000000000000000000000000000000000000000000000000"),
+    (4, 4, "449391216701380988441249719815239251449332952755203324479906895688734593458900830759356951737202937036060448701969884725329198633967802636116175731133941691209853588901353391665363025920000000000000000000000000000000000000"),
+    (4, 3, "530647516544532280802967619020086613912382716300430469053109561656643524662298952068094176929596104236309075206069937748044209292567568616175027200182133433868118474350026290628069949440000000000000000000000000000000000000000"),
+    (4, 2, "-991174482785339414968115795888646497218359022772408550684614453232594868525474545305971224805406957857246862020282329295248322204523968651023939554220530407416774551038748329973794209792000000000000000000000000000000000000000000"),
+    (4, 1, "198420584393576901744018154276891869301219738605352308450013988939404687673243240093655033488881611191393377542854607290281965037240161914272977616694169101016264283500281829149072424960000000000000000000000000000000000000000000000"),
+    (4, 0, "201845056856763977305783999007132874665471094831258221877276721069852813388367492571600838554971310937556356200613098468092632226655172633635185764789252444190696312506341004799306104832000000000000000000000000000000000000000000000000"),
+    (3, 3, "-1822556159938432571822997483061247824832504579650316843635129940411891991833114385763163714683233348413986097978707726284427926053513420059000552539788639912173027253999003616216461869056000000000000000000000000000000000000000000"),
+    (3, 2, "1060426504670589457609058625886262365106142271084328992530711880723168637646979055368323616996396537760229023495107214063037922061204291726763837412828682125617962891352137390319772631040000000000000000000000000000000000000000000000"),
+    (3, 1, "345945628793588583662774583402937530352938341896281958843253486307071562023138932593681569444893229896462553147483987771959151581307827794070228635307870639536475738823615748724475559936000000000000000000000000000000000000000000000000"),
+    (3, 0, "-305073831968453175179235359416684166064579619471901819076522873465859533544260032984634708230304078552743807712410166056995212233249369102688715417452062225766077639191180112415172853760000000000000000000000000000000000000000000000000000"),
+    (2, 2, "583965105781842436843243904813065575003558255776615953318362483997371325790613053502580541621343960044562379347974642179116063383945381992326662504962136171756422268645620537669151883264000000000000000000000000000000000000000000000000"),
+    (2, 1, "-928478171686997807231597011631411883777704278904664959163738744946176661566358069440794055736570351583465008093504521589992412651593446916114092638503404086525034317288656451865401098240000000000000000000000000000000000000000000000000000"),
+    (2, 0, "207300949578564243218645704188233573756698565784889102274747706712849481636623733588718435095972999043849990718073555702987704135418252911825286962549672213002200479961567303771369766912000000000000000000000000000000000000000000000000000000"),
+    (1, 1, "319821934456971398416636367068069350851929468309621880780198313520246860282658164285729476627144813512468377573794192513342453004956400687116953876323171214169599865717058639525970968576000000000000000000000000000000000000000000000000000000"),
+];
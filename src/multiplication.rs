@@ -0,0 +1,349 @@
+use num_bigint::{BigInt, Sign};
+use num_traits::{Zero, Signed};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use super::error::{Error, Result};
+use super::polynomial;
+use super::term;
+
+type Polynomial = polynomial::Polynomial;
+
+/// length thresholds of the multiplication backend
+/// shorter than karatsuba: schoolbook, shorter than kronecker: Karatsuba,
+/// otherwise Kronecker substitution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thresholds {
+    pub karatsuba: usize,
+    pub kronecker: usize,
+}
+
+static KARATSUBA_THRESHOLD: AtomicUsize = AtomicUsize::new(32);
+static KRONECKER_THRESHOLD: AtomicUsize = AtomicUsize::new(256);
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            karatsuba: 32,
+            kronecker: 256,
+        }
+    }
+}
+
+/// thresholds used by Polynomial * Polynomial
+pub fn thresholds() -> Thresholds {
+    Thresholds {
+        karatsuba: KARATSUBA_THRESHOLD.load(Ordering::Relaxed),
+        kronecker: KRONECKER_THRESHOLD.load(Ordering::Relaxed),
+    }
+}
+
+pub fn set_thresholds(thresholds: Thresholds) {
+    try_set_thresholds(thresholds).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn try_set_thresholds(thresholds: Thresholds) -> Result<()> {
+    if thresholds.karatsuba < 2 {
+        return Err(Error::InvalidArgument("karatsuba threshold must be >= 2".to_string()));
+    }
+    KARATSUBA_THRESHOLD.store(thresholds.karatsuba, Ordering::Relaxed);
+    KRONECKER_THRESHOLD.store(thresholds.kronecker, Ordering::Relaxed);
+    Ok(())
+}
+
+/// product of dense polynomials, a[i] is the coefficient of x^i
+pub fn multiply(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    multiply_with(a, b, &thresholds())
+}
+
+pub fn multiply_with(a: &[BigInt], b: &[BigInt], thresholds: &Thresholds) -> Vec<BigInt> {
+    let n = a.len().min(b.len());
+    if n < thresholds.karatsuba {
+        schoolbook(a, b)
+    } else if n < thresholds.kronecker {
+        karatsuba(a, b, thresholds.karatsuba)
+    } else {
+        kronecker(a, b)
+    }
+}
+
+/// O(n m)
+pub fn schoolbook(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut c = vec![BigInt::zero(); a.len() + b.len() - 1];
+    for (i, ai) in a.iter().enumerate() {
+        if ai.is_zero() {
+            continue;
+        }
+        for (j, bj) in b.iter().enumerate() {
+            c[i + j] += ai * bj;
+        }
+    }
+    c
+}
+
+fn add_into(c: &mut [BigInt], offset: usize, d: &[BigInt]) {
+    for (i, v) in d.iter().enumerate() {
+        c[offset + i] += v;
+    }
+}
+
+fn add(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    let mut c = vec![BigInt::zero(); a.len().max(b.len())];
+    add_into(&mut c, 0, a);
+    add_into(&mut c, 0, b);
+    c
+}
+
+/// O(n^1.58), falls back to schoolbook below threshold
+pub fn karatsuba(a: &[BigInt], b: &[BigInt], threshold: usize) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if short.len() < threshold.max(2) {
+        return schoolbook(long, short);
+    }
+    let mut c = vec![BigInt::zero(); a.len() + b.len() - 1];
+    if long.len() > short.len() {
+        // unbalanced: split the longer operand into chunks of the shorter length
+        for (k, chunk) in long.chunks(short.len()).enumerate() {
+            let d = karatsuba(chunk, short, threshold);
+            add_into(&mut c, k * short.len(), &d);
+        }
+        return c;
+    }
+    let m = long.len() / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let z0 = karatsuba(a0, b0, threshold);
+    let z2 = karatsuba(a1, b1, threshold);
+    let mut z1 = karatsuba(&add(a0, a1), &add(b0, b1), threshold);
+    for (i, v) in z0.iter().enumerate() {
+        z1[i] -= v;
+    }
+    for (i, v) in z2.iter().enumerate() {
+        z1[i] -= v;
+    }
+    add_into(&mut c, 0, &z0);
+    add_into(&mut c, m, &z1);
+    add_into(&mut c, 2 * m, &z2);
+    c
+}
+
+/// Kronecker substitution
+/// evaluate at x = 2^k, multiply as integers and read the coefficients back
+pub fn kronecker(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let max_a = a.iter().map(|v| v.abs()).max().unwrap();
+    let max_b = b.iter().map(|v| v.abs()).max().unwrap();
+    let bound = max_a * max_b * BigInt::from(a.len().min(b.len()));
+    // one more bit for the sign of the coefficients
+    let k = bound.bits() + 2;
+    let pack = |v: &[BigInt]| -> BigInt {
+        let mut r = BigInt::zero();
+        for c in v.iter().rev() {
+            r <<= k;
+            r += c;
+        }
+        r
+    };
+    let mut n = pack(a) * pack(b);
+    let len = a.len() + b.len() - 1;
+    let modulus = BigInt::from(1) << k;
+    let half = BigInt::from(1) << (k - 1);
+    let mut c: Vec<BigInt> = Vec::with_capacity(len);
+    for _ in 0..len {
+        let mut r = &n % &modulus;
+        if r.sign() == Sign::Minus {
+            r += &modulus;
+        }
+        if r >= half {
+            r -= &modulus;
+        }
+        n -= &r;
+        n >>= k;
+        c.push(r);
+    }
+    c
+}
+
+/// Monomial without x
+fn rest(m: &term::Monomial) -> term::Monomial {
+    term::Monomial {
+        xpow: 0,
+        ..*m
+    }
+}
+
+/// dense x-polynomials grouped by y, q and variable
+/// rest -> (lowest x power, coefficients)
+fn split(a: &Polynomial) -> BTreeMap<term::Monomial, (i32, Vec<BigInt>)> {
+    let mut groups: BTreeMap<term::Monomial, (i32, i32)> = BTreeMap::new();
+    for m in a.terms.keys() {
+        let g = groups.entry(rest(m)).or_insert((m.xpow, m.xpow));
+        g.0 = g.0.min(m.xpow);
+        g.1 = g.1.max(m.xpow);
+    }
+    let mut dense: BTreeMap<term::Monomial, (i32, Vec<BigInt>)> = BTreeMap::new();
+    for (r, (min, max)) in &groups {
+        dense.insert(*r, (*min, vec![BigInt::zero(); (max - min + 1) as usize]));
+    }
+    for (m, coef) in &a.terms {
+        let g = dense.get_mut(&rest(m)).unwrap();
+        g.1[(m.xpow - g.0) as usize] = coef.clone();
+    }
+    dense
+}
+
+/// groups are split(a)
+fn is_dense(a: &Polynomial, groups: &BTreeMap<term::Monomial, (i32, Vec<BigInt>)>) -> bool {
+    let len: usize = groups.values().map(|g| g.1.len()).sum();
+    len <= 2 * a.terms.len()
+}
+
+/// Polynomial * Polynomial term by term
+pub fn schoolbook_polynomial(a: &Polynomial, b: &Polynomial) -> Polynomial {
+    let mut pol = Polynomial::new();
+    for (ik, iv) in &a.terms {
+        let i = term::Term::from(ik, iv);
+        for (jk, jv) in &b.terms {
+            let j = term::Term::from(jk, jv);
+            pol += i.clone() * j;
+        }
+    }
+    pol
+}
+
+/// Polynomial * Polynomial
+/// multiply x-polynomials with the dense backend when they are long and dense enough
+pub fn multiply_polynomial(a: &Polynomial, b: &Polynomial) -> Polynomial {
+    let thresholds = thresholds();
+    if a.terms.len().min(b.terms.len()) < thresholds.karatsuba
+        || (a.has_variable() && b.has_variable()) {
+        return schoolbook_polynomial(a, b);
+    }
+    let split_a = split(a);
+    let split_b = split(b);
+    if !is_dense(a, &split_a) || !is_dense(b, &split_b) {
+        return schoolbook_polynomial(a, b);
+    }
+    let mut pol = Polynomial::new();
+    for (ra, (xa, va)) in split_a.iter() {
+        for (rb, (xb, vb)) in split_b.iter() {
+            let r = term::Monomial {
+                xpow: 0,
+                ypow: ra.ypow + rb.ypow,
+                qpow: ra.qpow + rb.qpow,
+                variable: if !ra.variable.empty { ra.variable } else { rb.variable },
+            };
+            let c = multiply_with(va, vb, &thresholds);
+            for (i, coef) in c.into_iter().enumerate() {
+                if coef.is_zero() {
+                    continue;
+                }
+                let m = term::Monomial {
+                    xpow: xa + xb + i as i32,
+                    ..r
+                };
+                pol += term::Term::from(&m, &coef);
+            }
+        }
+    }
+    pol
+}
+
+/// Polynomial * Polynomial as it was before the dense backends, verbatim
+/// it keeps the terms whose coefficients cancel to zero
+#[cfg(test)]
+fn baseline_multiply(a: &Polynomial, b: &Polynomial) -> Polynomial {
+    let mut pol = Polynomial::new();
+    for (ik, iv) in &a.terms {
+        let i = term::Term::from(ik, iv);
+        for (jk, jv) in &b.terms {
+            let j = term::Term::from(jk, jv);
+            let l = i.clone() * j;
+            if let Some(lv) = pol.terms.get_mut(&l.monomial) {
+                *lv += &l.coef;
+            } else {
+                pol.terms.insert(l.monomial, l.coef);
+            }
+        }
+    }
+    pol
+}
+
+/// baseline_multiply without the zero terms, * drops them
+#[cfg(test)]
+fn reference_multiply(a: &Polynomial, b: &Polynomial) -> Polynomial {
+    let mut pol = baseline_multiply(a, b);
+    pol.terms.retain(|_, coef| !coef.is_zero());
+    pol
+}
+
+#[cfg(test)]
+fn random_vec(len: usize, bits: u32) -> Vec<BigInt> {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    (0..len).map(|_| {
+        let v: i64 = rng.gen_range(-(1i64 << bits), 1i64 << bits);
+        BigInt::from(v)
+    }).collect()
+}
+
+#[test]
+fn multiplication_backend_test() {
+    for &(la, lb) in &[(1, 1), (5, 3), (40, 40), (100, 37), (300, 280), (513, 64)] {
+        let a = random_vec(la, 40);
+        let b = random_vec(lb, 40);
+        let c = schoolbook(&a, &b);
+        assert_eq!(karatsuba(&a, &b, 4), c);
+        assert_eq!(karatsuba(&a, &b, 32), c);
+        assert_eq!(kronecker(&a, &b), c);
+        let thresholds = Thresholds { karatsuba: 8, kronecker: 64 };
+        assert_eq!(multiply_with(&a, &b, &thresholds), c);
+    }
+    assert_eq!(kronecker(&[BigInt::from(0)], &[BigInt::from(5)]), vec![BigInt::from(0)]);
+}
+
+#[test]
+fn multiplication_polynomial_test() {
+    use super::bigint::Power;
+    use super::division_polynomial;
+    use super::term_builder;
+    use super::term_builder::TermBuildable;
+    type TermBuilder = term_builder::TermBuilder;
+
+    let a = BigInt::from(2);
+    let b = BigInt::from(3);
+    let psi = division_polynomial::psi(&a, &b, 7);
+    let phi = division_polynomial::phi(&a, &b, 4);
+    assert_eq!(&psi * &phi, reference_multiply(&psi, &phi));
+
+    // long enough for the dense backend
+    let f = TermBuilder::new().xpow(3).build()
+          + TermBuilder::new().coef(-2).xpow(1).build()
+          + TermBuilder::new().coef(3).build();
+    let g = f.power(20);
+    let h = f.power(31) - TermBuilder::new().coef(5).xpow(-2).build();
+    assert!(g.terms.len() >= thresholds().karatsuba);
+    assert_eq!(&g * &h, reference_multiply(&g, &h));
+
+    let y = TermBuilder::new().ypow(1).qpow(-1).build();
+    let gy = &g * y.to_pol() + &h;
+    assert_eq!(&gy * &gy, reference_multiply(&gy, &gy));
+
+    assert_eq!(&(&g - &g) * &h, Polynomial::new());
+    assert_eq!(schoolbook_polynomial(&gy, &h), reference_multiply(&gy, &h));
+
+    // x^2 - 1, the x terms cancel
+    let p = TermBuilder::new().xpow(1).build() + TermBuilder::new().coef(1).build();
+    let m = TermBuilder::new().xpow(1).build() - TermBuilder::new().coef(1).build();
+    assert_eq!(baseline_multiply(&p, &m).terms.len(), 3);
+    assert_eq_str!(&p * &m, "x^2 - 1");
+    assert_eq!((&p * &m).terms.len(), 2);
+    assert_eq!(&p * &m, reference_multiply(&p, &m));
+}
@@ -0,0 +1,324 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use super::elliptic_curve::{ECPoint, EllipticCurve};
+use super::error::{Error, Result};
+use super::field::FieldElement;
+use super::fp::Fp;
+use super::generic_curve::{GenericCurve, GenericPoint};
+
+/// how many shifts S are tried for degenerate divisors
+const SHIFT_ATTEMPTS: usize = 32;
+
+/// smallest k <= max_k with n | p^k - 1
+///
+/// MOV reduces the ECDLP in a subgroup of order n to F_{p^k}^*,
+/// so a small k means a weak curve or a pairing-friendly one.
+pub fn embedding_degree(p: &BigInt, n: &BigInt, max_k: u32) -> Option<u32> {
+    if n <= &One::one() || !p.gcd(n).is_one() {
+        return None;
+    }
+    let q = p.mod_floor(n);
+    let mut pk = q.clone();
+    for k in 1..=max_k {
+        if pk.is_one() {
+            return Some(k);
+        }
+        pk = (pk * &q).mod_floor(n);
+    }
+    None
+}
+
+/// l(X) / v(X) where l is the line through T and R and v the vertical line through T + R,
+/// as (numerator, denominator)
+fn line<F: FieldElement>(ec: &GenericCurve<F>, t: &GenericPoint<F>, r: &GenericPoint<F>, x: &(F, F)) -> (F, F) {
+    let one = ec.a.one_like();
+    let (xt, yt) = match t {
+        GenericPoint::Affine(x, y) => (x.clone(), y.clone()),
+        GenericPoint::Infinity => return (one.clone(), one),
+    };
+    let (xr, yr) = match r {
+        GenericPoint::Affine(x, y) => (x.clone(), y.clone()),
+        GenericPoint::Infinity => return (one.clone(), one),
+    };
+    let (xq, yq) = x.clone();
+    if xt == xr && (yt.clone() + yr.clone()).is_zero_elem() {
+        // T = -R, vertical line and T + R = O
+        return (xq - xt, one);
+    }
+    let lambda = if xt == xr {
+        (ec.a.elem_like(&BigInt::from(3)) * xt.clone() * xt.clone() + ec.a.clone()) / (ec.a.elem_like(&BigInt::from(2)) * yt.clone())
+    } else {
+        (yr - yt.clone()) / (xr.clone() - xt.clone())
+    };
+    let xs = lambda.clone() * lambda.clone() - xt.clone() - xr;
+    (yq - yt - lambda * (xq.clone() - xt), xq - xs)
+}
+
+/// f_{n,P}(X) of Miller's algorithm over F_q, div(f_{n,P}) = n (P) - ([n] P) - (n - 1) (O)
+///
+/// f is normalized at O, so it is the function with divisor n (P) - n (O) when [n] P = O.
+/// Fails with DivisionByZero when X is a zero or a pole of one of the lines.
+pub fn miller_generic<F: FieldElement>(ec: &GenericCurve<F>, p: &GenericPoint<F>, x: &GenericPoint<F>, n: &BigInt) -> Result<F> {
+    let x = match x {
+        GenericPoint::Affine(x, y) => (x.clone(), y.clone()),
+        GenericPoint::Infinity => return Err(Error::InvalidArgument("evaluation at O".to_string())),
+    };
+    if n <= &Zero::zero() {
+        return Err(Error::InvalidArgument(format!("n must be positive: {}", n)));
+    }
+    let mut num = ec.a.one_like();
+    let mut den = ec.a.one_like();
+    let mut t = p.clone();
+    for bit in n.to_str_radix(2).chars().skip(1) {
+        let (l, v) = line(ec, &t, &t, &x);
+        num = num.clone() * num * l;
+        den = den.clone() * den * v;
+        t = ec.plus(&t, &t);
+        if bit == '1' {
+            let (l, v) = line(ec, &t, p, &x);
+            num = num * l;
+            den = den * v;
+            t = ec.plus(&t, p);
+        }
+    }
+    if num.is_zero_elem() {
+        return Err(Error::DivisionByZero);
+    }
+    den.checked_inverse().map(|d| num * d).map_err(|_| Error::DivisionByZero)
+}
+
+/// f_{n,P}(X) of Miller's algorithm over F_p, see miller_generic
+pub fn miller(ec: &EllipticCurve, p: &ECPoint, x: &ECPoint, n: &BigInt) -> Result<Fp> {
+    let gc = ec.to_generic();
+    miller_generic(&gc, &gc.point_from(p), &gc.point_from(x), n)
+}
+
+/// [n] P = O and P is on the curve, n | q - 1 for the values in F_q
+fn check_torsion<F: FieldElement>(ec: &GenericCurve<F>, point: &GenericPoint<F>, n: &BigInt) -> Result<()> {
+    if !ec.is_on_curve(point) {
+        return Err(Error::PointNotOnCurve);
+    }
+    if !ec.multiply_scalar(point, n).is_infinity() {
+        return Err(Error::InvalidArgument(format!("[{}] P is not O", n)));
+    }
+    if embedding_degree(&ec.field_order(), n, 1).is_none() {
+        return Err(Error::InvalidArgument(format!("n = {} does not divide q - 1", n)));
+    }
+    Ok(())
+}
+
+/// points S = (x, y) for x = 1, 2, ..., so the shifted pairings are deterministic
+fn shift_points<F: FieldElement>(ec: &GenericCurve<F>) -> impl Iterator<Item = GenericPoint<F>> + '_ {
+    (1..=4 * SHIFT_ATTEMPTS as u64)
+        .filter_map(move |x| ec.lift_x(&ec.a.elem_like(&BigInt::from(x))))
+        .take(SHIFT_ATTEMPTS)
+}
+
+/// f_{n,P}((X + S) - (S)) for the first shift S with all the values defined
+fn miller_shifted<F: FieldElement>(ec: &GenericCurve<F>, p: &GenericPoint<F>, x: &GenericPoint<F>, n: &BigInt) -> Result<F> {
+    for s in shift_points(ec) {
+        let xs = ec.plus(x, &s);
+        if xs.is_infinity() {
+            continue;
+        }
+        if let (Ok(a), Ok(b)) = (miller_generic(ec, p, &xs, n), miller_generic(ec, p, &s, n)) {
+            return Ok(a / b);
+        }
+    }
+    Err(Error::DivisionByZero)
+}
+
+/// Weil pairing e_n(P, Q) in mu_n of F_q
+///
+/// e_n(P, Q) = (-1)^n f_{n,P}(Q) / f_{n,Q}(P), or with divisors shifted by a point S
+/// when a line of the loop meets P or Q. n must divide q - 1, so for embedding degree k
+/// the curve is taken over F_{p^k} (EllipticCurve::over_extension).
+pub fn weil_pairing_generic<F: FieldElement>(ec: &GenericCurve<F>, p: &GenericPoint<F>, q: &GenericPoint<F>, n: &BigInt) -> Result<F> {
+    check_torsion(ec, p, n)?;
+    check_torsion(ec, q, n)?;
+    let one = ec.a.one_like();
+    if p.is_infinity() || q.is_infinity() || p == q {
+        return Ok(one);
+    }
+    if let (Ok(a), Ok(b)) = (miller_generic(ec, p, q, n), miller_generic(ec, q, p, n)) {
+        let sign = if n.is_odd() { -one } else { one };
+        return Ok(sign * a / b);
+    }
+    // f_{n,P}((Q + S) - (S)) / f_{n,Q}((P - S) - (-S))
+    for s in shift_points(ec) {
+        let neg_s = ec.negate(&s);
+        let qs = ec.plus(q, &s);
+        let ps = ec.plus(p, &neg_s);
+        if qs.is_infinity() || ps.is_infinity() {
+            continue;
+        }
+        let values = (miller_generic(ec, p, &qs, n), miller_generic(ec, p, &s, n),
+                      miller_generic(ec, q, &ps, n), miller_generic(ec, q, &neg_s, n));
+        if let (Ok(a), Ok(b), Ok(c), Ok(d)) = values {
+            return Ok(a / b * d / c);
+        }
+    }
+    Err(Error::DivisionByZero)
+}
+
+/// Weil pairing e_n(P, Q) in mu_n of F_p
+///
+/// The values are in F_p, so n must divide p - 1 (embedding degree 1),
+/// use weil_pairing_generic over F_{p^k} otherwise.
+pub fn weil_pairing(ec: &EllipticCurve, p: &ECPoint, q: &ECPoint, n: &BigInt) -> Result<Fp> {
+    if !ec.is_on_curve(p) || !ec.is_on_curve(q) {
+        return Err(Error::PointNotOnCurve);
+    }
+    let gc = ec.to_generic();
+    weil_pairing_generic(&gc, &gc.point_from(p), &gc.point_from(q), n)
+}
+
+/// reduced Tate pairing t_n(P, Q) = f_{n,P}(Q)^((q - 1) / n) in mu_n of F_q
+///
+/// P is in E[n] and Q is a class of E / n E. n must divide q - 1.
+pub fn tate_pairing_generic<F: FieldElement>(ec: &GenericCurve<F>, p: &GenericPoint<F>, q: &GenericPoint<F>, n: &BigInt) -> Result<F> {
+    check_torsion(ec, p, n)?;
+    if !ec.is_on_curve(q) {
+        return Err(Error::PointNotOnCurve);
+    }
+    if p.is_infinity() || q.is_infinity() {
+        return Ok(ec.a.one_like());
+    }
+    let f = match miller_generic(ec, p, q, n) {
+        Ok(f) => f,
+        Err(_) => miller_shifted(ec, p, q, n)?,
+    };
+    let e = (ec.field_order() - 1u32) / n;
+    Ok(f.power(&e))
+}
+
+/// reduced Tate pairing t_n(P, Q) = f_{n,P}(Q)^((p - 1) / n) in mu_n of F_p
+///
+/// n must divide p - 1 (embedding degree 1), use tate_pairing_generic over F_{p^k} otherwise.
+pub fn tate_pairing(ec: &EllipticCurve, p: &ECPoint, q: &ECPoint, n: &BigInt) -> Result<Fp> {
+    if !ec.is_on_curve(p) || !ec.is_on_curve(q) {
+        return Err(Error::PointNotOnCurve);
+    }
+    let gc = ec.to_generic();
+    tate_pairing_generic(&gc, &gc.point_from(p), &gc.point_from(q), n)
+}
+
+#[cfg(test)]
+fn is_nth_root_of_unity<F: FieldElement>(x: &F, n: &BigInt) -> bool {
+    x.power(n) == x.one_like()
+}
+
+#[test]
+fn embedding_degree_test() {
+    let p = BigInt::from(1_000_003);
+    assert_eq!(embedding_degree(&p, &BigInt::from(2), 10), Some(1));
+    // supersingular y^2 = x^3 - x, #E = p + 1 and n | p + 1
+    assert_eq!(embedding_degree(&p, &BigInt::from(250_001), 10), Some(2));
+    assert_eq!(embedding_degree(&p, &BigInt::from(250_307), 10), None);
+    assert_eq!(embedding_degree(&p, &p, 10), None);
+    // secp256k1 has a huge embedding degree
+    let curve = super::secp256k1::Secp256k1::new();
+    assert_eq!(embedding_degree(curve.ec.p(), &curve.n, 1000), None);
+}
+
+#[test]
+fn pairing_test() {
+    use rand::SeedableRng;
+    use super::bigint::{self, Power};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(18);
+    // n = n1 of the group structure, then E[n] is in E(F_p)
+    for (a, b, p, n1) in [(-3, 0, 1009, 14), (0, 2, 1009, 9), (0, 1, 1021, 12), (-1, 0, 1021, 10)].iter() {
+        let ec = EllipticCurve::new(&BigInt::from(*a), &BigInt::from(*b), &BigInt::from(*p));
+        let g = ec.group_structure().unwrap();
+        let n = g.n1.clone();
+        assert_eq!(n, BigInt::from(*n1));
+        // basis of E[n]
+        let e1 = g.p1.clone();
+        let e2 = ec.multiply_scalar(&g.p2, &(&g.n2 / &n));
+        let w = weil_pairing(&ec, &e1, &e2, &n).unwrap();
+        assert!(is_nth_root_of_unity(&w, &n));
+        // non-degenerate on the basis: a primitive n-th root of unity
+        for (l, _) in bigint::factorize(&n) {
+            assert!(!is_nth_root_of_unity(&w, &(&n / &l)), "{}", ec);
+        }
+        assert!(weil_pairing(&ec, &e1, &e1, &n).unwrap().is_one());
+        assert_eq!(weil_pairing(&ec, &e2, &e1, &n).unwrap(), w.inv());
+        for _ in 0..4 {
+            let i = bigint::random_below(&mut rng, &n);
+            let j = bigint::random_below(&mut rng, &n);
+            let k = bigint::random_below(&mut rng, &n);
+            let p1 = ec.plus(&ec.multiply_scalar(&e1, &i), &ec.multiply_scalar(&e2, &j));
+            let q1 = ec.multiply_scalar(&e2, &k);
+            // e(i e1 + j e2, k e2) = w^(i k)
+            assert_eq!(weil_pairing(&ec, &p1, &q1, &n).unwrap(), w.power(&(&i * &k)));
+            // linear in both arguments, also for dependent points
+            let p2 = ec.multiply_scalar(&p1, &k);
+            assert_eq!(weil_pairing(&ec, &p2, &p1, &n).unwrap(), ec.field().one());
+            let t = tate_pairing(&ec, &p1, &q1, &n).unwrap();
+            assert!(is_nth_root_of_unity(&t, &n));
+            assert_eq!(tate_pairing(&ec, &p2, &q1, &n).unwrap(), t.power(&k));
+            assert_eq!(tate_pairing(&ec, &p1, &ec.multiply_scalar(&q1, &i), &n).unwrap(), t.power(&i));
+        }
+        // Tate pairing is non-degenerate on the basis
+        let t11 = tate_pairing(&ec, &e1, &e1, &n).unwrap();
+        let t12 = tate_pairing(&ec, &e1, &e2, &n).unwrap();
+        assert!(!(t11.is_one() && t12.is_one()));
+    }
+}
+
+#[test]
+fn pairing_error_test() {
+    let ec = EllipticCurve::new(&BigInt::from(-1), &BigInt::from(0), &BigInt::from(1_000_003));
+    let point = ec.lift_x(&BigInt::from(0), false).unwrap();
+    // (0, 0) has order 2, [3] (0, 0) is not O and 3 does not divide p - 1
+    assert!(weil_pairing(&ec, &point, &point, &BigInt::from(2)).is_ok());
+    assert!(weil_pairing(&ec, &point, &point, &BigInt::from(3)).is_err());
+    let bad = ec.point(&BigInt::from(1), &BigInt::from(1));
+    assert_eq!(tate_pairing(&ec, &point, &bad, &BigInt::from(2)), Err(Error::PointNotOnCurve));
+}
+
+
+#[test]
+fn pairing_extension_field_test() {
+    use super::bigint::Power;
+    use super::dense_polynomial::DensePolynomial;
+    use super::extension_field::ExtensionField;
+    use rand::SeedableRng;
+    // supersingular y^2 = x^3 - x, p = 3 mod 4, #E = p + 1 = 1020 = 2^2 * 3 * 5 * 17
+    let p = BigInt::from(1019);
+    let n = BigInt::from(17);
+    let ec = EllipticCurve::new(&BigInt::from(-1), &BigInt::from(0), &p);
+    assert_eq!(embedding_degree(&p, &n, 10), Some(2));
+    // F_{p^2} = F_p[i] / (i^2 + 1)
+    let field = ExtensionField::try_from_dense(&DensePolynomial::new(ec.field(), vec![BigInt::from(1), BigInt::from(0), BigInt::from(1)])).unwrap();
+    let gc = ec.over_extension(&field);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(18);
+    let point = loop {
+        let point = ec.multiply_scalar(&ec.random_point(&mut rng), &(BigInt::from(1020) / &n));
+        if !point.is_infinity() {
+            break gc.point_from(&point);
+        }
+    };
+    // distortion map (x, y) -> (-x, i y) gives a point of E[n] outside E(F_p)
+    let distorted = match &point {
+        GenericPoint::Affine(x, y) => GenericPoint::Affine(-x.clone(), field.generator() * y),
+        GenericPoint::Infinity => unreachable!(),
+    };
+    assert!(gc.is_on_curve(&distorted));
+    let w = weil_pairing_generic(&gc, &point, &distorted, &n).unwrap();
+    assert!(is_nth_root_of_unity(&w, &n));
+    assert!(!w.is_one());
+    assert_eq!(weil_pairing_generic(&gc, &distorted, &point, &n).unwrap(), w.inv());
+    let (i, j) = (BigInt::from(3), BigInt::from(5));
+    let e = weil_pairing_generic(&gc, &gc.multiply_scalar(&point, &i), &gc.multiply_scalar(&distorted, &j), &n).unwrap();
+    assert_eq!(e, w.power(&(&i * &j)));
+    let t = tate_pairing_generic(&gc, &point, &distorted, &n).unwrap();
+    assert!(is_nth_root_of_unity(&t, &n));
+    assert!(!t.is_one());
+    assert_eq!(tate_pairing_generic(&gc, &gc.multiply_scalar(&point, &i), &distorted, &n).unwrap(), t.power(&i));
+    // over F_p the values are in mu_n of F_p, which is trivial for k = 2
+    let q = ec.multiply_scalar(&ec.random_point(&mut rng), &(BigInt::from(1020) / &n));
+    assert!(weil_pairing(&ec, &q, &q, &n).is_err());
+    assert!(tate_pairing(&ec, &q, &q, &n).is_err());
+}
@@ -4,7 +4,11 @@ use num_traits::{Zero, One};
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::{fmt, ops};
-use super::bigint::{Inverse, Power};
+use super::bigint::{self, Power};
+use super::error::{Error, Result};
+use super::fp;
+use super::dense_polynomial;
+use super::multiplication;
 use super::term;
 use super::term_builder::TermBuildable;
 use super::term_builder;
@@ -12,6 +16,8 @@ use super::polynomial;
 use super::subscripted_variable;
 
 /// Polynomial
+/// coefficients are integers, e.g. for division and modular polynomials over Z
+/// the methods taking p reduce them, DensePolynomial is the polynomial type over F_p
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Polynomial {
     pub terms: BTreeMap<term::Monomial, BigInt>
@@ -109,20 +115,7 @@ impl_op_ex!(- |a: &Polynomial| -> Polynomial {
 
 // Polynomial * Polynomial
 impl_op_ex!(* |a: &Polynomial, b: &Polynomial| -> Polynomial {
-    let mut pol = Polynomial::new();
-    for (ik, iv) in &a.terms {
-        let i = term::Term::from(ik, iv);
-        for (jk, jv) in &b.terms {
-            let j = term::Term::from(jk, jv);
-            let l = i.clone() * j;
-            if let Some(lv) = pol.terms.get_mut(&l.monomial) {
-                *lv += &l.coef;
-            } else {
-                pol.terms.insert(l.monomial, l.coef);
-            }
-        }
-    }
-    pol
+    multiplication::multiply_polynomial(a, b)
 });
 
 // Polynomial * Term
@@ -144,22 +137,7 @@ impl_op_ex!(*= |a: &mut Polynomial, b: &Polynomial| {
 
 // Polynomial / Polynomial
 impl_op_ex!(/ |a: &Polynomial, b: &Polynomial| -> Polynomial {
-    if b.is_zero() {
-        panic!("b.is_zero()");
-    }
-    else if b.terms.len() == 1 {
-        let mut pol = Polynomial::new();
-        for (ak, av) in &a.terms {
-            let i = term::Term::from(ak, av);
-            let (m, coef) = b.terms.iter().next().unwrap();
-            let u2 = term::Term::from(m, coef); 
-            let u = i / &u2;
-            pol.terms.insert(u.monomial, u.coef);
-        }
-        pol
-    } else {
-        panic!("b.terms.len() >= 2");
-    }
+    a.checked_div(b).unwrap_or_else(|e| panic!("{}", e))
 });
 
 // Polynomial / Term
@@ -216,43 +194,13 @@ impl Power<BigInt> for Polynomial {
 }
 impl<'a> Power<&'a BigInt> for Polynomial {
     fn power(&self, n: &BigInt) -> Self {
-        assert!(n >= &Zero::zero(), "n:{}", n.to_string());
-        if n.is_zero() {
-            return One::one();
-        }
-        let mut e = n.clone();
-        let mut b = self.clone();
-        let mut r: Polynomial = One::one();
-        while e > One::one() {
-            if e.is_odd() {
-                r *= b.clone();
-            }
-            let f = b.clone() * b.clone();
-            b = f;
-            e /= 2;
-        } 
-        r * b
+        self.checked_power(n).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
 impl Power<i32> for Polynomial {
     fn power(&self, n: i32) -> Self {
-        assert!(n >= 0, "n:{}", n.to_string());
-        if n.is_zero() {
-            return One::one();
-        }
-        let mut e = n;
-        let mut b = self.clone();
-        let mut r: Polynomial = One::one();
-        while e > One::one() {
-            if e.is_odd() {
-                r *= b.clone();
-            }
-            let f = b.clone() * b.clone();
-            b = f;
-            e /= 2;
-        } 
-        r * b
+        self.power(&BigInt::from(n))
     }
 }
 
@@ -311,6 +259,54 @@ impl Polynomial {
         }
     }
 
+    /// division by a single term
+    pub fn checked_div(&self, other: &Polynomial) -> Result<Self> {
+        if other.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if other.terms.len() >= 2 {
+            return Err(Error::NonMonomialDivisor);
+        }
+        let (m, coef) = other.terms.iter().next().unwrap();
+        let u2 = term::Term::from(m, coef);
+        let mut pol = Polynomial::new();
+        for (ak, av) in &self.terms {
+            let u = term::Term::from(ak, av).checked_div(&u2)?;
+            pol.terms.insert(u.monomial, u.coef);
+        }
+        Ok(pol)
+    }
+
+    /// Polynomial * Polynomial
+    /// both can't have subscripted variables
+    pub fn checked_mul(&self, other: &Polynomial) -> Result<Self> {
+        if self.has_variable() && other.has_variable() {
+            return Err(Error::VariableProduct);
+        }
+        Ok(self * other)
+    }
+
+    /// self ^ n, n >= 0
+    pub fn checked_power(&self, n: &BigInt) -> Result<Self> {
+        if n < &Zero::zero() {
+            return Err(Error::NegativeExponent);
+        }
+        if n.is_zero() {
+            return Ok(One::one());
+        }
+        let mut e = n.clone();
+        let mut b = self.clone();
+        let mut r: Polynomial = One::one();
+        while e > One::one() {
+            if e.is_odd() {
+                r = r.checked_mul(&b)?;
+            }
+            b = b.checked_mul(&b)?;
+            e /= 2;
+        }
+        r.checked_mul(&b)
+    }
+
     pub fn square(&self) -> Self {
         self.power(2)
     }
@@ -320,22 +316,63 @@ impl Polynomial {
     }
 
     pub fn power_modulo(&self, n: &BigInt, p: &BigInt) -> Self {
-        assert!(*n >= Zero::zero());
+        self.checked_power_modulo(n, p).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// self ^ n with coefficients reduced mod p
+    pub fn checked_power_modulo(&self, n: &BigInt, p: &BigInt) -> Result<Self> {
+        if n < &Zero::zero() {
+            return Err(Error::NegativeExponent);
+        }
+        fp::PrimeField::try_new(p)?;
         let mut b = self % p;
         let mut r: Polynomial = One::one();
         let mut e = n.clone();
         while &e > &One::one() {
             if e.is_odd() {
-                r *= &b;
+                r = r.checked_mul(&b)?;
                 r.modular_assign(p);
             }
-            b *= b.clone();
+            b = b.checked_mul(&b)?;
             b.modular_assign(p);
             e /= 2;
         }
-        r *= b;
+        if !e.is_zero() {
+            r = r.checked_mul(&b)?;
+        }
         r.modular_assign(p);
-        r
+        Ok(r)
+    }
+
+    /// self^n mod (modulus, p)
+    /// square-and-multiply reducing by modulus at each step
+    pub fn power_mod_poly(&self, n: &BigInt, modulus: &Polynomial, p: &BigInt) -> Self {
+        self.checked_power_mod_poly(n, modulus, p).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_power_mod_poly(&self, n: &BigInt, modulus: &Polynomial, p: &BigInt) -> Result<Self> {
+        if *n < Zero::zero() {
+            return Err(Error::NegativeExponent);
+        }
+        let field = fp::PrimeField::try_new(p)?;
+        if self.is_univariate_x() && modulus.is_univariate_x() {
+            let a = dense_polynomial::DensePolynomial::from_polynomial(self, &field);
+            let m = dense_polynomial::DensePolynomial::from_polynomial(modulus, &field);
+            return Ok(a.checked_powmod(n, &m)?.to_polynomial());
+        }
+        let mut b = self.checked_polynomial_modular(modulus, p)?;
+        let mut r = Polynomial::one().polynomial_modular(modulus, p);
+        let mut e = n.clone();
+        while !e.is_zero() {
+            if e.is_odd() {
+                r = r.checked_mul(&b)?.polynomial_modular(modulus, p);
+            }
+            e >>= 1;
+            if !e.is_zero() {
+                b = b.checked_mul(&b)?.polynomial_modular(modulus, p);
+            }
+        }
+        Ok(r)
     }
 
     pub fn power_omit_high_order_q(&self, n: i32, order: i32) -> Self {
@@ -350,57 +387,61 @@ impl Polynomial {
     }
 
     pub fn polynomial_modular(&self, other: &Polynomial, p: &BigInt) -> Self {
-        assert!(!other.has_y(), "!other.has_y()");
-        assert!(!other.has_q(), "!other.has_q()");
-        let oh = other.highest_term_x();
+        self.checked_polynomial_modular(other, p).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// self mod (other, p)
+    /// other must have only x terms
+    pub fn checked_polynomial_modular(&self, other: &Polynomial, p: &BigInt) -> Result<Self> {
+        if other.has_y() || other.has_q() {
+            return Err(Error::NotUnivariate);
+        }
+        let field = fp::PrimeField::try_new(p)?;
+        let mut o = other.clone();
+        o.modular_assign(p);
+        if o.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        if self.is_univariate_x() && other.is_univariate_x() {
+            let a = dense_polynomial::DensePolynomial::from_polynomial(self, &field);
+            let b = dense_polynomial::DensePolynomial::from_polynomial(other, &field);
+            return Ok((a % b).to_polynomial());
+        }
+        let oh = o.highest_term_x();
+        let oh_inv = field.elem(oh.coef.clone()).inv();
         let mut r = self.clone();
+        r.modular_assign(p);
         loop {
             let rh = r.highest_term_x();
-            if rh.xpow() < oh.xpow() {
+            if r.is_zero() || rh.xpow() < oh.xpow() {
                 break;
             }
-            let mut q = term_builder::TermBuilder::new()
-                    .coef(&rh.coef * oh.coef.inverse(p))
+            let c = field.elem(rh.coef.clone()) * &oh_inv;
+            let q = term_builder::TermBuilder::new()
+                    .coef(c.value())
                     .xpow(rh.xpow() - oh.xpow())
                     .ypow(rh.ypow())
                     .qpow(rh.qpow())
                     .build();
-            q.modular_assign(&p);
-            let mut d = q * other;
-
-            d.modular_assign(&p);
-            r -= d;
-            r.modular_assign(&p);
+            r -= q * &o;
+            r.modular_assign(p);
         }
-        r.modular_assign(&p);
-        r
+        Ok(r)
     }
 
+    /// reduce coefficients into F_p
     pub fn modular_assign(&mut self, p: &BigInt) {
+        let field = fp::PrimeField::new(p);
         let mut del: BTreeSet<term::Monomial> = BTreeSet::new();
         for (m, coef) in &mut self.terms {
-            let c = coef.mod_floor(p);
-            if p != &Zero::zero() {
-                assert!(&c < p, "c {}", &c);
-            }
-            *coef = c.clone();
+            let c = field.elem(coef.clone());
             if c.is_zero() {
-                del.insert(m.clone());
+                del.insert(*m);
             }
+            *coef = c.to_bigint();
         }
-
-        // remove
         for m in &del {
-            if let Some(_v) = self.terms.get_mut(&m) {
-                self.terms.remove(m);
-            }
-        }
-
-        // check
-        for (_, coef) in &self.terms {
-            if p != &Zero::zero() {
-                assert!(coef < p, "{} {}", &coef, self.to_string());
-            }
+            self.terms.remove(m);
         }
     }
 
@@ -442,6 +483,12 @@ impl Polynomial {
     }
 
     pub fn gcd(&self, other: &Self, p: &BigInt) -> Polynomial {
+        if self.is_univariate_x() && other.is_univariate_x() {
+            let field = fp::PrimeField::new(p);
+            let a = dense_polynomial::DensePolynomial::from_polynomial(self, &field);
+            let b = dense_polynomial::DensePolynomial::from_polynomial(other, &field);
+            return a.gcd(&b).to_polynomial();
+        }
         let s = self.highest_term_x();
         let o = other.highest_term_x();
         if s.xpow() < o.xpow() {
@@ -454,17 +501,59 @@ impl Polynomial {
         return other.gcd(&r, p);
     }
 
+    /// roots in F_p with multiplicity in ascending order
+    /// self must have only x terms
+    pub fn roots(&self, p: &BigInt) -> Vec<BigInt> {
+        self.checked_roots(p).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_roots(&self, p: &BigInt) -> Result<Vec<BigInt>> {
+        let field = fp::PrimeField::try_new(p)?;
+        dense_polynomial::DensePolynomial::try_from_polynomial(self, &field)?.checked_roots()
+    }
+
+    /// monic irreducible factors mod p with multiplicity in ascending order of degree
+    /// self must have only x terms, the leading coefficient is dropped
+    pub fn factorize(&self, p: &BigInt) -> Vec<(Polynomial, u32)> {
+        self.checked_factorize(p).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_factorize(&self, p: &BigInt) -> Result<Vec<(Polynomial, u32)>> {
+        let field = fp::PrimeField::try_new(p)?;
+        let factors = dense_polynomial::DensePolynomial::try_from_polynomial(self, &field)?.checked_factorize()?;
+        Ok(factors.into_iter().map(|(f, i)| (f.to_polynomial(), i)).collect())
+    }
+
+    /// irreducible over F_p
+    pub fn is_irreducible(&self, p: &BigInt) -> bool {
+        self.checked_is_irreducible(p).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_is_irreducible(&self, p: &BigInt) -> Result<bool> {
+        let field = fp::PrimeField::try_new(p)?;
+        let f = dense_polynomial::DensePolynomial::try_from_polynomial(self, &field)?;
+        if !bigint::is_probable_prime(p) {
+            return Err(Error::NotPrime(p.clone()));
+        }
+        Ok(f.is_irreducible())
+    }
+
     pub fn to_monic(&self, p: &BigInt) -> Polynomial {
         if self.is_zero() {
             return self.clone();
         }
         let s = self.highest_term_x();
-        let inv = s.coef.inverse(p);
-        let mut pol = self * term_builder::TermBuilder::new().coef(&inv).build();
+        let inv = fp::PrimeField::new(p).elem(s.coef).inv();
+        let mut pol = self * term_builder::TermBuilder::new().coef(inv.value()).build();
         pol.modular_assign(p);
         pol
     }
 
+    /// has only x^n (n >= 0) terms
+    pub fn is_univariate_x(&self) -> bool {
+        self.terms.keys().all(|m| m.xpow >= 0 && m.ypow == 0 && m.qpow == 0 && m.variable.empty)
+    }
+
     pub fn has_x(&self) -> bool {
         for (m, coef) in &self.terms {
             let i = term::Term::from(m, coef);
@@ -485,6 +574,11 @@ impl Polynomial {
         false
     }
 
+    /// has a term with a subscripted variable
+    pub fn has_variable(&self) -> bool {
+        self.terms.keys().any(|m| !m.variable.empty)
+    }
+
     pub fn has_q(&self) -> bool {
         for (m, coef) in &self.terms {
             let i = term::Term::from(m, coef);
@@ -627,37 +721,37 @@ impl Polynomial {
     }
 
     pub fn to_scalar(&self) -> BigInt {
-        if self.terms.len() == 0 {
-            return Zero::zero();
+        self.try_to_scalar().unwrap_or_else(|e| panic!("{}: {}", e, self))
+    }
+
+    pub fn try_to_scalar(&self) -> Result<BigInt> {
+        if self.terms.is_empty() {
+            return Ok(Zero::zero());
         }
         if self.terms.len() >= 2 {
-            panic!();
+            return Err(Error::NotScalar);
         }
-        for (m, coef) in &self.terms {
-            if !m.xpow.is_zero() {
-                panic!();
-            }
-            if !m.ypow.is_zero() {
-                panic!();
-            }
-            if !m.qpow.is_zero() {
-                panic!();
-            }
-            return coef.clone();
+        let (m, coef) = self.terms.iter().next().unwrap();
+        if !m.xpow.is_zero() || !m.ypow.is_zero() || !m.qpow.is_zero() {
+            return Err(Error::NotScalar);
         }
-        panic!();
+        Ok(coef.clone())
     }
 
     /// get x degree
     /// assert if y equal not zero or q equal not zero
     pub fn degree_x(&self) -> i32 {
-        assert!(!self.has_y());
-        assert!(!self.has_q());
+        self.try_degree_x().unwrap_or_else(|e| panic!("{}: {}", e, self))
+    }
+
+    pub fn try_degree_x(&self) -> Result<i32> {
+        if self.has_y() || self.has_q() {
+            return Err(Error::NotUnivariate);
+        }
         if self.is_zero() {
-            return Zero::zero();
+            return Ok(Zero::zero());
         }
-        let s = self.highest_term_x();
-        s.xpow()
+        Ok(self.highest_term_x().xpow())
     }
 
     /// omit O(order+1) for q
@@ -1043,3 +1137,85 @@ fn derivative_test() {
     assert_eq_str!(p.derivative_y(), "12 x^6 y^2 q^3 + 4 x^5 y");
 }
 
+
+#[test]
+fn power_mod_poly_test() {
+    use super::term_builder;
+    type TermBuilder = term_builder::TermBuilder;
+    let p = BigInt::from(1_000_003);
+    let f = TermBuilder::new().xpow(3).build()
+          + TermBuilder::new().coef(2).xpow(1).build()
+          + TermBuilder::new().coef(1).build();
+    let x = TermBuilder::new().xpow(1).build().to_pol();
+    // 2^255 - 19
+    let n = (BigInt::from(1) << 255) - BigInt::from(19);
+    assert_eq_str!(x.power_mod_poly(&n, &f, &p), "279101 x^2 + 806774 x + 928446");
+    assert_eq_str!(x.power_mod_poly(&BigInt::from(0), &f, &p), "1");
+
+    // with y
+    let g = TermBuilder::new().xpow(2).build()
+          + TermBuilder::new().coef(3).ypow(1).build();
+    let expected = g.power(7).polynomial_modular(&f, &p);
+    assert_eq!(g.power_mod_poly(&BigInt::from(7), &f, &p), expected);
+}
+
+#[test]
+fn polynomial_checked_test() {
+    use super::term_builder;
+    type TermBuilder = term_builder::TermBuilder;
+    let p = TermBuilder::new().coef(4).xpow(2).build()
+          + TermBuilder::new().coef(2).ypow(1).build();
+    let q = TermBuilder::new().coef(2).xpow(1).build() + TermBuilder::new().coef(1).build();
+    assert_eq!(p.checked_div(&q), Err(Error::NonMonomialDivisor));
+    assert_eq!(p.checked_div(&Polynomial::new()), Err(Error::DivisionByZero));
+    assert_eq_str!(p.checked_div(&TermBuilder::new().coef(2).build().to_pol()).unwrap(), "2 x^2 + y");
+    assert_eq!(p.try_to_scalar(), Err(Error::NotScalar));
+    assert_eq!(TermBuilder::new().coef(5).build().to_pol().try_to_scalar(), Ok(BigInt::from(5)));
+    assert_eq!(p.try_degree_x(), Err(Error::NotUnivariate));
+    assert_eq!(q.checked_polynomial_modular(&p, &BigInt::from(7)), Err(Error::NotUnivariate));
+    assert_eq!(q.checked_power_mod_poly(&BigInt::from(-1), &q, &BigInt::from(7)), Err(Error::NegativeExponent));
+    assert_eq!(q.checked_power(&BigInt::from(-1)), Err(Error::NegativeExponent));
+    assert_eq!(q.checked_power_modulo(&BigInt::from(-1), &BigInt::from(7)), Err(Error::NegativeExponent));
+    assert_eq!(q.checked_power_modulo(&BigInt::from(2), &BigInt::from(1)), Err(Error::InvalidModulus(BigInt::from(1))));
+    assert_eq_str!(q.checked_power(&BigInt::from(3)).unwrap(), "8 x^3 + 12 x^2 + 6 x + 1");
+    assert_eq_str!(q.checked_power_modulo(&BigInt::from(3), &BigInt::from(7)).unwrap(), "x^3 + 5 x^2 + 6 x + 1");
+    assert_eq!(q.checked_power(&Zero::zero()), Ok(One::one()));
+    // subscripted variables can't be multiplied together
+    let c = TermBuilder::new().coef(3).variable_ij(1, 2).build().to_pol() + q.clone();
+    assert_eq!(c.checked_mul(&c), Err(Error::VariableProduct));
+    assert_eq!(c.checked_power(&BigInt::from(2)), Err(Error::VariableProduct));
+    assert_eq!(c.checked_power_modulo(&BigInt::from(2), &BigInt::from(7)), Err(Error::VariableProduct));
+    assert_eq!(c.checked_power(&BigInt::from(1)), Ok(c.clone()));
+    assert_eq!(c.checked_mul(&q), Ok(&c * &q));
+}
+
+#[test]
+fn polynomial_roots_test() {
+    use super::term_builder;
+    type TermBuilder = term_builder::TermBuilder;
+    // x^3 - x^2 - 8 x + 12 = (x - 2)^2 (x + 3)
+    let p = TermBuilder::new().xpow(3).build()
+          - TermBuilder::new().xpow(2).build()
+          - TermBuilder::new().coef(8).xpow(1).build()
+          + TermBuilder::new().coef(12).build();
+    assert_eq!(p.roots(&BigInt::from(11)), vec![BigInt::from(2), BigInt::from(2), BigInt::from(8)]);
+    let y = TermBuilder::new().ypow(1).build().to_pol();
+    assert_eq!(y.checked_roots(&BigInt::from(11)), Err(Error::NotUnivariate));
+    assert_eq!(p.checked_roots(&BigInt::from(1)), Err(Error::InvalidModulus(BigInt::from(1))));
+}
+
+#[test]
+fn polynomial_factorize_test() {
+    use super::term_builder;
+    type TermBuilder = term_builder::TermBuilder;
+    // 2 x^4 - 2 = 2 (x - 1) (x + 1) (x^2 + 1) mod 7
+    let p = TermBuilder::new().coef(2).xpow(4).build() + TermBuilder::new().coef(-2).build();
+    let seven = BigInt::from(7);
+    let factors: Vec<String> = p.factorize(&seven).iter().map(|(f, i)| format!("({})^{}", f, i)).collect();
+    assert_eq!(factors.join(" "), "(x + 1)^1 (x + 6)^1 (x^2 + 1)^1");
+    assert!(!p.is_irreducible(&seven));
+    let q = TermBuilder::new().xpow(2).build() + TermBuilder::new().coef(1).build();
+    assert!(q.is_irreducible(&seven));
+    assert!(!q.is_irreducible(&BigInt::from(5)));
+    assert_eq!(q.checked_is_irreducible(&BigInt::from(9)), Err(Error::NotPrime(BigInt::from(9))));
+}
@@ -1,247 +1,248 @@
-extern crate num_bigint;
-extern crate num_traits;
-extern crate num_iter;
-use num_traits::{Zero, One, ToPrimitive};
-use num_integer::Integer;
 use num_bigint::BigInt;
-use super::polynomial;
-use super::term_builder;
-use super::term_builder::TermBuildable;
+use num_integer::Integer;
+use num_traits::{Zero, One, ToPrimitive};
+use super::dense_polynomial;
 use super::division_polynomial;
+use super::error::{self, Error};
+use super::fp;
 use crate::bigint;
-use crate::bigint::{Power};
-
-type TermBuilder = term_builder::TermBuilder;
-type Polynomial = polynomial::Polynomial;
-
-/// Calculate the order of the Elliptic curve y^2 = x^3 + a x + b
-/// schoof algorithm
-pub fn schoof(a: &BigInt, b: &BigInt, q: i32) -> Vec<bigint::ModResult> {
-    let qq = BigInt::from(q);
-    let mut mod_result: Vec<bigint::ModResult> = Vec::new();
-    // l = 2
-    let l: BigInt = 2.into();
-    println!("{} l:{}", line!(), l);
-    let pol_l2 = TermBuilder::new().xpow(q).build() - TermBuilder::new().xpow(1).build();
-    let pol_standard = TermBuilder::new().xpow(3).build() + 
-                TermBuilder::new().coef(a).xpow(1).build() +
-                TermBuilder::new().coef(b).build();
-    let j2: BigInt;
-    if pol_standard.is_gcd_one(&pol_l2, &qq) {
-        // no common root
-        j2 = One::one();
-    } else {
-        // has common root
-        j2 = Zero::zero();
-    }
-    mod_result.push(bigint::ModResult { l: l.clone(), r: j2.clone() });
-    println!("a = {} mod 2", j2); 
-
-    // l >= 3
-    // TODO:
-    for l in vec![3i32, 5i32] {
-        println!("{} l:{}", line!(), l);
-        let ql: i32 = q.mod_floor(&l).to_i32().unwrap();
-        if l >= q {
-            break;
-        }
-        println!("q:{} l:{} ql:{}", q, l, ql);
-        let jmax: i32 = (l-1) / 2;
-        println!("j:[1, {}]", jmax);
-
-        // (b) x'
-        let nn1 = division_polynomial::omega(a, b, ql);
+use crate::bigint::Power;
 
-        let nn1 = nn1.reduction_modular(a, b, q);
+type DensePolynomial = dense_polynomial::DensePolynomial;
 
-        let nn2 = TermBuilder::new().ypow(q.pow(2)).build()
-                * division_polynomial::psi(a, b, ql).power(3);
-
-        let nn2 = nn2.reduction_modular(a, b, q);
-
-        let mut n1 = (nn1 - nn2).power(2);
-
-        n1.modular_assign(&qq);
-
-        let n2 = - (division_polynomial::phi(a, b, ql)
-                     + TermBuilder::new().xpow(q.pow(2)).build()
-                       * division_polynomial::psi(a, b, ql).power(2));
-
-
-        let n3 = (division_polynomial::phi(a, b, ql)
-                     - TermBuilder::new().xpow(q.pow(2)).build()
-                       * division_polynomial::psi(a, b, ql).power(2)).power(2);
-
-        let mut num1 = n1 + n2 * n3;
-        num1.modular_assign(&qq);
-        let num1 = num1.reduction_modular(a, b, q);
-
-        let den1 = (division_polynomial::psi(a, b, ql).power(2))
-                 * ((division_polynomial::phi(a, b, ql) - 
-                        TermBuilder::new().xpow(q.pow(2)).build()
-                   * division_polynomial::psi(a, b, ql).power(2)).power(2));
-        
-        let den1 = den1.reduction_modular(a, b, q);
+/// (X(x), y Y(x)) in F_p[x, y] / (h(x), y^2 - x^3 - a x - b)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum RingPoint {
+    Infinity,
+    Affine(DensePolynomial, DensePolynomial),
+}
 
-        let psi_l = &division_polynomial::psi(a, b, l) % q;
-        println!("{} psi({}):{}", line!(), l, psi_l);
+/// F_p[x] / (h) with h a factor of a division polynomial
+/// Err(g) means g is a nontrivial factor of h found during an inversion.
+pub(crate) struct Ring {
+    pub(crate) h: DensePolynomial,
+    /// x^3 + a x + b mod h
+    pub(crate) e: DensePolynomial,
+    a: fp::Fp,
+}
 
-        let mut found = false;
-        let mut jj: i32 = Zero::zero();
-        for j in num_iter::range(1, jmax + 1) {
-            println!("{} j:{}", line!(), j);
-            // x
-            let num2 = division_polynomial::phi(a, b, j).to_frob(q);
-            let num2 = num2.reduction_modular(a, b, q);
+impl Ring {
+    pub(crate) fn new(h: &DensePolynomial, a: &fp::Fp, b: &fp::Fp) -> Self {
+        let field = h.field();
+        let e = DensePolynomial::new(field, vec![b.to_bigint(), a.to_bigint(), Zero::zero(), One::one()]);
+        Ring {
+            h: h.clone(),
+            e: e % h,
+            a: a.clone(),
+        }
+    }
 
-            let den2 = division_polynomial::psi(a, b, j).to_frob(q).power(2);
+    pub(crate) fn mul(&self, u: &DensePolynomial, v: &DensePolynomial) -> DensePolynomial {
+        (u * v) % &self.h
+    }
 
-            let mut p1 = &num1 * &den2 - &num2 * &den1;
-            p1.modular_assign(&qq);
+    pub(crate) fn inv(&self, u: &DensePolynomial) -> Result<DensePolynomial, DensePolynomial> {
+        let (g, s, _) = u.xgcd(&self.h);
+        if g.is_one() {
+            Ok(s % &self.h)
+        } else {
+            Err(g)
+        }
+    }
 
-            let p1 = p1.reduction_modular(a, b, q);
+    pub(crate) fn negate(&self, p: &RingPoint) -> RingPoint {
+        match p {
+            RingPoint::Infinity => RingPoint::Infinity,
+            RingPoint::Affine(x, y) => RingPoint::Affine(x.clone(), -y),
+        }
+    }
 
-            let mut p1 = p1.polynomial_modular(&psi_l, &qq);
-            p1.modular_assign(&qq);
-            println!("{} pol % psi({}):{}", line!(), l, p1);
+    /// (x, y)
+    pub(crate) fn generic_point(&self) -> RingPoint {
+        let field = self.h.field();
+        RingPoint::Affine(DensePolynomial::x(field) % &self.h, DensePolynomial::one(field) % &self.h)
+    }
 
-            if p1.is_zero() {
-                // to iii.
-                found = true;
-                jj = j;
-                break;
+    pub(crate) fn add(&self, p1: &RingPoint, p2: &RingPoint) -> Result<RingPoint, DensePolynomial> {
+        let (x1, y1, x2, y2) = match (p1, p2) {
+            (RingPoint::Infinity, _) => return Ok(p2.clone()),
+            (_, RingPoint::Infinity) => return Ok(p1.clone()),
+            (RingPoint::Affine(x1, y1), RingPoint::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        if x1 == x2 {
+            if y1 == y2 {
+                return self.double(p1);
+            }
+            if (y1 + y2).is_zero() {
+                return Ok(RingPoint::Infinity);
             }
+            // equal on some roots of h and opposite on the others
+            return Err((y1 - y2).gcd(&self.h));
         }
-        if found {
-            // iii
-            println!("x found");
-            // y
-            let g = TermBuilder::new().ypow(q.pow(2)).build().to_pol();
-            let g = g.reduction_modular(a, b, q);
-
-            let mut omg = division_polynomial::omega(a, b, ql);
-            omg.modular_assign(&qq);
-
-            let d = omg - &g * division_polynomial::psi(a, b, ql).power(3);
-            let d = d.reduction_modular(a, b, q);
-
-            let mut e = - division_polynomial::phi(a, b, ql) +
-                    TermBuilder::new().coef(2).xpow(q.pow(2)).build()
-                    * division_polynomial::psi(a, b, ql).power(2);
-            e.modular_assign(&qq);
-
-            let e = e.reduction_modular(a, b, q);
-
-            let mut f = division_polynomial::phi(a, b, ql) - 
-                TermBuilder::new().xpow(q.pow(2)).build() * division_polynomial::psi(a, b, ql).power(2);
-            f.modular_assign(&qq);
-
-            let f = f.reduction_modular(a, b, q);
-
-            let num3 = &d * (e * f.power(2) - d.power(2)) - &g * division_polynomial::psi(a, b, ql).power(3) * f.power(3);
-            let mut num3 = num3.reduction_modular(a, b, q);
-
-            num3 *= division_polynomial::psi(a, b, ql);
-            let num3 = num3.reduction_modular(a, b, q);
-
-            let den3 = division_polynomial::psi(a, b, ql).power(3) * f.power(3) * division_polynomial::psi(a, b, ql);
-            let den3 = den3.reduction_modular(a, b, q);
-
-            let num4 = division_polynomial::omega(a, b, jj).to_frob(q);
-            let num4 = num4.reduction_modular(a, b, q);
-
-            let den4 = division_polynomial::psi(a, b, jj).to_frob(q).power(3);
-            let den4 = den4.reduction_modular(a, b, q);
-
-            let mut p7 = num3 * den4 - num4 * den3;
-            p7.modular_assign(&qq);
+        // lambda = y l
+        let l = self.mul(&(y2 - y1), &self.inv(&(x2 - x1))?);
+        let x3 = self.mul(&self.e, &self.mul(&l, &l)) - x1 - x2;
+        let y3 = self.mul(&l, &(x1 - &x3)) - y1;
+        Ok(RingPoint::Affine(x3, y3))
+    }
 
-            let p8 = p7.reduction_modular(a, b, q);
+    pub(crate) fn double(&self, p: &RingPoint) -> Result<RingPoint, DensePolynomial> {
+        let (x, y) = match p {
+            RingPoint::Infinity => return Ok(RingPoint::Infinity),
+            RingPoint::Affine(x, y) => (x, y),
+        };
+        if y.is_zero() {
+            return Ok(RingPoint::Infinity);
+        }
+        let field = self.h.field();
+        let c = |v: i32| DensePolynomial::constant(&field.elem(v));
+        // lambda = (3 x^2 + a) / (2 y) = y (3 x^2 + a) / (2 e y)
+        let num = &c(3) * self.mul(x, x) + DensePolynomial::constant(&self.a);
+        let den = &c(2) * self.mul(&self.e, y);
+        let l = self.mul(&num, &self.inv(&den)?);
+        let x3 = self.mul(&self.e, &self.mul(&l, &l)) - &c(2) * x;
+        let y3 = self.mul(&l, &(x - &x3)) - y;
+        Ok(RingPoint::Affine(x3, y3))
+    }
 
-            let mut p9: Polynomial;
-            if p8.has_y() {
-                p9 = p8 / TermBuilder::new().ypow(1).build();
-            } else {
-                p9 = p8.clone();
+    pub(crate) fn multiply_scalar(&self, p: &RingPoint, n: i64) -> Result<RingPoint, DensePolynomial> {
+        if n < 0 {
+            return Ok(self.negate(&self.multiply_scalar(p, -n)?));
+        }
+        let mut r = RingPoint::Infinity;
+        for i in (0..64 - n.leading_zeros()).rev() {
+            r = self.double(&r)?;
+            if (n >> i) & 1 == 1 {
+                r = self.add(&r, p)?;
             }
+        }
+        Ok(r)
+    }
 
-            p9.modular_assign(&qq);
+    /// trace of Frobenius mod l
+    /// pi^2 + [p] = [t] pi on the points killed by h
+    /// None if no t satisfies it, which means h is not a factor of the l-division polynomial
+    fn trace(&self, l: i64, p: &BigInt) -> Result<Option<i64>, DensePolynomial> {
+        let x = DensePolynomial::x(self.h.field());
+        let xp = x.powmod(p, &self.h);
+        let yp = self.e.powmod(&((p - 1) / 2), &self.h);
+        let xp2 = xp.powmod(p, &self.h);
+        let yp2 = yp.powmod(&(p + 1), &self.h);
+        let pi = RingPoint::Affine(xp, yp);
+        let pi2 = RingPoint::Affine(xp2, yp2);
+
+        let pl = p.mod_floor(&BigInt::from(l)).to_i64().unwrap();
+        let s = self.add(&pi2, &self.multiply_scalar(&self.generic_point(), pl)?)?;
+        if s == RingPoint::Infinity {
+            return Ok(Some(0));
+        }
+        let mut r = pi.clone();
+        for c in 1..l {
+            if r == s {
+                return Ok(Some(c));
+            }
+            r = self.add(&r, &pi)?;
+        }
+        Ok(None)
+    }
+}
 
-            let p9 = p9.polynomial_modular(&psi_l, &qq);
-            println!("{} pol % psi({}):{}", line!(), l, p9);
+/// trace of Frobenius mod l
+pub fn trace_modulo(a: &BigInt, b: &BigInt, p: &BigInt, l: i64) -> BigInt {
+    try_trace_modulo(a, b, p, l).unwrap_or_else(|e| panic!("{}", e))
+}
 
-            if !p9.is_zero() {
-                jj = -jj;
-            }
-            if jj < 0 {
-                jj += l;
-            }
-            mod_result.push(bigint::ModResult { l: BigInt::from(l), r: BigInt::from(jj) });
-            println!("(iii) y  a = {} mod {}", jj, l);
-        } else {
-            // (d)
-            println!("x not found");
-            let mut found = false;
-            let mut w: i32 = 1;
-            for i in num_iter::range(1, l) {
-                if i.pow(2).mod_floor(&l) == ql {
-                    found = true;
-                    w = i;
-                    break;
-                }
+pub fn try_trace_modulo(a: &BigInt, b: &BigInt, p: &BigInt, l: i64) -> error::Result<BigInt> {
+    check_curve(a, b, p)?;
+    if l < 2 || !primes::is_prime(l as u64) || BigInt::from(l) == *p {
+        return Err(Error::InvalidArgument(format!("l:{}", l)));
+    }
+    let field = fp::PrimeField::new(p);
+    let (a, b) = (field.elem(a.clone()), field.elem(b.clone()));
+    let x = DensePolynomial::x(&field);
+    let e = DensePolynomial::new(&field, vec![b.to_bigint(), a.to_bigint(), Zero::zero(), One::one()]);
+    if l == 2 {
+        // has a point of order 2 iff t is even
+        let g = (x.powmod(p, &e) - x).gcd(&e);
+        return Ok(if g.is_one() { One::one() } else { Zero::zero() });
+    }
+    let mut h = division_polynomial::psi_fp(&a, &b, l as usize);
+    loop {
+        match Ring::new(&h, &a, &b).trace(l, p) {
+            Ok(Some(t)) => return Ok(BigInt::from(t)),
+            Ok(None) => return Err(Error::InvalidArgument(format!("trace not found l:{}", l))),
+            Err(g) => {
+                let (q, _) = h.divrem(&g);
+                h = if g.degree() <= q.degree() { g } else { q.to_monic() };
             }
-            if !found {
-                mod_result.push(bigint::ModResult { l: BigInt::from(l), r: BigInt::from(0) });
-                println!("(d)  a = 0 mod l because of w not found (d)");
-            } else {
-                // (e) x
-                let mut p12 = TermBuilder::new().xpow(q).build()
-                       * division_polynomial::psi(a, b, w).power(2) - division_polynomial::phi(a, b, w);
-                p12.modular_assign(&qq);
-                let p13 = p12.reduction_modular(a, b, q);
+        }
+    }
+}
 
-                let p14 = &division_polynomial::psi(&qq, b, l) % q;
+/// Calculate the trace of Frobenius of the Elliptic curve y^2 = x^3 + a x + b mod l
+/// for primes l until their product exceeds 4 sqrt(p)
+/// schoof algorithm
+pub fn schoof(a: &BigInt, b: &BigInt, p: &BigInt) -> Vec<bigint::ModResult> {
+    try_schoof(a, b, p).unwrap_or_else(|e| panic!("{}", e))
+}
 
-                let qq = BigInt::from(q);
-                let p15 = p13.polynomial_modular(&p14, &qq);
+/// p > 3 and 4 a^3 + 27 b^2 != 0 mod p
+pub(crate) fn check_curve(a: &BigInt, b: &BigInt, p: &BigInt) -> error::Result<()> {
+    if p <= &BigInt::from(3) {
+        return Err(Error::UnsupportedPrime(p.clone()));
+    }
+    if !bigint::is_probable_prime(p) {
+        return Err(Error::NotPrime(p.clone()));
+    }
+    let d = BigInt::from(4) * a.power(3) + BigInt::from(27) * b.power(2);
+    if d.mod_floor(p).is_zero() {
+        return Err(Error::SingularCurve);
+    }
+    Ok(())
+}
 
-                if !p15.is_zero() {
-                    mod_result.push(bigint::ModResult { l: BigInt::from(l), r: BigInt::from(0) });
-                    println!("(e) y  a = 0 mod {}", l);
-                } else {
-                    // (e) y
-                    let aa: i32 = a.clone().to_i32().unwrap();
-                    let mut p16 = (TermBuilder::new().ypow(aa).build()
-                              * division_polynomial::psi(a, b, w).power(3) - division_polynomial::omega(a, b, w)) /
-                            TermBuilder::new().ypow(1).build();
-                    p16.modular_assign(&qq);
-                    let p17 = p16.reduction_modular(a, b, q);
-                    let mut ww: BigInt;
-                    let qq = BigInt::from(q);
-                    if p17.is_gcd_one(&division_polynomial::psi(a, b, l), &qq) {
-                        ww = - BigInt::from(2) * w;
-                    } else {
-                        ww = BigInt::from(2) * w;
-                    }
-                    if ww < BigInt::from(0) {
-                        ww += BigInt::from(l);
-                    }
-                    mod_result.push(bigint::ModResult { l: BigInt::from(l), r: ww.clone() });
-                    println!("(e) y  a = {} mod {}", &ww, l);
-                }
-            }
+pub fn try_schoof(a: &BigInt, b: &BigInt, p: &BigInt) -> error::Result<Vec<bigint::ModResult>> {
+    check_curve(a, b, p)?;
+    let mut mod_result: Vec<bigint::ModResult> = Vec::new();
+    let mut product: BigInt = One::one();
+    let mut l: i64 = 2;
+    // product > 4 sqrt(p)
+    while &product * &product <= BigInt::from(16) * p {
+        if primes::is_prime(l as u64) && BigInt::from(l) != *p {
+            let r = try_trace_modulo(a, b, p, l)?;
+            mod_result.push(bigint::ModResult { l: BigInt::from(l), r });
+            product *= l;
         }
+        l += 1;
+    }
+    Ok(mod_result)
+}
+
+/// order of the Elliptic curve y^2 = x^3 + a x + b over F_p
+/// #E = p + 1 - t, |t| <= 2 sqrt(p)
+pub fn count_points(a: &BigInt, b: &BigInt, p: &BigInt) -> BigInt {
+    try_count_points(a, b, p).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn try_count_points(a: &BigInt, b: &BigInt, p: &BigInt) -> error::Result<BigInt> {
+    let result = bigint::chinese_remainder(&try_schoof(a, b, p)?);
+    let mut t = result.r.mod_floor(&result.l);
+    if &t * 2 > result.l {
+        t -= &result.l;
     }
-    mod_result
+    // Hasse bound, fails only if a trace mod l was wrong
+    if &t * &t > BigInt::from(4) * p {
+        return Err(Error::InvalidArgument(format!("t:{}", t)));
+    }
+    Ok(p + 1 - t)
 }
 
 #[test]
-#[ignore]
 fn schoof_test7() {
     use crate::bigint::chinese_remainder;
 
-    let q = 7;
-    let mod_result = schoof(&BigInt::from(2), &BigInt::from(1), q);
+    let q = BigInt::from(7);
+    let mod_result = schoof(&BigInt::from(2), &BigInt::from(1), &q);
     assert_eq!(mod_result.len(), 3);
     assert_eq!(mod_result[0].l, BigInt::from(2));
     assert_eq!(mod_result[0].r, BigInt::from(1));
@@ -256,11 +257,10 @@ fn schoof_test7() {
 }
 
 #[test]
-#[ignore]
 fn schoof_test19() {
     use crate::bigint::chinese_remainder;
-    let q = 19;
-    let mod_result = schoof(&BigInt::from(2), &BigInt::from(1), q);
+    let q = BigInt::from(19);
+    let mod_result = schoof(&BigInt::from(2), &BigInt::from(1), &q);
     assert_eq!(mod_result.len(), 3);
     assert_eq!(mod_result[0].l, BigInt::from(2));
     assert_eq!(mod_result[0].r, BigInt::from(1));
@@ -274,3 +274,59 @@ fn schoof_test19() {
     assert_eq!(result.l, BigInt::from(30)); // 2 * 3 * 5
 }
 
+#[test]
+fn count_points_test() {
+    use super::elliptic_curve;
+    for p in [5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101] {
+        let p = BigInt::from(p);
+        for (a, b) in [(1, 1), (2, 1), (0, 3), (3, 0), (-3, 5), (4, 7)] {
+            let (a, b) = (BigInt::from(a), BigInt::from(b));
+            let d = BigInt::from(4) * a.power(3) + BigInt::from(27) * b.power(2);
+            if d.mod_floor(&p).is_zero() {
+                continue;
+            }
+            let ec = elliptic_curve::EllipticCurve::new(&a, &b, &p);
+            assert_eq!(count_points(&a, &b, &p), BigInt::from(ec.cardinality()), "a:{} b:{} p:{}", a, b, p);
+        }
+    }
+}
+
+#[test]
+fn count_points_test2() {
+    let p = BigInt::from(1_000_003);
+    assert_eq_str!(count_points(&BigInt::from(2), &BigInt::from(3), &p), "999708");
+}
+
+#[test]
+fn count_points_test3() {
+    use crate::bigint::PowerModulo;
+    use super::elliptic_curve;
+    // p = 3 mod 4
+    let p = BigInt::from(4_294_967_291u64);
+    let (a, b) = (BigInt::from(-3), BigInt::from(7));
+    let n = count_points(&a, &b, &p);
+    let ec = elliptic_curve::EllipticCurve::new_raw(&a, &b, &p);
+    let mut found = 0;
+    for x in 1..100 {
+        let x = BigInt::from(x);
+        let r = (x.power(3) + &a * &x + &b).mod_floor(&p);
+        let y = r.power_modulo(&((&p + 1) / 4), &p);
+        if (&y * &y).mod_floor(&p) != r {
+            continue;
+        }
+        let point = ec.point(&x, &y);
+        assert!(ec.multiply_scalar(&point, &n).is_infinity());
+        found += 1;
+    }
+    assert!(found > 10);
+}
+
+#[test]
+fn try_count_points_test() {
+    let (a, b) = (BigInt::from(2), BigInt::from(3));
+    assert_eq!(try_count_points(&a, &b, &BigInt::from(3)), Err(Error::UnsupportedPrime(BigInt::from(3))));
+    assert_eq!(try_count_points(&a, &b, &BigInt::from(21)), Err(Error::NotPrime(BigInt::from(21))));
+    assert_eq!(try_count_points(&BigInt::from(-3), &BigInt::from(2), &BigInt::from(7)), Err(Error::SingularCurve));
+    assert!(try_trace_modulo(&a, &b, &BigInt::from(7), 7).is_err());
+    assert_eq!(try_count_points(&a, &b, &BigInt::from(7)), Ok(BigInt::from(6)));
+}
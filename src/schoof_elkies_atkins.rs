@@ -1,14 +1,19 @@
-extern crate num_bigint;
-extern crate num_traits;
-extern crate num_iter;
-
 use num_bigint::BigInt;
-use num_traits::{Zero, ToPrimitive};
-use super::term_builder;
-use super::modular_polynomial;
+use num_integer::Integer;
+use num_traits::{Zero, One};
+use super::bigint;
+use super::bigint::{Inverse, Power};
+use super::dense_polynomial;
+use super::division_polynomial;
 use super::elliptic_curve;
+use super::error::{Error, Result};
+use super::fp;
+use super::modular_polynomial;
 use super::polynomial;
-use std::convert::TryInto;
+use super::schoof;
+use super::term_builder;
+
+type DensePolynomial = dense_polynomial::DensePolynomial;
 
 pub struct SEAResult {
     pub gcd: polynomial::Polynomial,
@@ -17,36 +22,65 @@ pub struct SEAResult {
     pub isogeny_j_invariants: Vec<BigInt>
 }
 
+/// Elkies prime
+/// Frobenius acts on the kernel of a rational l-isogeny as the eigenvalue
+pub struct ElkiesResult {
+    pub l: i32,
+    pub eigenvalue: BigInt,
+    /// kernel polynomial of degree (l - 1) / 2 from the Elkies procedure,
+    /// a factor of psi_l when the procedure is not used (e.g. j = 0, 1728)
+    pub kernel_polynomial: polynomial::Polynomial,
+    /// t = eigenvalue + p / eigenvalue mod l
+    pub trace: BigInt,
+}
+
+/// normalized l-isogenous curve by the Elkies procedure
+pub struct IsogenousCurve {
+    /// root of Phi_l(x, j)
+    pub j_invariant: BigInt,
+    /// model y^2 = x^3 + a x + b such that the isogeny is normalized
+    pub curve: elliptic_curve::EllipticCurve,
+    /// factor of psi_l of degree (l - 1) / 2, the kernel of the isogeny
+    pub kernel_polynomial: polynomial::Polynomial,
+}
+
+/// Atkin prime
+/// modular polynomial factors into irreducible polynomials of degree r
+pub struct AtkinResult {
+    pub l: i32,
+    pub r: i32,
+    /// possible traces mod l
+    pub traces: Vec<BigInt>,
+}
+
+/// primes l using the modular polynomial in count_points, all in modular_polynomial_table
+/// the others are calculated by schoof algorithm
+pub const MODULAR_POLYNOMIAL_PRIMES: [i32; 7] = [3, 5, 7, 11, 13, 17, 19];
+
+/// Phi_l(x, j) over F_p
+fn modular_polynomial_j(ec: &elliptic_curve::EllipticCurve, l: i32) -> DensePolynomial {
+    let mut mpol = modular_polynomial::modular_polynomial_cached(l);
+    mpol.modular_assign(ec.p());
+    let mpol = mpol.eval_y(&ec.j_invariant());
+    DensePolynomial::from_polynomial(&mpol, ec.field())
+}
+
 /// SEA algorithm
-/// TODO: in implementation
+/// classify l and find the j-invariants of the l-isogenous curves
 pub fn sea(ec: &elliptic_curve::EllipticCurve, l: i32) -> SEAResult {
-    let mut mpol = modular_polynomial::modular_polynomial(l);
-    mpol.modular_assign(&ec.p);
+    let mut mpol = modular_polynomial::modular_polynomial_cached(l);
+    mpol.modular_assign(ec.p());
     let mut mpol = mpol.eval_y(&ec.j_invariant());
-    mpol.modular_assign(&ec.p);
-    let pp: i32 = ec.p.to_i32().unwrap();
-    let pol = term_builder::TermBuilder::new().xpow(pp).build()
-            - term_builder::TermBuilder::new().xpow(1).build();
-    let gcd = pol.gcd(&mpol, &ec.p);
+    mpol.modular_assign(ec.p());
+    let x = term_builder::TermBuilder::new().xpow(1).build().to_pol();
+    let pol = x.power_mod_poly(ec.p(), &mpol, ec.p()) - &x;
+    let gcd = pol.gcd(&mpol, ec.p());
     // elkies prime for degree 1, 2, l+1
     // atkins prime for degree 0
     let degree = gcd.clone().degree_x();
     let is_elkies_prime = gcd.degree_x() > Zero::zero();
-    let mut isogeny_j_invariants: Vec<BigInt> = Vec::new();
-    if is_elkies_prime {
-        for j in num_iter::range(BigInt::from(0), ec.p.clone()) {
-            let mut tmp = gcd.clone().eval_x(&j.clone());
-            tmp.modular_assign(&ec.p);
-            if tmp.is_zero() {
-                isogeny_j_invariants.push(j);
-                if degree.clone() == isogeny_j_invariants.len().try_into().unwrap() ||
-                   isogeny_j_invariants.len() == 2
-                {
-                    break;
-                }
-            }
-        }
-    }
+    // gcd with x^p - x has no multiple roots
+    let isogeny_j_invariants = if is_elkies_prime { gcd.roots(ec.p()) } else { Vec::new() };
     SEAResult {
         gcd: gcd.clone(),
         degree_of_gcd: degree.clone(),
@@ -55,6 +89,388 @@ pub fn sea(ec: &elliptic_curve::EllipticCurve, l: i32) -> SEAResult {
     }
 }
 
+/// smallest r such that Phi_l(x, j) has a root in F_{p^r}
+/// r = 1 for Elkies primes
+/// None if Phi_l(x, j) has multiple roots (e.g. supersingular curves),
+/// the roots do not correspond to the subgroups of order l one to one,
+/// or if no root is found for r <= l + 1.
+pub fn frobenius_order(ec: &elliptic_curve::EllipticCurve, l: i32) -> Option<i32> {
+    let mpol = modular_polynomial_j(ec, l);
+    if !mpol.gcd(&mpol.derivative()).is_one() {
+        return None;
+    }
+    let x = DensePolynomial::x(ec.field());
+    let mut xk = x.clone();
+    for r in 1..=(l + 1) {
+        xk = xk.powmod(ec.p(), &mpol);
+        if !(&xk - &x).gcd(&mpol).is_one() {
+            return Some(r);
+        }
+    }
+    None
+}
+
+/// eigenvalue search on the kernel polynomial of an l-isogeny
+pub fn elkies(ec: &elliptic_curve::EllipticCurve, l: i32) -> ElkiesResult {
+    try_elkies(ec, l).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Frobenius is the eigenvalue on the whole kernel, so the search works modulo a polynomial of
+/// degree (l - 1) / 2 instead of psi_l of degree (l^2 - 1) / 2.
+/// psi_l is searched instead if the Elkies procedure gives no kernel polynomial
+/// or l is not in MODULAR_POLYNOMIAL_PRIMES.
+pub fn try_elkies(ec: &elliptic_curve::EllipticCurve, l: i32) -> Result<ElkiesResult> {
+    schoof::check_curve(ec.a_fp().value(), ec.b_fp().value(), ec.p())?;
+    if l < 3 || !primes::is_prime(l as u64) || &BigInt::from(l) == ec.p() {
+        return Err(Error::InvalidArgument(format!("l:{}", l)));
+    }
+    let p = ec.p();
+    let field = ec.field();
+    let curves = if MODULAR_POLYNOMIAL_PRIMES.contains(&l) && &BigInt::from(l) < p {
+        try_isogenous_curves(ec, l)?
+    } else {
+        Vec::new()
+    };
+    let h = match curves.first() {
+        Some(curve) => DensePolynomial::from_polynomial(&curve.kernel_polynomial, field),
+        None => division_polynomial::psi_fp(&ec.a_fp(), &ec.b_fp(), l as usize),
+    };
+    let ring = schoof::Ring::new(&h, &ec.a_fp(), &ec.b_fp());
+    let xp = DensePolynomial::x(field).powmod(p, &h);
+    let point = ring.generic_point();
+    let mut q = schoof::RingPoint::Infinity;
+    for lambda in 1..=(l - 1) / 2 {
+        // no point of order l is killed by the denominators
+        q = ring.add(&q, &point).map_err(|_| Error::InvalidArgument(format!("non invertible denominator l:{}", l)))?;
+        let (x, y) = match &q {
+            schoof::RingPoint::Affine(x, y) => (x, y),
+            schoof::RingPoint::Infinity => unreachable!(),
+        };
+        // x^p = x([lambda] P)
+        let g = (&xp - x).gcd(&h);
+        if g.degree() == 0 {
+            continue;
+        }
+        // y^p = y (x^3 + a x + b)^((p-1)/2)
+        let yp = ring.e.powmod(&((p - 1) / 2), &g);
+        let y = y % &g;
+        let (eigenvalue, kernel) = if y == yp {
+            (BigInt::from(lambda), g)
+        } else if (&y + &yp).is_zero() {
+            (BigInt::from(-lambda), g)
+        } else {
+            // both lambda and -lambda are eigenvalues
+            (BigInt::from(lambda), (&y - &yp).gcd(&g))
+        };
+        let lb = BigInt::from(l);
+        let eigenvalue = eigenvalue.mod_floor(&lb);
+        let trace = (&eigenvalue + p * eigenvalue.inverse(&lb)).mod_floor(&lb);
+        return Ok(ElkiesResult {
+            l,
+            eigenvalue,
+            kernel_polynomial: kernel.to_polynomial(),
+            trace,
+        });
+    }
+    Err(Error::InvalidArgument(format!("{} is not an Elkies prime", l)))
+}
+
+/// c_k of the Laurent series p(z) = z^-2 + sum c_k z^2k of the Weierstrass function, k < n
+/// c_1 = -a / 5, c_2 = -b / 7, c_k = 3 / ((k - 2) (2 k + 3)) sum c_j c_k-1-j
+fn weierstrass_coefficients(a: &fp::Fp, b: &fp::Fp, n: usize) -> Vec<fp::Fp> {
+    let field = a.field().unwrap();
+    let mut c = vec![field.zero(), -(a / field.elem(5)), -(b / field.elem(7))];
+    for k in 3..n {
+        let mut sum = field.zero();
+        for j in 1..=(k - 2) {
+            sum += &c[j] * &c[k - 1 - j];
+        }
+        c.push(field.elem(3) * sum / field.elem(((k - 2) * (2 * k + 3)) as u64));
+    }
+    c
+}
+
+/// prod (x - x_Q) of degree d from the sum s_1 of its roots
+///
+/// The Laurent series of the curves are related by
+/// c~_n - c_n = 2 / (2n)! sum_Q p^(2n)(x_Q), p^(2n) = P_n(p), P_0 = x, P_n+1 = 4 f P_n'' + 2 f' P_n',
+/// which gives the power sums s_2, ..., s_d and the coefficients by Newton's identities.
+fn kernel_from_power_sum(ec: &elliptic_curve::EllipticCurve, a: &fp::Fp, b: &fp::Fp,
+                         s1: &fp::Fp, d: usize) -> DensePolynomial {
+    let field = ec.field();
+    let c = weierstrass_coefficients(&ec.a_fp(), &ec.b_fp(), d);
+    let ct = weierstrass_coefficients(a, b, d);
+    let f = DensePolynomial::new(field, vec![ec.b_fp().to_bigint(), ec.a_fp().to_bigint(), BigInt::zero(), BigInt::one()]);
+    let df = f.derivative();
+    let mut s = vec![field.elem(d as u64), s1.clone()];
+    let mut pn = DensePolynomial::x(field);
+    // (2n)! / 2
+    let mut factorial = field.elem(1) / field.elem(2);
+    for n in 1..d {
+        let dp = pn.derivative();
+        pn = &f * dp.derivative() * field.elem(4) + &df * &dp * field.elem(2);
+        factorial *= field.elem((2 * n * (2 * n - 1)) as u64);
+        let mut sum = &factorial * (&ct[n] - &c[n]);
+        for (k, sk) in s.iter().enumerate() {
+            sum -= pn.coef(k) * sk;
+        }
+        s.push(sum / pn.coef(n + 1));
+    }
+    // k e_k = sum (-1)^(i-1) e_k-i s_i
+    let mut e = vec![field.one()];
+    for k in 1..=d {
+        let mut sum = field.zero();
+        for i in 1..=k {
+            let t = &e[k - i] * &s[i];
+            if i % 2 == 1 { sum += t } else { sum -= t }
+        }
+        e.push(sum / field.elem(k as u64));
+    }
+    let coefs = (0..=d).rev().map(|k| if k % 2 == 1 { -&e[k] } else { e[k].clone() }.to_bigint()).collect();
+    DensePolynomial::new(field, coefs)
+}
+
+/// Elkies procedure for a root j~ of Phi_l(x, j)
+pub fn isogenous_curve(ec: &elliptic_curve::EllipticCurve, l: i32, j_tilde: &BigInt) -> IsogenousCurve {
+    try_isogenous_curve(ec, l, j_tilde).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// normalized isogenous curve and kernel polynomial from the partial derivatives of Phi_l at (j, j~)
+///
+/// j, j~ must not be 0, 1728 and j~ must be a simple root of Phi_l(x, j).
+pub fn try_isogenous_curve(ec: &elliptic_curve::EllipticCurve, l: i32, j_tilde: &BigInt) -> Result<IsogenousCurve> {
+    schoof::check_curve(ec.a_fp().value(), ec.b_fp().value(), ec.p())?;
+    if l < 3 || !primes::is_prime(l as u64) || &BigInt::from(l) >= ec.p() {
+        return Err(Error::InvalidArgument(format!("l:{}", l)));
+    }
+    let field = ec.field();
+    let (a, b) = (ec.a_fp(), ec.b_fp());
+    let j = ec.j_invariant();
+    let jt = field.elem(j_tilde.clone());
+    let c1728 = field.elem(1728);
+    if a.is_zero() || b.is_zero() {
+        return Err(Error::UnsupportedJInvariant(j));
+    }
+    if jt.is_zero() || jt == c1728 {
+        return Err(Error::UnsupportedJInvariant(jt.to_bigint()));
+    }
+    let mut mpol = modular_polynomial::modular_polynomial_cached(l);
+    mpol.modular_assign(ec.p());
+    let eval = |pol: &polynomial::Polynomial| field.elem(pol.eval_xy(&j, jt.value()));
+    if !eval(&mpol).is_zero() {
+        return Err(Error::InvalidArgument(format!("Phi_{}({}, {}) is not 0", l, j, jt)));
+    }
+    let (dx, dy) = (mpol.derivative_x(), mpol.derivative_y());
+    let (px, py) = (eval(&dx), eval(&dy));
+    let (pxx, pxy, pyy) = (eval(&dx.derivative_x()), eval(&dx.derivative_y()), eval(&dy.derivative_y()));
+    if px.is_zero() || py.is_zero() {
+        return Err(Error::MultipleRoot(jt.to_bigint()));
+    }
+    let j = field.elem(j);
+    let lf = field.elem(l);
+    let e = |n: i32| field.elem(n);
+    // Eisenstein series E_4 = -a / 3, E_6 = -b / 2 and j' = -E_6 / E_4 j
+    let e4 = -(&a / e(3));
+    let e6 = -(&b / e(2));
+    let dj = -(&e6 / &e4) * &j;
+    let djt = -(&dj * &px) / (&lf * &py);
+    let e4t = djt.square() / (&jt * (&jt - c1728));
+    let e6t = -(&e4t * &djt) / &jt;
+    let l2 = lf.square();
+    let at = -(e(3) * l2.square() * &e4t);
+    let bt = -(e(2) * l2.square() * &l2 * &e6t);
+
+    let jj = -(dj.square() * pxx + e(2) * &lf * &dj * &djt * pxy + &l2 * djt.square() * pyy) / (&dj * px);
+    let p1 = &lf / e(2) * jj
+        + &lf / e(4) * (e4.square() / &e6 - &lf * e4t.square() / &e6t)
+        + &lf / e(3) * (&e6 / &e4 - &lf * &e6t / &e4t);
+    // p1 is the sum over the kernel for the model with x scaled by -12 (E_4 / 48, E_6 / 864)
+    let s1 = -(e(6) * p1);
+    let kernel = kernel_from_power_sum(ec, &at, &bt, &s1, ((l - 1) / 2) as usize);
+    Ok(IsogenousCurve {
+        j_invariant: jt.to_bigint(),
+        curve: elliptic_curve::EllipticCurve::try_new(&at.to_bigint(), &bt.to_bigint(), ec.p())?,
+        kernel_polynomial: kernel.to_polynomial(),
+    })
+}
+
+/// isogenous curves for the roots of Phi_l(x, j) found by sea
+pub fn isogenous_curves(ec: &elliptic_curve::EllipticCurve, l: i32) -> Vec<IsogenousCurve> {
+    try_isogenous_curves(ec, l).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// the roots the Elkies procedure does not handle are skipped (j or j~ 0 or 1728, multiple roots),
+/// so the result is empty for curves with j = 0 or 1728. Other errors are returned.
+pub fn try_isogenous_curves(ec: &elliptic_curve::EllipticCurve, l: i32) -> Result<Vec<IsogenousCurve>> {
+    schoof::check_curve(ec.a_fp().value(), ec.b_fp().value(), ec.p())?;
+    if l < 3 || !primes::is_prime(l as u64) || &BigInt::from(l) >= ec.p() {
+        return Err(Error::InvalidArgument(format!("l:{}", l)));
+    }
+    let mut curves = Vec::new();
+    for j in sea(ec, l).isogeny_j_invariants.iter() {
+        match try_isogenous_curve(ec, l, j) {
+            Ok(curve) => curves.push(curve),
+            Err(Error::UnsupportedJInvariant(_)) | Err(Error::MultipleRoot(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(curves)
+}
+
+/// t mod l such that the ratio of the roots of x^2 - t x + p has order r
+/// V_k = rho^k + rho^-k, V_0 = 2, V_1 = t^2 / p - 2, V_k+1 = V_1 V_k - V_k-1
+fn atkin_traces(p: &BigInt, l: i32, r: i32) -> Vec<BigInt> {
+    let lb = BigInt::from(l);
+    let pinv = p.mod_floor(&lb).inverse(&lb);
+    let mut traces: Vec<BigInt> = Vec::new();
+    for t in 0..l {
+        let z = (BigInt::from(t * t) * &pinv - BigInt::from(2)).mod_floor(&lb);
+        let (mut v0, mut v1) = (BigInt::from(2), z.clone());
+        for k in 1..=(l + 1) {
+            if v1 == BigInt::from(2) {
+                if k == r {
+                    traces.push(BigInt::from(t));
+                }
+                break;
+            }
+            let v2 = (&z * &v1 - &v0).mod_floor(&lb);
+            v0 = std::mem::replace(&mut v1, v2);
+        }
+    }
+    traces
+}
+
+/// possible traces for an Atkin prime
+pub fn atkin(ec: &elliptic_curve::EllipticCurve, l: i32) -> AtkinResult {
+    try_atkin(ec, l).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn try_atkin(ec: &elliptic_curve::EllipticCurve, l: i32) -> Result<AtkinResult> {
+    schoof::check_curve(ec.a_fp().value(), ec.b_fp().value(), ec.p())?;
+    if l < 3 || !primes::is_prime(l as u64) || &BigInt::from(l) == ec.p() {
+        return Err(Error::InvalidArgument(format!("l:{}", l)));
+    }
+    match frobenius_order(ec, l) {
+        Some(r) if r > 1 => Ok(AtkinResult {
+            l,
+            r,
+            traces: atkin_traces(ec.p(), l, r),
+        }),
+        Some(_) => Err(Error::InvalidArgument(format!("{} is not an Atkin prime", l))),
+        None => Err(Error::InvalidArgument(format!("Phi_{}(x, j) has multiple roots", l))),
+    }
+}
+
+/// keep t with [p + 1 - t] P = O
+/// P is taken on E_r: y^2 = x^3 + a r^2 x + b r^3, r = x0^3 + a x0 + b,
+/// E_r is E for a square r and the quadratic twist (order p + 1 + t) otherwise.
+fn filter_by_points(ec: &elliptic_curve::EllipticCurve, candidates: &mut Vec<BigInt>) {
+    let p = ec.p();
+    let mut x0 = BigInt::zero();
+    let mut count = 0;
+    while candidates.len() > 1 && count < 20 && &x0 < p {
+        let r = ec.rhs(&ec.field().elem(x0.clone()));
+        if !r.is_zero() {
+            let square = r.power(&((p - 1) / 2)).is_one();
+            let r2 = r.square();
+            let r3 = &r2 * &r;
+            let er = elliptic_curve::EllipticCurve::new_raw(&(ec.a_fp() * &r2).to_bigint(), &(ec.b_fp() * &r3).to_bigint(), p);
+            let point = er.point(&(&r * ec.field().elem(x0.clone())).to_bigint(), &r2.to_bigint());
+            candidates.retain(|t| {
+                let n = if square { p + 1 - t } else { p + 1 + t };
+                er.multiply_scalar(&point, &n).is_infinity()
+            });
+            count += 1;
+        }
+        x0 += 1;
+    }
+}
+
+/// t in the Hasse interval, t = r mod l
+fn hasse_candidates(p: &BigInt, result: &bigint::ModResult) -> Vec<BigInt> {
+    let bound = (p * BigInt::from(4)).sqrt();
+    let mut t = -&bound + (&result.r + &bound).mod_floor(&result.l);
+    let mut candidates: Vec<BigInt> = Vec::new();
+    while t <= bound {
+        candidates.push(t.clone());
+        t += &result.l;
+    }
+    candidates
+}
+
+/// order of the Elliptic curve by SEA algorithm
+pub fn count_points(ec: &elliptic_curve::EllipticCurve) -> BigInt {
+    count_points_with_primes(ec, &MODULAR_POLYNOMIAL_PRIMES)
+}
+
+pub fn try_count_points(ec: &elliptic_curve::EllipticCurve) -> Result<BigInt> {
+    try_count_points_with_primes(ec, &MODULAR_POLYNOMIAL_PRIMES)
+}
+
+/// order of the Elliptic curve by SEA algorithm
+/// modular_primes: primes l classified as Elkies or Atkin with Phi_l
+pub fn count_points_with_primes(ec: &elliptic_curve::EllipticCurve, modular_primes: &[i32]) -> BigInt {
+    try_count_points_with_primes(ec, modular_primes).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn try_count_points_with_primes(ec: &elliptic_curve::EllipticCurve, modular_primes: &[i32]) -> Result<BigInt> {
+    let (a, b, p) = (&ec.a_fp().to_bigint(), &ec.b_fp().to_bigint(), ec.p());
+    schoof::check_curve(a, b, p)?;
+    let j = ec.j_invariant();
+    // Phi_l(x, j) has other factorization patterns for j = 0, 1728
+    let use_modular = !j.is_zero() && j != BigInt::from(1728).mod_floor(p);
+
+    let mut exact = vec![bigint::ModResult { l: BigInt::from(2), r: schoof::try_trace_modulo(a, b, p, 2)? }];
+    let mut atkins: Vec<AtkinResult> = Vec::new();
+    let mut product = BigInt::from(2);
+    let mut l: i32 = 3;
+    // product > 4 sqrt(p)
+    while &product * &product <= p * 16 {
+        if primes::is_prime(l as u64) && BigInt::from(l) != *p {
+            let r = if use_modular && modular_primes.contains(&l) { frobenius_order(ec, l) } else { None };
+            match r {
+                Some(1) => {
+                    let result = try_elkies(ec, l)?;
+                    exact.push(bigint::ModResult { l: BigInt::from(l), r: result.trace });
+                },
+                Some(r) => atkins.push(AtkinResult { l, r, traces: atkin_traces(p, l, r) }),
+                None => {
+                    exact.push(bigint::ModResult { l: BigInt::from(l), r: schoof::try_trace_modulo(a, b, p, l as i64)? });
+                },
+            }
+            product *= l;
+        }
+        l += 1;
+    }
+
+    // match
+    let mut residues = vec![bigint::chinese_remainder(&exact)];
+    for atkin in &atkins {
+        let mut next: Vec<bigint::ModResult> = Vec::new();
+        for residue in &residues {
+            for t in &atkin.traces {
+                let m = bigint::ModResult { l: BigInt::from(atkin.l), r: t.clone() };
+                next.push(bigint::chinese_remainder(&vec![residue.clone(), m]));
+            }
+        }
+        residues = next;
+    }
+    let mut candidates: Vec<BigInt> = residues.iter().flat_map(|r| hasse_candidates(p, r)).collect();
+    filter_by_points(ec, &mut candidates);
+    while candidates.len() > 1 {
+        if primes::is_prime(l as u64) && BigInt::from(l) != *p {
+            let lb = BigInt::from(l);
+            let r = schoof::try_trace_modulo(a, b, p, l as i64)?;
+            candidates.retain(|t| t.mod_floor(&lb) == r);
+        }
+        l += 1;
+    }
+    match candidates.first() {
+        Some(t) => Ok(p + 1 - t),
+        None => Err(Error::InvalidArgument("no trace matches".to_string())),
+    }
+}
+
 #[test]
 fn sea_test1() {
     let p = BigInt::from(23);
@@ -71,7 +487,27 @@ fn sea_test1() {
 }
 
 #[test]
-#[ignore]
+fn sea_large_prime_test() {
+    // 2^127 - 1, the roots are not found by trying every j
+    let p = (BigInt::one() << 127usize) - 1u32;
+    let mut elkies_count = 0;
+    for b in 1..=6 {
+        let ec = elliptic_curve::EllipticCurve::new(&BigInt::from(3), &BigInt::from(b), &p);
+        let result = sea(&ec, 3);
+        if !result.is_elkies_prime {
+            continue;
+        }
+        assert_eq!(result.isogeny_j_invariants.len() as i32, result.degree_of_gcd);
+        let mpol = modular_polynomial::modular_polynomial_cached(3);
+        for j in result.isogeny_j_invariants.iter() {
+            assert!(mpol.eval_xy(j, &ec.j_invariant()).mod_floor(&p).is_zero());
+        }
+        elkies_count += 1;
+    }
+    assert!(elkies_count > 0);
+}
+
+#[test]
 fn sea_test2() {
     let p = BigInt::from(131);
     let ec = elliptic_curve::EllipticCurve::new(&BigInt::from(1), &BigInt::from(23), &p);
@@ -85,3 +521,164 @@ fn sea_test2() {
     assert_eq_str!(result.isogeny_j_invariants[1], "26");
 }
 
+
+#[test]
+fn elkies_atkin_test() {
+    let mut elkies_count = 0;
+    let mut atkin_count = 0;
+    for p in [23, 29, 31, 37, 41, 43] {
+        let p = BigInt::from(p);
+        for (a, b) in [(1, 7), (2, 3), (5, 1), (3, 11)] {
+            let ec = match elliptic_curve::EllipticCurve::try_new(&BigInt::from(a), &BigInt::from(b), &p) {
+                Ok(ec) => ec,
+                Err(Error::SingularCurve) => continue,
+                Err(e) => panic!("{}", e),
+            };
+            let j = ec.j_invariant();
+            if j.is_zero() || j == BigInt::from(1728).mod_floor(&p) {
+                continue;
+            }
+            let t = (&p + BigInt::from(1) - BigInt::from(ec.cardinality())).mod_floor(&BigInt::from(3));
+            let r = frobenius_order(&ec, 3);
+            if r.is_none() {
+                continue;
+            }
+            if r == Some(1) {
+                let result = elkies(&ec, 3);
+                assert_eq!(result.trace, t);
+                assert_eq!(result.kernel_polynomial.degree_x(), 1);
+                let psi = division_polynomial::psi_fp(&ec.a_fp(), &ec.b_fp(), 3);
+                let kernel = DensePolynomial::from_polynomial(&result.kernel_polynomial, ec.field());
+                assert!((psi % kernel).is_zero());
+                elkies_count += 1;
+            } else {
+                let result = atkin(&ec, 3);
+                assert!(result.traces.contains(&t));
+                atkin_count += 1;
+            }
+        }
+    }
+    assert!(elkies_count > 0);
+    assert!(atkin_count > 0);
+}
+
+#[test]
+fn isogenous_curve_test() {
+    use super::isogeny::Isogeny;
+    let mut count = 0;
+    for p in [23, 1009] {
+        let p = BigInt::from(p);
+        for (a, b) in [(1, 7), (2, 3), (5, 1), (3, 11), (7, 9)] {
+            let ec = elliptic_curve::EllipticCurve::new(&BigInt::from(a), &BigInt::from(b), &p);
+            for l in MODULAR_POLYNOMIAL_PRIMES {
+                if frobenius_order(&ec, l) != Some(1) {
+                    continue;
+                }
+                let psi = division_polynomial::psi_fp(&ec.a_fp(), &ec.b_fp(), l as usize);
+                for result in isogenous_curves(&ec, l) {
+                    assert_eq!(result.curve.j_invariant(), result.j_invariant);
+                    let kernel = DensePolynomial::from_polynomial(&result.kernel_polynomial, ec.field());
+                    assert_eq!(kernel.degree() as i32, (l - 1) / 2);
+                    assert!((&psi % &kernel).is_zero(), "{} {}", ec, result.kernel_polynomial);
+                    // Kohel's formulas give the same normalized model
+                    let iso = Isogeny::from_kernel_polynomial(&ec, &result.kernel_polynomial).unwrap();
+                    assert_eq!((iso.codomain.a_fp(), iso.codomain.b_fp()), (result.curve.a_fp(), result.curve.b_fp()));
+                    count += 1;
+                }
+            }
+        }
+    }
+    assert!(count > 4);
+
+    let ec = elliptic_curve::EllipticCurve::new(&BigInt::from(1), &BigInt::from(7), &BigInt::from(23));
+    // 20 and 22 are the roots of Phi_3(x, j)
+    assert!(try_isogenous_curve(&ec, 3, &BigInt::from(20)).is_ok());
+    assert!(try_isogenous_curve(&ec, 3, &BigInt::from(21)).is_err());
+    assert!(try_isogenous_curve(&ec, 4, &BigInt::from(20)).is_err());
+    assert_eq!(try_isogenous_curve(&ec, 3, &BigInt::from(1728)).err(), Some(Error::UnsupportedJInvariant(BigInt::from(1728 % 23))));
+    assert_eq!(try_isogenous_curves(&ec, 3).map(|v| v.len()), Ok(2));
+    assert!(matches!(try_isogenous_curves(&ec, 4), Err(Error::InvalidArgument(_))));
+    let singular = elliptic_curve::EllipticCurve::new_raw(&BigInt::from(-3), &BigInt::from(2), &BigInt::from(7));
+    assert_eq!(try_isogenous_curves(&singular, 3).err(), Some(Error::SingularCurve));
+
+    // j = 0 and j = 1728 have Elkies primes, but no isogenous curves from the Elkies procedure
+    for (a, b) in [(0, 7), (1, 0)] {
+        let ec = elliptic_curve::EllipticCurve::new(&BigInt::from(a), &BigInt::from(b), &BigInt::from(1009));
+        let mut elkies_count = 0;
+        for l in MODULAR_POLYNOMIAL_PRIMES {
+            if !sea(&ec, l).isogeny_j_invariants.is_empty() {
+                elkies_count += 1;
+            }
+            assert!(isogenous_curves(&ec, l).is_empty());
+            if let Some(j) = sea(&ec, l).isogeny_j_invariants.first() {
+                assert_eq!(try_isogenous_curve(&ec, l, j).err(), Some(Error::UnsupportedJInvariant(ec.j_invariant())));
+            }
+        }
+        assert!(elkies_count > 0);
+    }
+}
+
+#[test]
+fn sea_count_points_test() {
+    for p in [5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101] {
+        let p = BigInt::from(p);
+        for (a, b) in [(1, 1), (2, 1), (0, 3), (3, 0), (-3, 5), (4, 7)] {
+            let (a, b) = (BigInt::from(a), BigInt::from(b));
+            let ec = match elliptic_curve::EllipticCurve::try_new(&a, &b, &p) {
+                Ok(ec) => ec,
+                Err(Error::SingularCurve) => continue,
+                Err(e) => panic!("{}", e),
+            };
+            let n = BigInt::from(ec.cardinality());
+            assert_eq!(count_points_with_primes(&ec, &[3]), n, "a:{} b:{} p:{}", a, b, p);
+        }
+    }
+}
+
+#[test]
+fn sea_count_points_test2() {
+    let p = BigInt::from(1_000_003);
+    let ec = elliptic_curve::EllipticCurve::new_raw(&BigInt::from(2), &BigInt::from(3), &p);
+    assert_eq_str!(count_points_with_primes(&ec, &[3]), "999708");
+}
+
+#[test]
+fn sea_elkies_kernel_test() {
+    // t = 296, the eigenvalue is searched modulo the kernel polynomial of degree (l - 1) / 2
+    let p = BigInt::from(1_000_003);
+    let ec = elliptic_curve::EllipticCurve::new(&BigInt::from(2), &BigInt::from(3), &p);
+    let t = BigInt::from(296);
+    let mut elkies_count = 0;
+    for l in MODULAR_POLYNOMIAL_PRIMES {
+        if frobenius_order(&ec, l) != Some(1) {
+            continue;
+        }
+        let result = elkies(&ec, l);
+        assert_eq!(result.trace, t.mod_floor(&BigInt::from(l)), "l:{}", l);
+        assert_eq!(result.kernel_polynomial.degree_x(), (l - 1) / 2, "l:{}", l);
+        elkies_count += 1;
+    }
+    assert!(elkies_count > 1);
+    assert_eq!(count_points(&ec), &p + 1 - &t);
+}
+
+#[test]
+fn sea_count_points_test3() {
+    let p = BigInt::from(131);
+    // j = 0 and j = 1728 are counted without the modular polynomials
+    for (a, b) in [(1, 23), (2, 3), (0, 5), (7, 0)] {
+        let ec = elliptic_curve::EllipticCurve::new(&BigInt::from(a), &BigInt::from(b), &p);
+        assert_eq!(count_points(&ec), BigInt::from(ec.cardinality()), "a:{} b:{}", a, b);
+        assert_eq!(try_count_points(&ec), Ok(BigInt::from(ec.cardinality())));
+    }
+}
+
+#[test]
+fn sea_try_test() {
+    let ec = elliptic_curve::EllipticCurve::new_raw(&BigInt::from(-3), &BigInt::from(2), &BigInt::from(7));
+    assert_eq!(try_count_points(&ec).err(), Some(Error::SingularCurve));
+    let ec = elliptic_curve::EllipticCurve::new(&BigInt::from(1), &BigInt::from(7), &BigInt::from(23));
+    assert!(try_elkies(&ec, 4).is_err());
+    assert!(try_atkin(&ec, 3).is_err());
+    assert!(try_elkies(&ec, 3).is_ok());
+}
@@ -9,13 +9,95 @@
 
 use crate::bigint::Power;
 use num_bigint::BigInt;
-use num_traits::One;
+use num_integer::Integer;
+use num_traits::Signed;
+use std::sync::OnceLock;
 use super::elliptic_curve;
+use super::error::Result;
+use super::secp256k1_field::Fe;
 
 #[derive(Debug, Clone)]
 pub struct Secp256k1 {
     pub ec: elliptic_curve::EllipticCurve,
     pub g: elliptic_curve::ECPoint,
+    /// order of g
+    pub n: BigInt,
+    /// j 16^i G for the 64 4-bit digits i of a scalar and j in [0, 16)
+    table: OnceLock<Vec<[ProjectivePoint; 16]>>,
+}
+
+/// point in homogeneous projective coordinates over the fixed-width field,
+/// (X : Y : Z) is the affine point (X / Z, Y / Z), O = (0 : 1 : 0)
+#[derive(Debug, Clone, Copy)]
+struct ProjectivePoint {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+}
+
+impl ProjectivePoint {
+    fn infinity() -> ProjectivePoint {
+        ProjectivePoint { x: Fe::zero(), y: Fe::one(), z: Fe::zero() }
+    }
+
+    fn from_affine(ec: &elliptic_curve::EllipticCurve, point: &elliptic_curve::ECPoint) -> ProjectivePoint {
+        let point = ec.to_affine(point);
+        if point.is_infinity() {
+            return ProjectivePoint::infinity();
+        }
+        ProjectivePoint { x: Fe::from_bigint(point.x().value()), y: Fe::from_bigint(point.y().value()), z: Fe::one() }
+    }
+
+    fn to_affine(self, ec: &elliptic_curve::EllipticCurve) -> elliptic_curve::ECPoint {
+        if self.z.is_zero() {
+            return elliptic_curve::ECPoint::infinity();
+        }
+        let zinv = self.z.inv();
+        ec.point(&(self.x * zinv).to_bigint(), &(self.y * zinv).to_bigint())
+    }
+
+    /// P + Q by the complete formulas for a = 0 of Renes, Costello and Batina (Algorithm 7),
+    /// the same operations for doubling, O and P + (-P)
+    fn add(&self, other: &ProjectivePoint) -> ProjectivePoint {
+        let b3 = Fe::from_u64(21);
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = (x1 + y1) * (x2 + y2);
+        let mut t4 = t0 + t1;
+        t3 = t3 - t4;
+        t4 = (y1 + z1) * (y2 + z2);
+        t4 = t4 - (t1 + t2);
+        let mut y3 = (x1 + z1) * (x2 + z2);
+        y3 = y3 - (t0 + t2);
+        t0 = t0 + t0 + t0;
+        t2 = b3 * t2;
+        let mut z3 = t1 + t2;
+        t1 = t1 - t2;
+        y3 = b3 * y3;
+        let mut x3 = t4 * y3;
+        x3 = t3 * t1 - x3;
+        y3 = y3 * t0 + t1 * z3;
+        z3 = z3 * t4 + t0 * t3;
+        ProjectivePoint { x: x3, y: y3, z: z3 }
+    }
+
+    /// a if bit is 1, b if bit is 0
+    fn select(bit: u64, a: &ProjectivePoint, b: &ProjectivePoint) -> ProjectivePoint {
+        ProjectivePoint {
+            x: Fe::select(bit, &a.x, &b.x),
+            y: Fe::select(bit, &a.y, &b.y),
+            z: Fe::select(bit, &a.z, &b.z),
+        }
+    }
+
+    fn conditional_swap(a: &mut ProjectivePoint, b: &mut ProjectivePoint, bit: u64) {
+        Fe::conditional_swap(&mut a.x, &mut b.x, bit);
+        Fe::conditional_swap(&mut a.y, &mut b.y, bit);
+        Fe::conditional_swap(&mut a.z, &mut b.z, bit);
+    }
 }
 
 impl Secp256k1 {
@@ -23,23 +105,98 @@ impl Secp256k1 {
         let p = BigInt::from(2).power(256) - BigInt::from(2).power(32) - BigInt::from(977);
         let a = BigInt::from(0);
         let b = BigInt::from(7);
-        let ec = elliptic_curve::EllipticCurve::new_raw(&a, &b, &p);
+        let ec = elliptic_curve::EllipticCurve::try_new(&a, &b, &p).unwrap();
         let gx = BigInt::from(5) * BigInt::from(10).power((12 * 3 + 2) * 2)
                    + BigInt::from(50_662_630_222_773_436_695_787_188_951_685_343_262u128) * BigInt::from(10).power(12 * 3 + 2)
                    + BigInt::from(50_603_453_777_594_175_500_187_360_389_116_729_240u128);
         let gy = BigInt::from(3) * BigInt::from(10).power((12 * 3 + 2) * 2)
                    + BigInt::from(26_705_100_207_588_169_780_830_851_305_070_431_844u128) * BigInt::from(10).power(12 * 3 + 2)
                    + BigInt::from(71_273_380_659_243_275_938_904_335_757_337_482_424u128);
-        let g = elliptic_curve::ECPoint::new(
-            &gx,
-            &gy,
-            &One::one());
+        let g = ec.point(&gx, &gy);
+
+        let n = BigInt::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", 16).unwrap();
 
         Secp256k1 {
             ec: ec,
             g: g, 
+            n,
+            table: OnceLock::new(),
         }
     }
+
+    /// k * P by Montgomery ladder over all 256 bits of k
+    /// every bit costs one complete addition and one complete doubling on fixed-width limbs,
+    /// with no branch or table index depending on k. P is not secret.
+    /// k in [0, 2^256) is used as it is, other k are reduced modulo n first.
+    pub fn multiply_scalar(&self, point: &elliptic_curve::ECPoint, k: &BigInt) -> elliptic_curve::ECPoint {
+        let k = self.scalar_limbs(k);
+        let mut r0 = ProjectivePoint::infinity();
+        let mut r1 = ProjectivePoint::from_affine(&self.ec, point);
+        for i in (0..256).rev() {
+            let bit = (k[i / 64] >> (i % 64)) & 1;
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.add(&r1);
+            r0 = r0.add(&r0);
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+        }
+        r0.to_affine(&self.ec)
+    }
+
+    /// k * G by the precomputed table of G, k as in multiply_scalar
+    /// one complete addition per 4-bit digit of k, every entry of a row is read and masked
+    /// the table is built on first call
+    pub fn multiply_generator(&self, k: &BigInt) -> elliptic_curve::ECPoint {
+        let table = self.table.get_or_init(|| self.generator_table());
+        let k = self.scalar_limbs(k);
+        let mut r = ProjectivePoint::infinity();
+        for (i, row) in table.iter().enumerate() {
+            let digit = (k[i / 16] >> (4 * (i % 16))) & 0xf;
+            let mut entry = ProjectivePoint::infinity();
+            for (j, point) in row.iter().enumerate() {
+                // 1 if j == digit
+                let bit = ((j as u64 ^ digit).wrapping_sub(1)) >> 63;
+                entry = ProjectivePoint::select(bit, point, &entry);
+            }
+            r = r.add(&entry);
+        }
+        r.to_affine(&self.ec)
+    }
+
+    fn generator_table(&self) -> Vec<[ProjectivePoint; 16]> {
+        let mut base = ProjectivePoint::from_affine(&self.ec, &self.g);
+        let mut table = Vec::with_capacity(64);
+        for _ in 0..64 {
+            let mut row = [ProjectivePoint::infinity(); 16];
+            for j in 1..16 {
+                row[j] = row[j - 1].add(&base);
+            }
+            base = row[15].add(&base);
+            table.push(row);
+        }
+        table
+    }
+
+    /// little-endian 64-bit limbs of k, every point has order n as the cofactor is 1
+    /// the conversion from BigInt depends on the length of k
+    fn scalar_limbs(&self, k: &BigInt) -> [u64; 4] {
+        let k = if k.is_negative() || k.bits() > 256 { k.mod_floor(&self.n) } else { k.clone() };
+        let (_, bytes) = k.to_bytes_le();
+        let mut limbs = [0u64; 4];
+        for (i, b) in bytes.iter().enumerate() {
+            limbs[i / 8] |= (*b as u64) << (8 * (i % 8));
+        }
+        limbs
+    }
+
+    /// point (x, y) with the given parity of y
+    pub fn lift_x(&self, x: &BigInt, odd: bool) -> Result<elliptic_curve::ECPoint> {
+        self.ec.lift_x(x, odd)
+    }
+
+    /// public key of the secret key sk
+    pub fn public_key(&self, sk: &BigInt) -> elliptic_curve::ECPoint {
+        self.multiply_generator(sk)
+    }
 }
 
 #[test]
@@ -58,6 +215,81 @@ fn secp256k1_test1() {
         "(112711660439710606056748659173929673102114977341539408544630613555209775888121, 25583027980570883691656905877401976406448868254816295069919888960541586679410)");
 }
 
+#[test]
+fn secp256k1_order_test() {
+    let curve = Secp256k1::new();
+    let ec = curve.ec;
+    let g = curve.g;
+    let n = BigInt::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", 16).unwrap();
+    assert_eq!(ec.multiply_scalar(&g, &n), elliptic_curve::ECPoint::infinity());
+    assert_eq!(ec.multiply_scalar(&g, &(&n - 1)), ec.negate(&g));
+    // k G for k = 2^128
+    assert_eq_str!(ec.multiply_scalar(&g, &BigInt::from(2).power(128)).x().value().to_str_radix(16),
+        "8f68b9d2f63b5f339239c1ad981f162ee88c5678723ea3351b7b444c9ec4c0da");
+}
+
+#[test]
+fn secp256k1_constant_time_test() {
+    let curve = Secp256k1::new();
+    let ks = [
+        BigInt::from(0),
+        BigInt::from(1),
+        BigInt::from(3),
+        &curve.n - 1,
+        curve.n.clone(),
+        BigInt::from(2).power(255) + 12345,
+        BigInt::from(2).power(256) - 1,
+        BigInt::from(2).power(256) + 3,
+        BigInt::from(-5),
+        BigInt::parse_bytes(b"C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22", 16).unwrap(),
+    ];
+    for k in ks.iter() {
+        let r = curve.ec.multiply_scalar(&curve.g, &k.mod_floor(&curve.n));
+        assert_eq!(curve.multiply_scalar(&curve.g, k), r, "k:{}", k);
+        assert_eq!(curve.multiply_generator(k), r, "k:{}", k);
+    }
+    let p = curve.multiply_generator(&BigInt::from(7));
+    assert_eq!(curve.multiply_scalar(&p, &BigInt::from(11)), curve.public_key(&BigInt::from(77)));
+    assert_eq!(curve.multiply_scalar(&elliptic_curve::ECPoint::infinity(), &BigInt::from(11)), elliptic_curve::ECPoint::infinity());
+
+    // the complete formulas cover doubling, O and P + (-P)
+    let ec = &curve.ec;
+    let g = ProjectivePoint::from_affine(ec, &curve.g);
+    let o = ProjectivePoint::infinity();
+    let minus_g = ProjectivePoint::from_affine(ec, &ec.negate(&curve.g));
+    assert_eq!(g.add(&g).to_affine(ec), ec.plus(&curve.g, &curve.g));
+    assert_eq!(g.add(&scaled_projective(ec, &p)).to_affine(ec), ec.plus(&curve.g, &p));
+    assert_eq!(o.add(&g).to_affine(ec), curve.g);
+    assert_eq!(g.add(&o).to_affine(ec), curve.g);
+    assert!(o.add(&o).to_affine(ec).is_infinity());
+    assert!(g.add(&minus_g).to_affine(ec).is_infinity());
+}
+
+#[cfg(test)]
+fn scaled_projective(ec: &elliptic_curve::EllipticCurve, point: &elliptic_curve::ECPoint) -> ProjectivePoint {
+    // a representative with Z != 1
+    let z = Fe::from_u64(12345);
+    let q = ProjectivePoint::from_affine(ec, point);
+    ProjectivePoint { x: q.x * z, y: q.y * z, z }
+}
+
+#[test]
+fn secp256k1_sec1_test() {
+    let curve = Secp256k1::new();
+    let hex = |s: &str| (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect::<Vec<u8>>();
+    let gx = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    let gy = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+    assert_eq!(curve.ec.encode_point(&curve.g, true), hex(&format!("02{}", gx)));
+    assert_eq!(curve.ec.encode_point(&curve.g, false), hex(&format!("04{}{}", gx, gy)));
+    assert_eq!(curve.ec.decode_point(&hex(&format!("02{}", gx))), Ok(curve.g.clone()));
+    assert_eq!(curve.ec.decode_point(&hex(&format!("03{}", gx))), Ok(curve.ec.negate(&curve.g)));
+    assert_eq!(curve.ec.decode_point(&hex(&format!("04{}{}", gx, gy))), Ok(curve.g.clone()));
+    assert_eq!(curve.ec.encode_point(&elliptic_curve::ECPoint::infinity(), true), vec![0]);
+    // 2 G in Jacobian coordinates is normalized
+    let g2 = curve.ec.jacobian_double(&curve.g);
+    assert_eq!(curve.ec.decode_point(&curve.ec.encode_point(&g2, true)), Ok(curve.ec.plus(&curve.g, &curve.g)));
+}
+
 #[test]
 #[ignore]
 fn secp256k1_test2() {
@@ -76,10 +308,7 @@ fn secp256k1_test2() {
     let sy = BigInt::from(9) * BigInt::from(10).power(38 * 2)
                         + BigInt::from(88118691354782330710178928771578899669u128) * BigInt::from(10).power(38)
                         + BigInt::from(23567543651789733849964475999540762862u128);
-    let s = elliptic_curve::ECPoint::new(
-        &sx,
-        &sy,
-        &One::one());
+    let s = ec.point(&sx, &sy);
     
     loop {
         let i0: u128 = rng.gen();
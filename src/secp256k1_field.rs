@@ -0,0 +1,247 @@
+// limbs are indexed together with the carries
+#![allow(clippy::needless_range_loop)]
+
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use std::{fmt, ops};
+
+/// element of the secp256k1 base field F_p, p = 2^256 - 2^32 - 977
+/// four 64-bit limbs, least significant first, always reduced into [0, p)
+/// the arithmetic has no branches or memory accesses that depend on the values
+#[derive(Debug, Clone, Copy)]
+pub struct Fe([u64; 4]);
+
+const P: [u64; 4] = [0xFFFF_FFFE_FFFF_FC2F, 0xFFFF_FFFF_FFFF_FFFF, 0xFFFF_FFFF_FFFF_FFFF, 0xFFFF_FFFF_FFFF_FFFF];
+/// 2^256 - p
+const C: u64 = 0x1_0000_03D1;
+
+/// a + b + carry, carry out
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// a - b - borrow, borrow out
+fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// all ones if bit is 1, zero if bit is 0
+fn mask(bit: u64) -> u64 {
+    0u64.wrapping_sub(bit)
+}
+
+impl Fe {
+    pub fn zero() -> Fe {
+        Fe([0; 4])
+    }
+
+    pub fn one() -> Fe {
+        Fe([1, 0, 0, 0])
+    }
+
+    pub fn from_u64(v: u64) -> Fe {
+        Fe([v, 0, 0, 0]).reduce_once(0)
+    }
+
+    /// x mod p
+    pub fn from_bigint(x: &BigInt) -> Fe {
+        let p = BigInt::from_bytes_le(Sign::Plus, &Fe(P).to_bytes_le());
+        let (_, bytes) = x.mod_floor(&p).to_bytes_le();
+        let mut limbs = [0u64; 4];
+        for (i, b) in bytes.iter().enumerate() {
+            limbs[i / 8] |= (*b as u64) << (8 * (i % 8));
+        }
+        Fe(limbs)
+    }
+
+    pub fn to_bigint(&self) -> BigInt {
+        BigInt::from_bytes_le(Sign::Plus, &self.to_bytes_le())
+    }
+
+    fn to_bytes_le(self) -> Vec<u8> {
+        self.0.iter().flat_map(|limb| limb.to_le_bytes()).collect()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().fold(0, |acc, limb| acc | limb) == 0
+    }
+
+    /// a if bit is 1, b if bit is 0
+    pub fn select(bit: u64, a: &Fe, b: &Fe) -> Fe {
+        let m = mask(bit);
+        let mut r = [0u64; 4];
+        for i in 0..4 {
+            r[i] = (a.0[i] & m) | (b.0[i] & !m);
+        }
+        Fe(r)
+    }
+
+    /// swap a and b if bit is 1
+    pub fn conditional_swap(a: &mut Fe, b: &mut Fe, bit: u64) {
+        let m = mask(bit);
+        for i in 0..4 {
+            let t = (a.0[i] ^ b.0[i]) & m;
+            a.0[i] ^= t;
+            b.0[i] ^= t;
+        }
+    }
+
+    /// v + carry 2^256 minus p if that is not negative, v + carry 2^256 must be < 2 p
+    fn reduce_once(&self, carry: u64) -> Fe {
+        // v - p = v + C - 2^256
+        let mut t = [0u64; 4];
+        let mut k = 0;
+        for i in 0..4 {
+            (t[i], k) = adc(self.0[i], if i == 0 { C } else { 0 }, k);
+        }
+        Fe::select(carry | k, &Fe(t), self)
+    }
+
+    fn add(&self, other: &Fe) -> Fe {
+        let mut s = [0u64; 4];
+        let mut carry = 0;
+        for i in 0..4 {
+            (s[i], carry) = adc(self.0[i], other.0[i], carry);
+        }
+        Fe(s).reduce_once(carry)
+    }
+
+    fn sub(&self, other: &Fe) -> Fe {
+        let mut d = [0u64; 4];
+        let mut borrow = 0;
+        for i in 0..4 {
+            (d[i], borrow) = sbb(self.0[i], other.0[i], borrow);
+        }
+        // on borrow d is a - b + 2^256, a - b + p = d - C
+        let c = C & mask(borrow);
+        let mut k = 0;
+        for i in 0..4 {
+            (d[i], k) = sbb(d[i], if i == 0 { c } else { 0 }, k);
+        }
+        Fe(d)
+    }
+
+    fn neg(&self) -> Fe {
+        Fe::zero().sub(self)
+    }
+
+    fn mul(&self, other: &Fe) -> Fe {
+        let mut r = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                let t = self.0[i] as u128 * other.0[j] as u128 + r[i + j] as u128 + carry as u128;
+                r[i + j] = t as u64;
+                carry = (t >> 64) as u64;
+            }
+            r[i + 4] = carry;
+        }
+        Fe::reduce_wide(&r)
+    }
+
+    pub fn square(&self) -> Fe {
+        self.mul(self)
+    }
+
+    /// 512-bit r mod p, 2^256 = C mod p is folded into the low half
+    fn reduce_wide(r: &[u64; 8]) -> Fe {
+        let mut t = [0u64; 4];
+        let mut carry: u128 = 0;
+        for i in 0..4 {
+            let v = r[i] as u128 + r[i + 4] as u128 * C as u128 + carry;
+            t[i] = v as u64;
+            carry = v >> 64;
+        }
+        // carry < 2^34, fold it twice, the second fold doesn't overflow
+        for _ in 0..2 {
+            let mut k = carry * C as u128;
+            for limb in t.iter_mut() {
+                let v = *limb as u128 + k;
+                *limb = v as u64;
+                k = v >> 64;
+            }
+            carry = k;
+        }
+        Fe(t).reduce_once(0)
+    }
+
+    /// a^e for a public exponent e
+    fn power(&self, e: &[u64; 4]) -> Fe {
+        let mut r = Fe::one();
+        for i in (0..256).rev() {
+            r = r.square();
+            if (e[i / 64] >> (i % 64)) & 1 == 1 {
+                r = r.mul(self);
+            }
+        }
+        r
+    }
+
+    /// 1/a by a^(p - 2), 0 for a = 0
+    pub fn inv(&self) -> Fe {
+        let mut e = P;
+        e[0] -= 2;
+        self.power(&e)
+    }
+}
+
+impl_op_ex!(+ |a: &Fe, b: &Fe| -> Fe { a.add(b) });
+impl_op_ex!(- |a: &Fe, b: &Fe| -> Fe { a.sub(b) });
+impl_op_ex!(* |a: &Fe, b: &Fe| -> Fe { a.mul(b) });
+impl_op_ex!(- |a: &Fe| -> Fe { a.neg() });
+
+impl PartialEq for Fe {
+    fn eq(&self, other: &Self) -> bool {
+        (self - other).is_zero()
+    }
+}
+impl Eq for Fe {}
+
+impl fmt::Display for Fe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_bigint())
+    }
+}
+
+#[test]
+fn secp256k1_field_test() {
+    use rand::{Rng, SeedableRng};
+    use num_traits::Zero;
+    use super::bigint::{Inverse, Power};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(10);
+    let p = Fe(P).to_bigint();
+    assert_eq!(p, BigInt::from(2).power(256) - BigInt::from(2).power(32) - 977);
+    let mut values = vec![BigInt::from(0), BigInt::from(1), BigInt::from(C), &p - 1, &p - 2, BigInt::from(u64::MAX)];
+    for _ in 0..20 {
+        let bytes: [u8; 32] = rng.gen();
+        values.push(BigInt::from_bytes_le(Sign::Plus, &bytes).mod_floor(&p));
+    }
+    for x in &values {
+        let a = Fe::from_bigint(x);
+        assert_eq!(a.to_bigint(), *x);
+        assert_eq!((-a).to_bigint(), (-x).mod_floor(&p));
+        if !x.is_zero() {
+            assert_eq!(a.inv().to_bigint(), x.inverse(&p));
+            assert_eq!(a * a.inv(), Fe::one());
+        }
+        for y in &values {
+            let b = Fe::from_bigint(y);
+            assert_eq!((a + b).to_bigint(), (x + y).mod_floor(&p), "{} {}", x, y);
+            assert_eq!((a - b).to_bigint(), (x - y).mod_floor(&p), "{} {}", x, y);
+            assert_eq!((a * b).to_bigint(), (x * y).mod_floor(&p), "{} {}", x, y);
+        }
+    }
+    assert_eq!(Fe::from_bigint(&(&p + 5)), Fe::from_u64(5));
+    assert_eq!(Fe::from_bigint(&BigInt::from(-1)).to_bigint(), &p - 1);
+    assert!(Fe::zero().inv().is_zero());
+
+    let (mut a, mut b) = (Fe::from_u64(3), Fe::from_u64(4));
+    Fe::conditional_swap(&mut a, &mut b, 0);
+    assert_eq!((a, b), (Fe::from_u64(3), Fe::from_u64(4)));
+    Fe::conditional_swap(&mut a, &mut b, 1);
+    assert_eq!((a, b), (Fe::from_u64(4), Fe::from_u64(3)));
+    assert_eq!(Fe::select(1, &a, &b), a);
+    assert_eq!(Fe::select(0, &a, &b), b);
+}
@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 use std::fmt;
 use primes::is_prime;
+use super::error::{Error, Result};
 
 /// subscripted variable
 /// if i = 0 and j = 0, omit it
@@ -84,14 +85,19 @@ pub struct SubscriptedVariableConverter {
 
 impl SubscriptedVariableConverter {
     pub fn new(p: i32) -> Self {
-        assert!(p >= 2);
+        SubscriptedVariableConverter::try_new(p).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(p: i32) -> Result<Self> {
+        if p < 2 {
+            return Err(Error::InvalidModulus(p.into()));
+        }
         if !is_prime(p as u64) {
-            panic!("p must be prime!");
+            return Err(Error::NotPrime(p.into()));
         }
-
-        SubscriptedVariableConverter {
+        Ok(SubscriptedVariableConverter {
             p: p,
-        }
+        })
     }
 
     pub fn count(&self) -> u64 {
@@ -108,8 +114,13 @@ impl SubscriptedVariableConverter {
     }
 
     pub fn index_from_variable(&self, variable: SubscriptedVariable) -> Option<u64> {
+        self.try_index_from_variable(variable).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// None for empty variable
+    pub fn try_index_from_variable(&self, variable: SubscriptedVariable) -> Result<Option<u64>> {
         if variable.empty {
-            return None as Option<u64>;
+            return Ok(None);
         }
 
         let mut index: u64 = 0;
@@ -117,7 +128,7 @@ impl SubscriptedVariableConverter {
             for j in num_iter::range(i+1, self.p+1) {
                 if variable.i == i &&
                    variable.j == j {
-                    return Some(index);
+                    return Ok(Some(index));
                 }
                 index += 1;
             }
@@ -125,38 +136,42 @@ impl SubscriptedVariableConverter {
         for i in num_iter::range(0, self.p+1) {
             if variable.i == i &&
                variable.j == i {
-                return Some(index);
+                return Ok(Some(index));
             }
             index += 1;
         }
-        panic!("invalid variable!");
+        Err(Error::InvalidVariable)
     }
 
     pub fn variable_from_index(&self, index: u64) -> SubscriptedVariable {
+        self.try_variable_from_index(index).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_variable_from_index(&self, index: u64) -> Result<SubscriptedVariable> {
         let mut local_index: i32 = 0;
         for i in num_iter::range(0, self.p+1) {
             for j in num_iter::range(i+1, self.p+1) {
                 if local_index == index as i32 {
-                    return SubscriptedVariable {
+                    return Ok(SubscriptedVariable {
                         i: i,
                         j: j,
                         empty: false,
-                    };
+                    });
                 }
                 local_index += 1;
             }
         } 
         for i in num_iter::range(0, self.p+1) {
             if local_index == index as i32 {
-                return SubscriptedVariable {
+                return Ok(SubscriptedVariable {
                     i: i,
                     j: i,
                     empty: false,
-                };
+                });
             }
             local_index += 1;
         }
-        panic!("invalid index!");
+        Err(Error::InvalidIndex(index))
     }
 }
 
@@ -221,3 +236,13 @@ fn subscripted_converter_test1() {
     }
 }
 
+#[test]
+fn subscripted_converter_try_test() {
+    assert_eq!(SubscriptedVariableConverter::try_new(4).err(), Some(Error::NotPrime(4.into())));
+    assert_eq!(SubscriptedVariableConverter::try_new(1).err(), Some(Error::InvalidModulus(1.into())));
+    let converter = SubscriptedVariableConverter::try_new(3).unwrap();
+    assert_eq!(converter.try_variable_from_index(10), Err(Error::InvalidIndex(10)));
+    let v = SubscriptedVariable { i: 2, j: 7, empty: false };
+    assert_eq!(converter.try_index_from_variable(v), Err(Error::InvalidVariable));
+    assert_eq!(converter.try_index_from_variable(SubscriptedVariable::new()), Ok(None));
+}
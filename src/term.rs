@@ -4,6 +4,7 @@ use num_traits::{Zero, One, ToPrimitive};
 use std::cmp::Ordering;
 use std::fmt;
 use std::ops; 
+use super::error::{Error, Result};
 use super::polynomial;
 use super::bigint::{Power, PowerModulo};
 use super::term_builder;
@@ -45,18 +46,7 @@ impl_op_ex!(+ |a: &Term, b: &Term| -> polynomial::Polynomial {
 });
 
 impl_op_ex!(* |a: &Term, b: &Term| -> Term {
-    if !a.variable().empty && !b.variable().empty {
-        panic!("can't multiply another variable!");
-    }
-    Term {
-        coef: &a.coef * &b.coef,
-        monomial: Monomial { 
-            xpow: a.xpow() + b.xpow(),
-            ypow: a.ypow() + b.ypow(),
-            qpow: a.qpow() + b.qpow(),
-            variable: if !a.variable().empty { a.variable() } else { b.variable() },
-        },
-    }
+    a.checked_mul(b).unwrap_or_else(|e| panic!("{}", e))
 });
 
 impl_op_ex!(- |a: &Term, b: &Term| -> polynomial::Polynomial {
@@ -64,16 +54,7 @@ impl_op_ex!(- |a: &Term, b: &Term| -> polynomial::Polynomial {
 });
 
 impl_op_ex!(/ |a: &Term, b: &Term| -> Term {
-    if !b.variable().empty {
-        panic!("b is not zero!");
-    }
-    term_builder::TermBuilder::new()
-        .coef(a.coef.div_floor(&b.coef))
-        .xpow(a.xpow() - b.xpow())
-        .ypow(a.ypow() - b.ypow())
-        .qpow(a.qpow() - b.qpow())
-        .variable(a.variable())
-        .build()
+    a.checked_div(b).unwrap_or_else(|e| panic!("{}", e))
 });
 
 impl_op_ex!(- |a: &Term| -> Term {
@@ -89,9 +70,6 @@ impl_op_ex!(- |a: &Term| -> Term {
 /// Term ^ n
 impl Power<i32> for Term {
     fn power(&self, n: i32) -> Self {
-        if !self.variable().empty {
-            panic!("variable can't power");
-        }
         self.power(BigInt::from(n))
     }
 }
@@ -105,9 +83,9 @@ impl Power<BigInt> for Term {
 impl<'a> Power<&'a BigInt> for Term {
     fn power(&self, n: &BigInt) -> Self {
         if !self.variable().empty {
-            panic!("can't power non zero variable!");
+            panic!("{}", Error::VariablePower);
         }
-        let nn: i32 = n.to_i32().unwrap();
+        let nn: i32 = n.to_i32().unwrap_or_else(|| panic!("{}", Error::ExponentOverflow));
         term_builder::TermBuilder::new()
           .coef(self.coef.power(n))
           .xpow(self.xpow() * nn)
@@ -155,6 +133,55 @@ impl Term {
         }
     }
 
+    pub fn checked_mul(&self, other: &Term) -> Result<Term> {
+        if !self.variable().empty && !other.variable().empty {
+            return Err(Error::VariableProduct);
+        }
+        Ok(Term {
+            coef: &self.coef * &other.coef,
+            monomial: Monomial { 
+                xpow: self.xpow() + other.xpow(),
+                ypow: self.ypow() + other.ypow(),
+                qpow: self.qpow() + other.qpow(),
+                variable: if !self.variable().empty { self.variable() } else { other.variable() },
+            },
+        })
+    }
+
+    pub fn checked_div(&self, other: &Term) -> Result<Term> {
+        if !other.variable().empty {
+            return Err(Error::VariableDivisor);
+        }
+        if other.coef.is_zero() {
+            return Err(Error::DivisionByZero);
+        }
+        Ok(term_builder::TermBuilder::new()
+            .coef(self.coef.div_floor(&other.coef))
+            .xpow(self.xpow() - other.xpow())
+            .ypow(self.ypow() - other.ypow())
+            .qpow(self.qpow() - other.qpow())
+            .variable(self.variable())
+            .build())
+    }
+
+    /// self ^ n, n >= 0
+    pub fn checked_power(&self, n: &BigInt) -> Result<Term> {
+        if !self.variable().empty {
+            return Err(Error::VariablePower);
+        }
+        if n < &Zero::zero() {
+            return Err(Error::NegativeExponent);
+        }
+        let nn = n.to_i32().ok_or(Error::ExponentOverflow)?;
+        let overflow = |v: i32| v.checked_mul(nn).ok_or(Error::ExponentOverflow);
+        Ok(term_builder::TermBuilder::new()
+          .coef(self.coef.power(n))
+          .xpow(overflow(self.xpow())?)
+          .ypow(overflow(self.ypow())?)
+          .qpow(overflow(self.qpow())?)
+          .build())
+    }
+
     pub fn xpow(&self) -> i32 {
         self.monomial.xpow
     }
@@ -542,3 +569,18 @@ fn unit_test() {
     assert_eq_str!(u8, "- x^4 y^2"); 
 }
 
+
+#[test]
+fn term_checked_test() {
+    use super::error::Error;
+    let a = term_builder::TermBuilder::new().coef(3).variable_ij(1, 2).build();
+    let b = term_builder::TermBuilder::new().coef(2).variable_ij(0, 1).build();
+    let x = term_builder::TermBuilder::new().coef(2).xpow(1).build();
+    assert_eq!(a.checked_mul(&b), Err(Error::VariableProduct));
+    assert_eq!(x.checked_div(&b), Err(Error::VariableDivisor));
+    assert_eq!(x.checked_div(&Term::new()), Err(Error::DivisionByZero));
+    assert_eq!(a.checked_power(&BigInt::from(2)), Err(Error::VariablePower));
+    assert_eq!(x.checked_power(&BigInt::from(-1)), Err(Error::NegativeExponent));
+    assert_eq_str!(a.checked_mul(&x).unwrap(), "6 x c_1_2");
+    assert_eq_str!(x.checked_power(&BigInt::from(3)).unwrap(), "8 x^3");
+}